@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 
+use super::hint::HintProgram;
 use crate::native_types::{Expression, Witness};
 use crate::OPCODE;
 
@@ -40,6 +41,77 @@ impl Gate {
             _ => panic!("tried to convert a non arithmetic gate to an Expression struct"),
         }
     }
+
+    /// The name of the gate, as written in the textual format of ACIR
+    pub fn name(&self) -> &str {
+        match self {
+            Gate::Arithmetic(_) => "arithmetic",
+            Gate::Range(..) => "range",
+            Gate::And(_) => "and",
+            Gate::Xor(_) => "xor",
+            Gate::GadgetCall(call) => call.name.name(),
+            Gate::Directive(directive) => directive.name(),
+        }
+    }
+
+    /// Returns every witness which is read or written by the gate
+    pub fn witnesses(&self) -> Vec<Witness> {
+        let mut gate = self.clone();
+        gate.witnesses_mut().into_iter().map(|witness| *witness).collect()
+    }
+
+    /// Returns every witness which is read or written by the gate, so that they can be renumbered
+    pub fn witnesses_mut(&mut self) -> Vec<&mut Witness> {
+        let mut witnesses = Vec::new();
+        match self {
+            Gate::Arithmetic(expression) => push_expression(expression, &mut witnesses),
+            Gate::Range(witness, _) => witnesses.push(witness),
+            Gate::And(AndGate { a, b, result, .. }) | Gate::Xor(XorGate { a, b, result, .. }) => {
+                witnesses.extend([a, b, result])
+            }
+            Gate::GadgetCall(call) => {
+                witnesses.extend(call.inputs.iter_mut().map(|input| &mut input.witness));
+                witnesses.extend(call.outputs.iter_mut());
+            }
+            Gate::Directive(directive) => match directive {
+                Directive::Invert { x, result } => witnesses.extend([x, result]),
+                Directive::Quotient { a, b, q, r } => {
+                    push_expression(a, &mut witnesses);
+                    push_expression(b, &mut witnesses);
+                    witnesses.extend([q, r]);
+                }
+                Directive::Truncate { a, b, c, .. } => witnesses.extend([a, b, c]),
+                Directive::Oddrange { a, b, r, .. } => witnesses.extend([a, b, r]),
+                Directive::Split { a, b, .. } => {
+                    witnesses.push(a);
+                    witnesses.extend(b.iter_mut());
+                }
+                Directive::Hint { inputs, outputs, .. } => {
+                    inputs.iter_mut().for_each(|input| push_expression(input, &mut witnesses));
+                    witnesses.extend(outputs.iter_mut());
+                }
+                Directive::PermutationSort { inputs, bits, .. } => {
+                    inputs
+                        .iter_mut()
+                        .flatten()
+                        .for_each(|input| push_expression(input, &mut witnesses));
+                    witnesses.extend(bits.iter_mut());
+                }
+                Directive::Log { inputs, predicate, .. } => {
+                    inputs.iter_mut().for_each(|input| push_expression(input, &mut witnesses));
+                    push_expression(predicate, &mut witnesses);
+                }
+            },
+        }
+        witnesses
+    }
+}
+
+fn push_expression<'a>(expression: &'a mut Expression, witnesses: &mut Vec<&'a mut Witness>) {
+    for (_, lhs, rhs) in &mut expression.mul_terms {
+        witnesses.extend([lhs, rhs]);
+    }
+    witnesses.extend(expression.linear_combinations.iter_mut().map(|(_, witness)| witness));
 }
 
 impl std::fmt::Debug for Gate {
@@ -101,6 +173,32 @@ impl std::fmt::Debug for Gate {
                     b.last().unwrap().witness_index(),
                 )
             }
+            Gate::Directive(Directive::Hint { inputs, outputs, program }) => {
+                let outputs: Vec<_> =
+                    outputs.iter().map(|w| format!("x{}", w.witness_index())).collect();
+                let inputs: Vec<_> = inputs.iter().map(|i| i.to_string()).collect();
+                write!(
+                    f,
+                    "Hint: ({}) = hint program of {} instructions ({})",
+                    outputs.join(", "),
+                    program.instructions.len(),
+                    inputs.join(", ")
+                )
+            }
+            Gate::Directive(Directive::PermutationSort { inputs, bits, sort_by }) => {
+                write!(
+                    f,
+                    "PermutationSort: {} tuples sorted by {:?} into x{}...x{}",
+                    inputs.len(),
+                    sort_by,
+                    bits.first().unwrap().witness_index(),
+                    bits.last().unwrap().witness_index(),
+                )
+            }
+            Gate::Directive(Directive::Log { format, inputs, predicate }) => {
+                let inputs: Vec<_> = inputs.iter().map(|i| i.to_string()).collect();
+                write!(f, "Log: {:?} ({}) if {}", format, inputs.join(", "), predicate)
+            }
         }
     }
 }
@@ -122,6 +220,32 @@ pub enum Directive {
 
     //bit decomposition of a: a=\sum b[i]*2^i
     Split { a: Witness, b: Vec<Witness>, bit_size: u32 },
+
+    //Runs the hint program on the values of the inputs and stores its results in the outputs
+    Hint { inputs: Vec<Expression>, outputs: Vec<Witness>, program: HintProgram },
+
+    //Sorts the input tuples by the elements at the sort_by positions and stores in bits the control bits of
+    //the Benes network which routes the inputs into sorted order. The number of tuples must be a power of two.
+    PermutationSort { inputs: Vec<Vec<Expression>>, bits: Vec<Witness>, sort_by: Vec<u32> },
+
+    //Prints the format string when the predicate is not zero, where each placeholder {i} is replaced by the value of the i-th input
+    //and {{ and }} are the escaped braces
+    Log { format: String, inputs: Vec<Expression>, predicate: Expression },
+}
+
+impl Directive {
+    pub fn name(&self) -> &str {
+        match self {
+            Directive::Invert { .. } => "invert",
+            Directive::Quotient { .. } => "quotient",
+            Directive::Truncate { .. } => "truncate",
+            Directive::Oddrange { .. } => "oddrange",
+            Directive::Split { .. } => "split",
+            Directive::Hint { .. } => "hint",
+            Directive::PermutationSort { .. } => "permutation_sort",
+            Directive::Log { .. } => "log",
+        }
+    }
 }
 
 // Note: Some gadgets will not use all of the witness
@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+
+use noir_field::FieldElement;
+
+/// A register of the hint interpreter. Every register holds a single field element.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Register(pub u32);
+
+/// Describes how the values held in registers are interpreted by an operation
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HintNumeric {
+    Field,
+    Unsigned(u32),
+    Signed(u32), // two's complement representation
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HintBinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div, // Field division for HintNumeric::Field, euclidian division otherwise
+    Rem,
+    Eq,
+    Lt,
+    Le,
+    And,
+    Or,
+    Xor,
+    Shl,
+    Shr,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HintOp {
+    // destination = value
+    Const {
+        destination: Register,
+        value: FieldElement,
+    },
+    // destination = source
+    Mov {
+        destination: Register,
+        source: Register,
+    },
+    // destination = lhs op rhs, integer results wrap around the bit size of the numeric type
+    Binary {
+        op: HintBinaryOp,
+        numeric: HintNumeric,
+        lhs: Register,
+        rhs: Register,
+        destination: Register,
+    },
+    // destination = !source, a bitwise not for integers and a boolean not for Unsigned(1)
+    Not {
+        numeric: HintNumeric,
+        source: Register,
+        destination: Register,
+    },
+    // destination = source mod 2^bit_size
+    Truncate {
+        source: Register,
+        destination: Register,
+        bit_size: u32,
+    },
+    // destination = register[base + index], used for arrays which are laid out in consecutive registers
+    Load {
+        destination: Register,
+        base: Register,
+        index: Register,
+    },
+    // register[base + index] = source
+    Store {
+        base: Register,
+        index: Register,
+        source: Register,
+    },
+    // Continues the execution from the instruction at position target
+    Jump {
+        target: usize,
+    },
+    // Jumps to the instruction at position target if condition is zero
+    JumpIfNot {
+        condition: Register,
+        target: usize,
+    },
+    // Halts the execution with a failure, e.g when a constrain does not hold
+    Trap,
+}
+
+/// A self-contained program which is executed by the ACVM in order to compute the outputs of a Hint directive.
+/// The program starts by copying its inputs into `inputs` and runs until the instruction pointer goes past
+/// the last instruction, at which point the `outputs` registers hold the results.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HintProgram {
+    pub num_registers: u32,
+    pub inputs: Vec<Register>,
+    pub outputs: Vec<Register>,
+    pub instructions: Vec<HintOp>,
+}
+
+impl std::fmt::Display for Register {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "r{}", self.0)
+    }
+}
+
+impl std::fmt::Display for HintOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HintOp::Const { destination, value } => write!(f, "{} = {}", destination, value),
+            HintOp::Mov { destination, source } => write!(f, "{} = {}", destination, source),
+            HintOp::Binary { op, numeric, lhs, rhs, destination } => {
+                write!(f, "{} = {:?}.{:?} {}, {}", destination, numeric, op, lhs, rhs)
+            }
+            HintOp::Not { numeric, source, destination } => {
+                write!(f, "{} = {:?}.Not {}", destination, numeric, source)
+            }
+            HintOp::Truncate { source, destination, bit_size } => {
+                write!(f, "{} = truncate {} to {} bits", destination, source, bit_size)
+            }
+            HintOp::Load { destination, base, index } => {
+                write!(f, "{} = {}[{}]", destination, base, index)
+            }
+            HintOp::Store { base, index, source } => write!(f, "{}[{}] = {}", base, index, source),
+            HintOp::Jump { target } => write!(f, "jump {}", target),
+            HintOp::JumpIfNot { condition, target } => {
+                write!(f, "jump {} if not {}", target, condition)
+            }
+            HintOp::Trap => write!(f, "trap"),
+        }
+    }
+}
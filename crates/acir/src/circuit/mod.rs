@@ -1,16 +1,16 @@
 pub mod gate;
+pub mod hint;
+pub mod text;
+mod validation;
 
 pub use gate::Gate;
 use noir_field::FieldElement;
+pub use validation::ValidationError;
 
 use crate::native_types::Witness;
-use rmp_serde;
+use crate::serialization::{self, SerializationError, CIRCUIT_MAGIC};
 use serde::{Deserialize, Serialize};
 
-use flate2::bufread::{DeflateDecoder, DeflateEncoder};
-use flate2::Compression;
-use std::io::prelude::*;
-
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Circuit {
     pub current_witness_index: u32,
@@ -23,19 +23,13 @@ impl Circuit {
         self.current_witness_index + 1
     }
 
-    pub fn from_bytes(bytes: &[u8]) -> Circuit {
-        let mut deflater = DeflateDecoder::new(bytes);
-        let mut buf_d = Vec::new();
-        deflater.read_to_end(&mut buf_d).unwrap();
-        rmp_serde::from_slice(buf_d.as_slice()).unwrap()
+    /// Decodes a circuit written by `to_bytes`, migrating it if it was written with an older format version
+    pub fn from_bytes(bytes: &[u8]) -> Result<Circuit, SerializationError> {
+        serialization::deserialize(CIRCUIT_MAGIC, bytes)
     }
 
     pub fn to_bytes(&self) -> Vec<u8> {
-        let buf = rmp_serde::to_vec(&self).unwrap();
-        let mut deflater = DeflateEncoder::new(buf.as_slice(), Compression::best());
-        let mut buf_c = Vec::new();
-        deflater.read_to_end(&mut buf_c).unwrap();
-        buf_c
+        serialization::serialize(CIRCUIT_MAGIC, self)
     }
 }
 
@@ -65,6 +59,7 @@ impl Default for Selector {
 mod test {
     use super::{gate::AndGate, Circuit, Gate, PublicInputs};
     use crate::native_types::Witness;
+    use crate::serialization::{SerializationError, ACIR_FORMAT_VERSION};
     use noir_field::FieldElement;
 
     #[test]
@@ -119,7 +114,38 @@ mod test {
         let bytes = circuit.to_bytes();
         println!("bytes: {:?}", bytes);
 
-        let deserialized = Circuit::from_bytes(bytes.as_slice());
+        let deserialized = Circuit::from_bytes(bytes.as_slice()).unwrap();
         assert_eq!(circuit, deserialized);
     }
+
+    #[test]
+    fn test_versioned_bytes() {
+        let circuit = Circuit {
+            current_witness_index: 2,
+            gates: vec![Gate::Range(Witness(1), 8)],
+            public_inputs: PublicInputs(vec![Witness(2)]),
+        };
+        let bytes = circuit.to_bytes();
+        assert_eq!(&bytes[..4], b"ACIR");
+        assert_eq!(bytes[4..6], ACIR_FORMAT_VERSION.to_le_bytes());
+
+        // Circuits written before the header was introduced are still readable
+        let legacy = &bytes[6..];
+        assert_eq!(Circuit::from_bytes(legacy).unwrap(), circuit);
+
+        let mut future = bytes.clone();
+        future[4..6].copy_from_slice(&(ACIR_FORMAT_VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            Circuit::from_bytes(&future),
+            Err(SerializationError::UnsupportedVersion { .. })
+        ));
+
+        let witness = Witness::to_bytes(&std::collections::BTreeMap::new());
+        assert!(matches!(
+            Circuit::from_bytes(&witness),
+            Err(SerializationError::WrongArtifact { .. })
+        ));
+        assert!(matches!(Circuit::from_bytes(&bytes[..5]), Err(SerializationError::Truncated)));
+        assert!(Circuit::from_bytes(&[1, 2, 3]).is_err());
+    }
 }
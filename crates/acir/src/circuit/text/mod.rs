@@ -0,0 +1,221 @@
+//! A textual format for circuits, which can be written by hand and reviewed in diffs.
+//!
+//! A circuit starts with its header and is followed by one gate per line:
+//!
+//! ```text
+//! current_witness_index 4
+//! public_inputs [x1]
+//! arithmetic(1*x1*x2 + -1*x3 + 0)
+//! range(x3, 8)
+//! and(x1, x2, x4, 8)
+//! gadget(sha256, [x1:8, x2:8], [x3, x4])
+//! ```
+//!
+//! Witnesses are written `x<index>` and hint registers `r<index>`. Field elements are written in decimal,
+//! as the negation of a decimal (`-1` is the field element p-1), or in hexadecimal with the `0x` prefix.
+//! An expression is written in parentheses as its multiplication terms, then its linear terms, then its
+//! constant, in their order: `(q*xi*xj + ... + q*xi + ... + q_c)`. Coefficients are always written, so that
+//! the printed expression is exactly the stored one.
+//!
+//! The arguments of every gate and directive follow the order of the fields of its Rust definition:
+//! - `arithmetic(expression)`
+//! - `range(witness, num_bits)`
+//! - `and(a, b, result, num_bits)` and `xor(a, b, result, num_bits)`
+//! - `gadget(name, [witness:num_bits, ...], [outputs])`
+//! - `invert(x, result)`
+//! - `quotient(a, b, q, r)` where `a` and `b` are expressions
+//! - `truncate(a, b, c, bit_size)`
+//! - `oddrange(a, b, r, bit_size)`
+//! - `split(a, [b], bit_size)`
+//! - `hint([inputs], [outputs], program(num_registers, [inputs], [outputs], [instructions]))`
+//! - `permutation_sort([[expressions], ...], [bits], [sort_by])`
+//! - `log("format", [inputs], predicate)` where the format is a string with the escapes of Rust
+//!
+//! The hint instructions are written the same way:
+//! `const(destination, value)`, `mov(destination, source)`, `binary(op, numeric, lhs, rhs, destination)`,
+//! `not(numeric, source, destination)`, `truncate(source, destination, bit_size)`, `load(destination, base, index)`,
+//! `store(base, index, source)`, `jump(target)`, `jump_if_not(condition, target)` and `trap`.
+//! Binary operations are named after the `HintBinaryOp` variants in lower case, and numeric types
+//! are written `field`, `u<bits>` or `i<bits>`.
+//!
+//! Whitespace is not significant and `//` starts a comment which runs to the end of the line.
+mod parser;
+mod printer;
+
+pub use parser::ParseError;
+
+use super::Circuit;
+
+impl std::fmt::Display for Circuit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        printer::print_circuit(self, f)
+    }
+}
+
+impl std::str::FromStr for Circuit {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Circuit, ParseError> {
+        parser::parse_circuit(text)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::circuit::{
+        gate::{AndGate, Directive, GadgetCall, GadgetInput, XorGate},
+        hint::{HintBinaryOp, HintNumeric, HintOp, HintProgram, Register},
+        Circuit, Gate, PublicInputs,
+    };
+    use crate::native_types::{Expression, Witness};
+    use crate::OPCODE;
+    use noir_field::FieldElement;
+
+    fn expression(mul: i128, linear: i128, q_c: i128) -> Expression {
+        Expression {
+            mul_terms: vec![(FieldElement::from(mul), Witness(1), Witness(2))],
+            linear_combinations: vec![
+                (FieldElement::from(linear), Witness(3)),
+                (FieldElement::zero(), Witness(1)),
+            ],
+            q_c: FieldElement::from(q_c),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let program = HintProgram {
+            num_registers: 3,
+            inputs: vec![Register(0)],
+            outputs: vec![Register(2)],
+            instructions: vec![
+                HintOp::Const { destination: Register(1), value: -FieldElement::one() },
+                HintOp::Binary {
+                    op: HintBinaryOp::Shl,
+                    numeric: HintNumeric::Signed(8),
+                    lhs: Register(0),
+                    rhs: Register(1),
+                    destination: Register(2),
+                },
+                HintOp::Not {
+                    numeric: HintNumeric::Unsigned(1),
+                    source: Register(2),
+                    destination: Register(2),
+                },
+                HintOp::JumpIfNot { condition: Register(2), target: 5 },
+                HintOp::Trap,
+            ],
+        };
+        let circuit = Circuit {
+            current_witness_index: 9,
+            gates: vec![
+                Gate::Arithmetic(expression(1, -1, 0)),
+                Gate::Arithmetic(Expression::default()),
+                Gate::Range(Witness(1), 8),
+                Gate::And(AndGate {
+                    a: Witness(1),
+                    b: Witness(2),
+                    result: Witness(3),
+                    num_bits: 4,
+                }),
+                Gate::Xor(XorGate {
+                    a: Witness(1),
+                    b: Witness(2),
+                    result: Witness(3),
+                    num_bits: 4,
+                }),
+                Gate::GadgetCall(GadgetCall {
+                    name: OPCODE::SHA256,
+                    inputs: vec![GadgetInput { witness: Witness(1), num_bits: 8 }],
+                    outputs: vec![Witness(4), Witness(5)],
+                }),
+                Gate::Directive(Directive::Invert { x: Witness(1), result: Witness(2) }),
+                Gate::Directive(Directive::Quotient {
+                    a: expression(2, 3, 4),
+                    b: Expression::from_field(
+                        FieldElement::from_hex("0x1234567890abcdef1234567890abcdef1234").unwrap(),
+                    ),
+                    q: Witness(5),
+                    r: Witness(6),
+                }),
+                Gate::Directive(Directive::Truncate {
+                    a: Witness(1),
+                    b: Witness(2),
+                    c: Witness(3),
+                    bit_size: 8,
+                }),
+                Gate::Directive(Directive::Oddrange {
+                    a: Witness(1),
+                    b: Witness(2),
+                    r: Witness(3),
+                    bit_size: 8,
+                }),
+                Gate::Directive(Directive::Split {
+                    a: Witness(1),
+                    b: vec![Witness(2), Witness(3)],
+                    bit_size: 2,
+                }),
+                Gate::Directive(Directive::Split { a: Witness(1), b: vec![], bit_size: 0 }),
+                Gate::Directive(Directive::Hint {
+                    inputs: vec![expression(1, 1, 1)],
+                    outputs: vec![Witness(7)],
+                    program,
+                }),
+                Gate::Directive(Directive::PermutationSort {
+                    inputs: vec![vec![expression(1, 0, 0), Expression::one()], vec![]],
+                    bits: vec![Witness(8)],
+                    sort_by: vec![0],
+                }),
+                Gate::Directive(Directive::Log {
+                    format: "\"{0}\" {{}}\n\t\\ é".to_string(),
+                    inputs: vec![expression(0, 1, 0)],
+                    predicate: Expression::one(),
+                }),
+            ],
+            public_inputs: PublicInputs(vec![Witness(1), Witness(2)]),
+        };
+
+        let text = circuit.to_string();
+        let parsed: Circuit = text.parse().unwrap();
+        assert_eq!(parsed, circuit);
+        assert_eq!(parsed.to_string(), text);
+    }
+
+    #[test]
+    fn hand_written() {
+        let text = "
+            // x3 = x1 * x2
+            current_witness_index 3
+            public_inputs []
+            arithmetic(1*x1*x2 + -1*x3 + 0)
+            gadget(pedersen, [x1:254], [x2, x3])
+            quotient((1*x1 + 0), (0xff), x2, x3)
+        ";
+        let circuit: Circuit = text.parse().unwrap();
+        assert_eq!(circuit.gates.len(), 3);
+        assert_eq!(
+            circuit.gates[0],
+            Gate::Arithmetic(Expression {
+                mul_terms: vec![(FieldElement::one(), Witness(1), Witness(2))],
+                linear_combinations: vec![(-FieldElement::one(), Witness(3))],
+                q_c: FieldElement::zero(),
+            })
+        );
+        assert!(matches!(
+            &circuit.gates[2],
+            Gate::Directive(Directive::Quotient { b, .. }) if b.q_c == FieldElement::from(255_i128)
+        ));
+    }
+
+    #[test]
+    fn errors() {
+        let error =
+            "current_witness_index 1\npublic_inputs []\nrange(x1)".parse::<Circuit>().unwrap_err();
+        assert_eq!(error.line, 3);
+        assert!("current_witness_index 1\npublic_inputs []\nfoo(x1)".parse::<Circuit>().is_err());
+        assert!("current_witness_index 1\npublic_inputs [y1]".parse::<Circuit>().is_err());
+        assert!("current_witness_index 1\npublic_inputs []\nlog(\"a, [], (0))"
+            .parse::<Circuit>()
+            .is_err());
+    }
+}
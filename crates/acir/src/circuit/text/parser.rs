@@ -0,0 +1,538 @@
+use noir_field::FieldElement;
+
+use super::printer::binary_op_name;
+use crate::circuit::gate::{AndGate, Directive, GadgetCall, GadgetInput, Gate, XorGate};
+use crate::circuit::hint::{HintBinaryOp, HintNumeric, HintOp, HintProgram, Register};
+use crate::circuit::{Circuit, PublicInputs};
+use crate::native_types::{Expression, Witness};
+use crate::OPCODE;
+
+/// An error in a textual circuit, with the line where it occurs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    Number(String),
+    Str(String),
+    Punct(char),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Ident(ident) | Token::Number(ident) => write!(f, "{}", ident),
+            Token::Str(string) => write!(f, "{:?}", string),
+            Token::Punct(c) => write!(f, "{}", c),
+        }
+    }
+}
+
+pub(super) fn parse_circuit(text: &str) -> Result<Circuit, ParseError> {
+    let mut parser = Parser { tokens: tokenize(text)?, position: 0 };
+
+    parser.expect_keyword("current_witness_index")?;
+    let current_witness_index = parser.parse_u32()?;
+    parser.expect_keyword("public_inputs")?;
+    let public_inputs = PublicInputs(parser.parse_list(Parser::parse_witness)?);
+
+    let mut gates = Vec::new();
+    while parser.peek().is_some() {
+        gates.push(parser.parse_gate()?);
+    }
+    Ok(Circuit { current_witness_index, gates, public_inputs })
+}
+
+fn tokenize(text: &str) -> Result<Vec<(Token, usize)>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => line += 1,
+            c if c.is_whitespace() => (),
+            '/' if chars.peek() == Some(&'/') => while chars.next_if(|c| *c != '\n').is_some() {},
+            '"' => {
+                let start = line;
+                let string = tokenize_string(&mut chars, &mut line)?;
+                tokens.push((Token::Str(string), start));
+            }
+            c if c.is_ascii_alphanumeric() || c == '_' => {
+                let mut word = c.to_string();
+                while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_') {
+                    word.push(c);
+                }
+                let token =
+                    if c.is_ascii_digit() { Token::Number(word) } else { Token::Ident(word) };
+                tokens.push((token, line));
+            }
+            '(' | ')' | '[' | ']' | ',' | '*' | '+' | '-' | ':' => {
+                tokens.push((Token::Punct(c), line))
+            }
+            c => return Err(ParseError { line, message: format!("unexpected character {:?}", c) }),
+        }
+    }
+    Ok(tokens)
+}
+
+// Reads a string up to its closing quote, with the escapes printed by Debug
+fn tokenize_string(
+    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+    line: &mut usize,
+) -> Result<String, ParseError> {
+    let start = *line;
+    let error = |message: &str| ParseError { line: start, message: message.to_owned() };
+    let mut string = String::new();
+    loop {
+        match chars.next().ok_or_else(|| error("unterminated string"))? {
+            '"' => return Ok(string),
+            '\\' => {
+                let c = match chars.next().ok_or_else(|| error("unterminated string"))? {
+                    'n' => '\n',
+                    't' => '\t',
+                    'r' => '\r',
+                    '0' => '\0',
+                    c @ ('\\' | '"' | '\'') => c,
+                    'u' => {
+                        if chars.next() != Some('{') {
+                            return Err(error("invalid unicode escape"));
+                        }
+                        let mut code = String::new();
+                        for c in chars.by_ref() {
+                            if c == '}' {
+                                break;
+                            }
+                            code.push(c);
+                        }
+                        u32::from_str_radix(&code, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| error("invalid unicode escape"))?
+                    }
+                    c => return Err(error(&format!("unknown escape \\{}", c))),
+                };
+                string.push(c);
+            }
+            c => {
+                if c == '\n' {
+                    *line += 1;
+                }
+                string.push(c);
+            }
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn error(&self, message: String) -> ParseError {
+        let line = match self.tokens.get(self.position) {
+            Some((_, line)) => *line,
+            None => self.tokens.last().map_or(1, |(_, line)| *line),
+        };
+        ParseError { line, message }
+    }
+
+    fn next(&mut self) -> Result<Token, ParseError> {
+        let token = self.tokens.get(self.position).map(|(token, _)| token.clone());
+        let token = token.ok_or_else(|| self.error("unexpected end of the circuit".to_owned()))?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn unexpected<T>(&mut self, expected: &str) -> Result<T, ParseError> {
+        let message = match self.peek() {
+            Some(token) => format!("expected {}, found {}", expected, token),
+            None => format!("expected {}, found the end of the circuit", expected),
+        };
+        Err(self.error(message))
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.peek() == Some(&Token::Punct(c));
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ParseError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            self.unexpected(&format!("'{}'", c))
+        }
+    }
+
+    fn parse_ident(&mut self) -> Result<String, ParseError> {
+        match self.peek() {
+            Some(Token::Ident(ident)) => {
+                let ident = ident.clone();
+                self.position += 1;
+                Ok(ident)
+            }
+            _ => self.unexpected("a name"),
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(Token::Ident(ident)) if ident == keyword => {
+                self.position += 1;
+                Ok(())
+            }
+            _ => self.unexpected(keyword),
+        }
+    }
+
+    fn parse_number<T: std::str::FromStr>(&mut self) -> Result<T, ParseError> {
+        match self.peek() {
+            Some(Token::Number(number)) => match number.parse() {
+                Ok(number) => {
+                    self.position += 1;
+                    Ok(number)
+                }
+                Err(_) => Err(self.error(format!("invalid number {}", number))),
+            },
+            _ => self.unexpected("a number"),
+        }
+    }
+
+    fn parse_u32(&mut self) -> Result<u32, ParseError> {
+        self.parse_number()
+    }
+
+    fn parse_field(&mut self) -> Result<FieldElement, ParseError> {
+        let negative = self.eat('-');
+        let number = match self.peek() {
+            Some(Token::Number(number)) => number.clone(),
+            _ => return self.unexpected("a field element"),
+        };
+        // Hexadecimal values are decoded byte by byte
+        let value = match number.strip_prefix("0x") {
+            Some(hex) if hex.len() % 2 == 1 => FieldElement::from_hex(&format!("0{}", hex)),
+            _ => FieldElement::try_from_str(&number),
+        };
+        let value = value.ok_or_else(|| self.error(format!("invalid field element {}", number)))?;
+        self.position += 1;
+        Ok(if negative { -value } else { value })
+    }
+
+    // Parses a name made of a prefix and an index, such as x1 or r2
+    fn parse_indexed(&mut self, prefix: char, expected: &str) -> Result<u32, ParseError> {
+        if let Some(Token::Ident(ident)) = self.peek() {
+            if let Some(Ok(index)) = ident.strip_prefix(prefix).map(str::parse) {
+                self.position += 1;
+                return Ok(index);
+            }
+        }
+        self.unexpected(expected)
+    }
+
+    fn parse_witness(&mut self) -> Result<Witness, ParseError> {
+        self.parse_indexed('x', "a witness").map(Witness)
+    }
+
+    fn parse_register(&mut self) -> Result<Register, ParseError> {
+        self.parse_indexed('r', "a register").map(Register)
+    }
+
+    fn parse_list<T>(
+        &mut self,
+        mut parse_item: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        if self.eat(']') {
+            return Ok(items);
+        }
+        loop {
+            items.push(parse_item(self)?);
+            if self.eat(']') {
+                return Ok(items);
+            }
+            self.expect(',')?;
+        }
+    }
+
+    fn parse_expression(&mut self) -> Result<Expression, ParseError> {
+        self.expect('(')?;
+        let mut expression = Expression::default();
+        loop {
+            let coefficient = self.parse_field()?;
+            if !self.eat('*') {
+                expression.q_c = coefficient;
+                self.expect(')')?;
+                return Ok(expression);
+            }
+            let witness = self.parse_witness()?;
+            if self.eat('*') {
+                let rhs = self.parse_witness()?;
+                expression.mul_terms.push((coefficient, witness, rhs));
+            } else {
+                expression.linear_combinations.push((coefficient, witness));
+            }
+            self.expect('+')?;
+        }
+    }
+
+    fn parse_gate(&mut self) -> Result<Gate, ParseError> {
+        let name = self.parse_ident()?;
+        if name == "arithmetic" {
+            return Ok(Gate::Arithmetic(self.parse_expression()?));
+        }
+        self.expect('(')?;
+        let gate = match name.as_str() {
+            "range" => {
+                let witness = self.parse_witness()?;
+                self.expect(',')?;
+                Gate::Range(witness, self.parse_u32()?)
+            }
+            "and" => {
+                let (a, b, result, num_bits) = self.parse_bit_operands()?;
+                Gate::And(AndGate { a, b, result, num_bits })
+            }
+            "xor" => {
+                let (a, b, result, num_bits) = self.parse_bit_operands()?;
+                Gate::Xor(XorGate { a, b, result, num_bits })
+            }
+            "gadget" => {
+                let opcode = self.parse_ident()?;
+                // AES cannot be called from programs, but it is still an opcode
+                let name = match OPCODE::lookup(&opcode) {
+                    Some(name) => name,
+                    None if opcode == OPCODE::AES.name() => OPCODE::AES,
+                    None => return Err(self.error(format!("unknown gadget {}", opcode))),
+                };
+                self.expect(',')?;
+                let inputs = self.parse_list(|parser| {
+                    let witness = parser.parse_witness()?;
+                    parser.expect(':')?;
+                    Ok(GadgetInput { witness, num_bits: parser.parse_u32()? })
+                })?;
+                self.expect(',')?;
+                let outputs = self.parse_list(Parser::parse_witness)?;
+                Gate::GadgetCall(GadgetCall { name, inputs, outputs })
+            }
+            _ => Gate::Directive(self.parse_directive(&name)?),
+        };
+        self.expect(')')?;
+        Ok(gate)
+    }
+
+    // Parses three witnesses and a bit size
+    fn parse_bit_operands(&mut self) -> Result<(Witness, Witness, Witness, u32), ParseError> {
+        let a = self.parse_witness()?;
+        self.expect(',')?;
+        let b = self.parse_witness()?;
+        self.expect(',')?;
+        let result = self.parse_witness()?;
+        self.expect(',')?;
+        Ok((a, b, result, self.parse_u32()?))
+    }
+
+    fn parse_directive(&mut self, name: &str) -> Result<Directive, ParseError> {
+        let directive = match name {
+            "invert" => {
+                let x = self.parse_witness()?;
+                self.expect(',')?;
+                Directive::Invert { x, result: self.parse_witness()? }
+            }
+            "quotient" => {
+                let a = self.parse_expression()?;
+                self.expect(',')?;
+                let b = self.parse_expression()?;
+                self.expect(',')?;
+                let q = self.parse_witness()?;
+                self.expect(',')?;
+                Directive::Quotient { a, b, q, r: self.parse_witness()? }
+            }
+            "truncate" => {
+                let (a, b, c, bit_size) = self.parse_bit_operands()?;
+                Directive::Truncate { a, b, c, bit_size }
+            }
+            "oddrange" => {
+                let (a, b, r, bit_size) = self.parse_bit_operands()?;
+                Directive::Oddrange { a, b, r, bit_size }
+            }
+            "split" => {
+                let a = self.parse_witness()?;
+                self.expect(',')?;
+                let b = self.parse_list(Parser::parse_witness)?;
+                self.expect(',')?;
+                Directive::Split { a, b, bit_size: self.parse_u32()? }
+            }
+            "hint" => {
+                let inputs = self.parse_list(Parser::parse_expression)?;
+                self.expect(',')?;
+                let outputs = self.parse_list(Parser::parse_witness)?;
+                self.expect(',')?;
+                Directive::Hint { inputs, outputs, program: self.parse_hint_program()? }
+            }
+            "permutation_sort" => {
+                let inputs =
+                    self.parse_list(|parser| parser.parse_list(Parser::parse_expression))?;
+                self.expect(',')?;
+                let bits = self.parse_list(Parser::parse_witness)?;
+                self.expect(',')?;
+                Directive::PermutationSort {
+                    inputs,
+                    bits,
+                    sort_by: self.parse_list(Parser::parse_u32)?,
+                }
+            }
+            "log" => {
+                let format = match self.next()? {
+                    Token::Str(format) => format,
+                    _ => {
+                        self.position -= 1;
+                        return self.unexpected("a format string");
+                    }
+                };
+                self.expect(',')?;
+                let inputs = self.parse_list(Parser::parse_expression)?;
+                self.expect(',')?;
+                Directive::Log { format, inputs, predicate: self.parse_expression()? }
+            }
+            _ => {
+                self.position -= 2;
+                return Err(self.error(format!("unknown gate {}", name)));
+            }
+        };
+        Ok(directive)
+    }
+
+    fn parse_hint_program(&mut self) -> Result<HintProgram, ParseError> {
+        self.expect_keyword("program")?;
+        self.expect('(')?;
+        let num_registers = self.parse_u32()?;
+        self.expect(',')?;
+        let inputs = self.parse_list(Parser::parse_register)?;
+        self.expect(',')?;
+        let outputs = self.parse_list(Parser::parse_register)?;
+        self.expect(',')?;
+        let instructions = self.parse_list(Parser::parse_hint_op)?;
+        self.expect(')')?;
+        Ok(HintProgram { num_registers, inputs, outputs, instructions })
+    }
+
+    fn parse_hint_op(&mut self) -> Result<HintOp, ParseError> {
+        let name = self.parse_ident()?;
+        if name == "trap" {
+            return Ok(HintOp::Trap);
+        }
+        self.expect('(')?;
+        let instruction = match name.as_str() {
+            "const" => {
+                let destination = self.parse_register()?;
+                self.expect(',')?;
+                HintOp::Const { destination, value: self.parse_field()? }
+            }
+            "mov" => {
+                let destination = self.parse_register()?;
+                self.expect(',')?;
+                HintOp::Mov { destination, source: self.parse_register()? }
+            }
+            "binary" => {
+                let op = self.parse_binary_op()?;
+                self.expect(',')?;
+                let numeric = self.parse_numeric()?;
+                let [lhs, rhs, destination] = self.parse_registers()?;
+                HintOp::Binary { op, numeric, lhs, rhs, destination }
+            }
+            "not" => {
+                let numeric = self.parse_numeric()?;
+                self.expect(',')?;
+                let source = self.parse_register()?;
+                self.expect(',')?;
+                HintOp::Not { numeric, source, destination: self.parse_register()? }
+            }
+            "truncate" => {
+                let source = self.parse_register()?;
+                self.expect(',')?;
+                let destination = self.parse_register()?;
+                self.expect(',')?;
+                HintOp::Truncate { source, destination, bit_size: self.parse_u32()? }
+            }
+            "load" => {
+                let destination = self.parse_register()?;
+                let [base, index] = self.parse_registers()?;
+                HintOp::Load { destination, base, index }
+            }
+            "store" => {
+                let base = self.parse_register()?;
+                let [index, source] = self.parse_registers()?;
+                HintOp::Store { base, index, source }
+            }
+            "jump" => HintOp::Jump { target: self.parse_number()? },
+            "jump_if_not" => {
+                let condition = self.parse_register()?;
+                self.expect(',')?;
+                HintOp::JumpIfNot { condition, target: self.parse_number()? }
+            }
+            _ => {
+                self.position -= 2;
+                return Err(self.error(format!("unknown hint instruction {}", name)));
+            }
+        };
+        self.expect(')')?;
+        Ok(instruction)
+    }
+
+    // Parses registers which are each preceded by a comma
+    fn parse_registers<const N: usize>(&mut self) -> Result<[Register; N], ParseError> {
+        let mut registers = [Register(0); N];
+        for register in &mut registers {
+            self.expect(',')?;
+            *register = self.parse_register()?;
+        }
+        Ok(registers)
+    }
+
+    fn parse_binary_op(&mut self) -> Result<HintBinaryOp, ParseError> {
+        use HintBinaryOp::*;
+        let name = self.parse_ident()?;
+        let ops = [Add, Sub, Mul, Div, Rem, Eq, Lt, Le, And, Or, Xor, Shl, Shr];
+        match ops.iter().find(|op| binary_op_name(**op) == name) {
+            Some(op) => Ok(*op),
+            None => {
+                self.position -= 1;
+                self.unexpected("a binary operation")
+            }
+        }
+    }
+
+    fn parse_numeric(&mut self) -> Result<HintNumeric, ParseError> {
+        let name = self.parse_ident()?;
+        let bits = |prefix| name.strip_prefix(prefix).and_then(|bits| bits.parse().ok());
+        match (name.as_str(), bits('u'), bits('i')) {
+            ("field", ..) => Ok(HintNumeric::Field),
+            (_, Some(bits), _) => Ok(HintNumeric::Unsigned(bits)),
+            (_, _, Some(bits)) => Ok(HintNumeric::Signed(bits)),
+            _ => {
+                self.position -= 1;
+                self.unexpected("a numeric type")
+            }
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use std::fmt::{Formatter, Result};
+
+use noir_field::FieldElement;
+
+use crate::circuit::gate::{Directive, Gate};
+use crate::circuit::hint::{HintBinaryOp, HintNumeric, HintOp, HintProgram};
+use crate::circuit::Circuit;
+use crate::native_types::{Expression, Witness};
+
+pub(super) fn print_circuit(circuit: &Circuit, f: &mut Formatter<'_>) -> Result {
+    writeln!(f, "current_witness_index {}", circuit.current_witness_index)?;
+    write!(f, "public_inputs ")?;
+    print_list(&circuit.public_inputs.0, f, print_witness)?;
+    writeln!(f)?;
+    for gate in &circuit.gates {
+        print_gate(gate, f)?;
+        writeln!(f)?;
+    }
+    Ok(())
+}
+
+fn print_gate(gate: &Gate, f: &mut Formatter<'_>) -> Result {
+    match gate {
+        Gate::Arithmetic(expression) => {
+            write!(f, "arithmetic")?;
+            print_expression(expression, f)
+        }
+        Gate::Range(witness, num_bits) => write!(f, "range(x{}, {})", witness.0, num_bits),
+        Gate::And(gate) => {
+            write!(f, "and(x{}, x{}, x{}, {})", gate.a.0, gate.b.0, gate.result.0, gate.num_bits)
+        }
+        Gate::Xor(gate) => {
+            write!(f, "xor(x{}, x{}, x{}, {})", gate.a.0, gate.b.0, gate.result.0, gate.num_bits)
+        }
+        Gate::GadgetCall(call) => {
+            write!(f, "gadget({}, ", call.name.name())?;
+            print_list(&call.inputs, f, |input, f| {
+                write!(f, "x{}:{}", input.witness.0, input.num_bits)
+            })?;
+            write!(f, ", ")?;
+            print_list(&call.outputs, f, print_witness)?;
+            write!(f, ")")
+        }
+        Gate::Directive(directive) => print_directive(directive, f),
+    }
+}
+
+fn print_directive(directive: &Directive, f: &mut Formatter<'_>) -> Result {
+    match directive {
+        Directive::Invert { x, result } => write!(f, "invert(x{}, x{})", x.0, result.0),
+        Directive::Quotient { a, b, q, r } => {
+            write!(f, "quotient(")?;
+            print_expression(a, f)?;
+            write!(f, ", ")?;
+            print_expression(b, f)?;
+            write!(f, ", x{}, x{})", q.0, r.0)
+        }
+        Directive::Truncate { a, b, c, bit_size } => {
+            write!(f, "truncate(x{}, x{}, x{}, {})", a.0, b.0, c.0, bit_size)
+        }
+        Directive::Oddrange { a, b, r, bit_size } => {
+            write!(f, "oddrange(x{}, x{}, x{}, {})", a.0, b.0, r.0, bit_size)
+        }
+        Directive::Split { a, b, bit_size } => {
+            write!(f, "split(x{}, ", a.0)?;
+            print_list(b, f, print_witness)?;
+            write!(f, ", {})", bit_size)
+        }
+        Directive::Hint { inputs, outputs, program } => {
+            write!(f, "hint(")?;
+            print_list(inputs, f, print_expression)?;
+            write!(f, ", ")?;
+            print_list(outputs, f, print_witness)?;
+            write!(f, ", ")?;
+            print_hint_program(program, f)?;
+            write!(f, ")")
+        }
+        Directive::PermutationSort { inputs, bits, sort_by } => {
+            write!(f, "permutation_sort(")?;
+            print_list(inputs, f, |tuple, f| print_list(tuple, f, print_expression))?;
+            write!(f, ", ")?;
+            print_list(bits, f, print_witness)?;
+            write!(f, ", ")?;
+            print_list(sort_by, f, |index, f| write!(f, "{}", index))?;
+            write!(f, ")")
+        }
+        Directive::Log { format, inputs, predicate } => {
+            write!(f, "log({:?}, ", format)?;
+            print_list(inputs, f, print_expression)?;
+            write!(f, ", ")?;
+            print_expression(predicate, f)?;
+            write!(f, ")")
+        }
+    }
+}
+
+// Hint programs are printed with one instruction per line
+fn print_hint_program(program: &HintProgram, f: &mut Formatter<'_>) -> Result {
+    write!(f, "program({}, ", program.num_registers)?;
+    print_list(&program.inputs, f, |register, f| write!(f, "{}", register))?;
+    write!(f, ", ")?;
+    print_list(&program.outputs, f, |register, f| write!(f, "{}", register))?;
+    write!(f, ", [")?;
+    for (i, instruction) in program.instructions.iter().enumerate() {
+        let separator = if i + 1 < program.instructions.len() { "," } else { "" };
+        write!(f, "\n    ")?;
+        print_hint_op(instruction, f)?;
+        write!(f, "{}", separator)?;
+    }
+    if !program.instructions.is_empty() {
+        writeln!(f)?;
+    }
+    write!(f, "])")
+}
+
+fn print_hint_op(instruction: &HintOp, f: &mut Formatter<'_>) -> Result {
+    match instruction {
+        HintOp::Const { destination, value } => {
+            write!(f, "const({}, ", destination)?;
+            print_field(*value, f)?;
+            write!(f, ")")
+        }
+        HintOp::Mov { destination, source } => write!(f, "mov({}, {})", destination, source),
+        HintOp::Binary { op, numeric, lhs, rhs, destination } => {
+            write!(f, "binary({}, ", binary_op_name(*op))?;
+            print_numeric(*numeric, f)?;
+            write!(f, ", {}, {}, {})", lhs, rhs, destination)
+        }
+        HintOp::Not { numeric, source, destination } => {
+            write!(f, "not(")?;
+            print_numeric(*numeric, f)?;
+            write!(f, ", {}, {})", source, destination)
+        }
+        HintOp::Truncate { source, destination, bit_size } => {
+            write!(f, "truncate({}, {}, {})", source, destination, bit_size)
+        }
+        HintOp::Load { destination, base, index } => {
+            write!(f, "load({}, {}, {})", destination, base, index)
+        }
+        HintOp::Store { base, index, source } => {
+            write!(f, "store({}, {}, {})", base, index, source)
+        }
+        HintOp::Jump { target } => write!(f, "jump({})", target),
+        HintOp::JumpIfNot { condition, target } => {
+            write!(f, "jump_if_not({}, {})", condition, target)
+        }
+        HintOp::Trap => write!(f, "trap"),
+    }
+}
+
+pub(super) fn binary_op_name(op: HintBinaryOp) -> &'static str {
+    match op {
+        HintBinaryOp::Add => "add",
+        HintBinaryOp::Sub => "sub",
+        HintBinaryOp::Mul => "mul",
+        HintBinaryOp::Div => "div",
+        HintBinaryOp::Rem => "rem",
+        HintBinaryOp::Eq => "eq",
+        HintBinaryOp::Lt => "lt",
+        HintBinaryOp::Le => "le",
+        HintBinaryOp::And => "and",
+        HintBinaryOp::Or => "or",
+        HintBinaryOp::Xor => "xor",
+        HintBinaryOp::Shl => "shl",
+        HintBinaryOp::Shr => "shr",
+    }
+}
+
+fn print_numeric(numeric: HintNumeric, f: &mut Formatter<'_>) -> Result {
+    match numeric {
+        HintNumeric::Field => write!(f, "field"),
+        HintNumeric::Unsigned(bits) => write!(f, "u{}", bits),
+        HintNumeric::Signed(bits) => write!(f, "i{}", bits),
+    }
+}
+
+fn print_expression(expression: &Expression, f: &mut Formatter<'_>) -> Result {
+    write!(f, "(")?;
+    for (coefficient, lhs, rhs) in &expression.mul_terms {
+        print_field(*coefficient, f)?;
+        write!(f, "*x{}*x{} + ", lhs.0, rhs.0)?;
+    }
+    for (coefficient, witness) in &expression.linear_combinations {
+        print_field(*coefficient, f)?;
+        write!(f, "*x{} + ", witness.0)?;
+    }
+    print_field(expression.q_c, f)?;
+    write!(f, ")")
+}
+
+// Values which are close to zero or to the modulus are printed in decimal, the other ones in hexadecimal
+fn print_field(value: FieldElement, f: &mut Formatter<'_>) -> Result {
+    if let Some(value) = value.try_into_u128() {
+        write!(f, "{}", value)
+    } else if let Some(value) = (-value).try_into_u128() {
+        write!(f, "-{}", value)
+    } else {
+        write!(f, "0x{}", value.to_hex())
+    }
+}
+
+fn print_witness(witness: &Witness, f: &mut Formatter<'_>) -> Result {
+    write!(f, "x{}", witness.0)
+}
+
+fn print_list<T>(
+    items: &[T],
+    f: &mut Formatter<'_>,
+    mut print_item: impl FnMut(&T, &mut Formatter<'_>) -> Result,
+) -> Result {
+    write!(f, "[")?;
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        print_item(item, f)?;
+    }
+    write!(f, "]")
+}
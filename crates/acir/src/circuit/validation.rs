@@ -0,0 +1,235 @@
+use noir_field::FieldElement;
+
+use super::gate::Gate;
+use super::Circuit;
+use crate::native_types::Witness;
+use crate::opcode::InputSize;
+use crate::OPCODE;
+
+/// A reason for which a circuit is malformed.
+/// The errors which come from a gate carry the index of that gate in the circuit
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    WitnessOutOfBounds { gate_index: usize, witness: Witness, current_witness_index: u32 },
+    GadgetInputCount { gate_index: usize, name: OPCODE, expected: u128, found: usize },
+    GadgetOutputCount { gate_index: usize, name: OPCODE, expected: u128, found: usize },
+    UndefinedGadget { gate_index: usize, name: OPCODE },
+    BitSizeTooLarge { gate_index: usize, num_bits: u32, max_bits: u32 },
+    PublicInputOutOfBounds { witness: Witness, current_witness_index: u32 },
+    UnusedPublicInput { witness: Witness },
+}
+
+impl ValidationError {
+    pub fn gate_index(&self) -> Option<usize> {
+        match self {
+            ValidationError::WitnessOutOfBounds { gate_index, .. }
+            | ValidationError::GadgetInputCount { gate_index, .. }
+            | ValidationError::GadgetOutputCount { gate_index, .. }
+            | ValidationError::UndefinedGadget { gate_index, .. }
+            | ValidationError::BitSizeTooLarge { gate_index, .. } => Some(*gate_index),
+            ValidationError::PublicInputOutOfBounds { .. }
+            | ValidationError::UnusedPublicInput { .. } => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationError::WitnessOutOfBounds { gate_index, witness, current_witness_index } => {
+                write!(
+                    f,
+                    "gate {} uses the witness x{} but the circuit only has {} witnesses",
+                    gate_index, witness.0, current_witness_index
+                )
+            }
+            ValidationError::GadgetInputCount { gate_index, name, expected, found } => write!(
+                f,
+                "gate {} calls {} with {} inputs but it takes {}",
+                gate_index, name, found, expected
+            ),
+            ValidationError::GadgetOutputCount { gate_index, name, expected, found } => write!(
+                f,
+                "gate {} calls {} with {} outputs but it returns {}",
+                gate_index, name, found, expected
+            ),
+            ValidationError::UndefinedGadget { gate_index, name } => {
+                write!(f, "gate {} calls {} which has no definition", gate_index, name)
+            }
+            ValidationError::BitSizeTooLarge { gate_index, num_bits, max_bits } => write!(
+                f,
+                "gate {} works on {} bits but field elements only have {} bits",
+                gate_index, num_bits, max_bits
+            ),
+            ValidationError::PublicInputOutOfBounds { witness, current_witness_index } => write!(
+                f,
+                "the public input x{} is not a witness of the circuit, which has {} witnesses",
+                witness.0, current_witness_index
+            ),
+            ValidationError::UnusedPublicInput { witness } => {
+                write!(f, "the public input x{} is not used by any gate", witness.0)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl Circuit {
+    /// Checks that the circuit is well formed, and returns every problem found if it is not
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        let mut used_witnesses = std::collections::BTreeSet::new();
+
+        for (gate_index, gate) in self.gates.iter().enumerate() {
+            for witness in gate.witnesses() {
+                if witness.0 > self.current_witness_index {
+                    errors.push(ValidationError::WitnessOutOfBounds {
+                        gate_index,
+                        witness,
+                        current_witness_index: self.current_witness_index,
+                    });
+                }
+                used_witnesses.insert(witness);
+            }
+            validate_gate(gate_index, gate, &mut errors);
+        }
+
+        for &witness in &self.public_inputs.0 {
+            if witness.0 > self.current_witness_index {
+                errors.push(ValidationError::PublicInputOutOfBounds {
+                    witness,
+                    current_witness_index: self.current_witness_index,
+                });
+            } else if !used_witnesses.contains(&witness) {
+                errors.push(ValidationError::UnusedPublicInput { witness });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+// Checks the sizes of a gate, its witnesses are checked by the caller
+fn validate_gate(gate_index: usize, gate: &Gate, errors: &mut Vec<ValidationError>) {
+    let max_bits = FieldElement::max_num_bits();
+    let num_bits = match gate {
+        Gate::Range(_, num_bits) => *num_bits,
+        Gate::And(gate) => gate.num_bits,
+        Gate::Xor(gate) => gate.num_bits,
+        Gate::GadgetCall(call) => {
+            // AES is the only opcode which does not have a definition yet
+            if call.name == OPCODE::AES {
+                errors.push(ValidationError::UndefinedGadget { gate_index, name: call.name });
+                return;
+            }
+            let definition = call.name.definition();
+            if let InputSize::Fixed(expected) = definition.input_size {
+                if call.inputs.len() as u128 != expected {
+                    errors.push(ValidationError::GadgetInputCount {
+                        gate_index,
+                        name: call.name,
+                        expected,
+                        found: call.inputs.len(),
+                    });
+                }
+            }
+            if call.outputs.len() as u128 != definition.output_size.0 {
+                errors.push(ValidationError::GadgetOutputCount {
+                    gate_index,
+                    name: call.name,
+                    expected: definition.output_size.0,
+                    found: call.outputs.len(),
+                });
+            }
+            return;
+        }
+        Gate::Arithmetic(_) | Gate::Directive(_) => return,
+    };
+    if num_bits > max_bits {
+        errors.push(ValidationError::BitSizeTooLarge { gate_index, num_bits, max_bits });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ValidationError;
+    use crate::circuit::gate::{GadgetCall, GadgetInput};
+    use crate::circuit::{Circuit, Gate, PublicInputs};
+    use crate::native_types::{Expression, Witness};
+    use crate::OPCODE;
+    use noir_field::FieldElement;
+
+    #[test]
+    fn valid_circuit() {
+        let circuit = Circuit {
+            current_witness_index: 3,
+            gates: vec![
+                Gate::Arithmetic(&Expression::from(&Witness(1)) - &Witness(3)),
+                Gate::Range(Witness(2), 8),
+                Gate::GadgetCall(GadgetCall {
+                    name: OPCODE::FixedBaseScalarMul,
+                    inputs: vec![GadgetInput { witness: Witness(1), num_bits: 254 }],
+                    outputs: vec![Witness(2), Witness(3)],
+                }),
+            ],
+            public_inputs: PublicInputs(vec![Witness(1)]),
+        };
+        assert_eq!(circuit.validate(), Ok(()));
+    }
+
+    #[test]
+    fn invalid_circuit() {
+        let circuit = Circuit {
+            current_witness_index: 2,
+            gates: vec![
+                Gate::Range(Witness(3), FieldElement::max_num_bits() + 1),
+                Gate::GadgetCall(GadgetCall {
+                    name: OPCODE::ToBits,
+                    inputs: vec![GadgetInput { witness: Witness(1), num_bits: 8 }],
+                    outputs: vec![],
+                }),
+            ],
+            public_inputs: PublicInputs(vec![Witness(2), Witness(5)]),
+        };
+        let errors = circuit.validate().unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError::WitnessOutOfBounds {
+                    gate_index: 0,
+                    witness: Witness(3),
+                    current_witness_index: 2
+                },
+                ValidationError::BitSizeTooLarge {
+                    gate_index: 0,
+                    num_bits: FieldElement::max_num_bits() + 1,
+                    max_bits: FieldElement::max_num_bits()
+                },
+                ValidationError::GadgetInputCount {
+                    gate_index: 1,
+                    name: OPCODE::ToBits,
+                    expected: 2,
+                    found: 1
+                },
+                ValidationError::GadgetOutputCount {
+                    gate_index: 1,
+                    name: OPCODE::ToBits,
+                    expected: 1,
+                    found: 0
+                },
+                ValidationError::UnusedPublicInput { witness: Witness(2) },
+                ValidationError::PublicInputOutOfBounds {
+                    witness: Witness(5),
+                    current_witness_index: 2
+                },
+            ]
+        );
+        assert_eq!(errors[2].gate_index(), Some(1));
+        assert_eq!(errors[4].gate_index(), None);
+    }
+}
@@ -5,6 +5,7 @@
 pub mod circuit;
 pub mod native_types;
 pub mod optimiser;
+pub mod serialization;
 
 pub mod opcode;
 
@@ -1,11 +1,7 @@
-use std::io::Read;
-
-use flate2::{
-    bufread::{DeflateDecoder, DeflateEncoder},
-    Compression,
-};
 use serde::{Deserialize, Serialize};
 
+use crate::serialization::{self, SerializationError, WITNESS_MAGIC};
+
 #[derive(
     Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize,
 )]
@@ -34,20 +30,14 @@ impl Witness {
     pub fn to_bytes(
         witnesses: &std::collections::BTreeMap<Witness, noir_field::FieldElement>,
     ) -> Vec<u8> {
-        let buf = rmp_serde::to_vec(witnesses).unwrap();
-        let mut deflater = DeflateEncoder::new(buf.as_slice(), Compression::best());
-        let mut buf_c = Vec::new();
-        deflater.read_to_end(&mut buf_c).unwrap();
-        buf_c
+        serialization::serialize(WITNESS_MAGIC, witnesses)
     }
 
     pub fn from_bytes(
         bytes: &[u8],
-    ) -> std::collections::BTreeMap<Witness, noir_field::FieldElement> {
-        let mut deflater = DeflateDecoder::new(bytes);
-        let mut buf_d = Vec::new();
-        deflater.read_to_end(&mut buf_d).unwrap();
-        rmp_serde::from_slice(buf_d.as_slice()).unwrap()
+    ) -> Result<std::collections::BTreeMap<Witness, noir_field::FieldElement>, SerializationError>
+    {
+        serialization::deserialize(WITNESS_MAGIC, bytes)
     }
 }
 
@@ -14,6 +14,7 @@ pub enum OPCODE {
     EcdsaSecp256k1,
     FixedBaseScalarMul,
     ToBits,
+    Keccak256,
 }
 
 impl std::fmt::Display for OPCODE {
@@ -35,6 +36,7 @@ impl OPCODE {
             OPCODE::EcdsaSecp256k1 => 7,
             OPCODE::FixedBaseScalarMul => 8,
             OPCODE::ToBits => 9,
+            OPCODE::Keccak256 => 10,
         }
     }
     pub fn name(&self) -> &str {
@@ -49,6 +51,7 @@ impl OPCODE {
             OPCODE::EcdsaSecp256k1 => "ecdsa_secp256k1",
             OPCODE::FixedBaseScalarMul => "fixed_base_scalar_mul",
             OPCODE::ToBits => "to_bits",
+            OPCODE::Keccak256 => "keccak256",
         }
     }
     pub fn lookup(op_name: &str) -> Option<OPCODE> {
@@ -62,6 +65,7 @@ impl OPCODE {
             "ecdsa_secp256k1" => Some(OPCODE::EcdsaSecp256k1),
             "fixed_base_scalar_mul" => Some(OPCODE::FixedBaseScalarMul),
             "to_bits" => Some(OPCODE::ToBits),
+            "keccak256" => Some(OPCODE::Keccak256),
             _ => None,
         }
     }
@@ -119,6 +123,11 @@ impl OPCODE {
                 input_size: InputSize::Fixed(2),
                 output_size: OutputSize(1),
             },
+            OPCODE::Keccak256 => GadgetDefinition {
+                name: self.name().into(),
+                input_size: InputSize::Variable,
+                output_size: OutputSize(32),
+            },
         }
     }
 }
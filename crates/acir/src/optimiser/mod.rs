@@ -1,5 +1,7 @@
 mod csat_optimiser;
 mod general_optimiser;
+mod range_optimiser;
 
 pub use csat_optimiser::Optimiser as CSatOptimiser;
 pub use general_optimiser::GeneralOpt as GeneralOptimiser;
+pub use range_optimiser::RangeOptimiser;
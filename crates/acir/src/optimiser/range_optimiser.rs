@@ -1,19 +1,257 @@
-// XXX: We could alleviate a runtime check from noir
-// By casting directly
-// Example:
-// priv z1 =  x as u32 
-// priv z2 =  x as u16 
+use std::collections::{BTreeMap, BTreeSet};
+
+use noir_field::FieldElement;
+
+use crate::circuit::{
+    gate::{Directive, Gate},
+    Circuit,
+};
+use crate::native_types::{Expression, Witness};
+
+// Removes the range gates which do not constrain their witness any further.
 //
-// The IR would see both casts and replace it with
-// 
-// 
-// priv z1 = x as u16;
-// priv z2 = x as u16;
-//
-// 
-// Then maybe another optimisation could be done so that it transforms into
-//
-// priv z1 = x as u16
-// priv z2 = z1
-// This is what I would call a general optimisation, so it could live inside of the IR module
-// A more specific optimisation would be to have z2 = z1 not use a gate (copy_from_to), this is more specific to plonk-aztec and would not live in this module
\ No newline at end of file
+// A witness is often range checked several times, for instance once per cast, so only the tightest
+// range gate of each witness is kept.
+// A range gate is also removed when the bit size of its witness is already bounded by another gate:
+// - the inputs and the result of the And and Xor gates are constrained to their number of bits by the backend
+// - a witness decomposed by a Split directive is bounded by its number of bits, provided that the circuit
+//   constrains each bit to be boolean and has the arithmetic gate a - Σ 2^i b_i which recomposes them.
+//   The directive alone only computes the bits, it does not constrain anything
+pub struct RangeOptimiser {
+    // The tightest range gate of each witness
+    ranges: BTreeMap<Witness, u32>,
+    // The tightest bound of each witness which is implied by a gate other than a range gate
+    implied: BTreeMap<Witness, u32>,
+}
+
+impl RangeOptimiser {
+    pub fn new(circuit: &Circuit) -> RangeOptimiser {
+        let mut ranges = BTreeMap::new();
+        let mut implied = BTreeMap::new();
+        let mut booleans = BTreeSet::new();
+        let mut linear_gates = Vec::new();
+        // The linear gates in which each witness appears
+        let mut linear_gates_of: BTreeMap<Witness, Vec<usize>> = BTreeMap::new();
+        let mut splits = Vec::new();
+        for gate in &circuit.gates {
+            match gate {
+                Gate::Range(witness, num_bits) => tighten(&mut ranges, *witness, *num_bits),
+                Gate::And(gate) => {
+                    for witness in [gate.a, gate.b, gate.result] {
+                        tighten(&mut implied, witness, gate.num_bits);
+                    }
+                }
+                Gate::Xor(gate) => {
+                    for witness in [gate.a, gate.b, gate.result] {
+                        tighten(&mut implied, witness, gate.num_bits);
+                    }
+                }
+                Gate::Arithmetic(expression) => {
+                    if let Some(bit) = boolean_witness(expression) {
+                        booleans.insert(bit);
+                    } else if let Some(terms) = linear_terms(expression) {
+                        for witness in terms.keys() {
+                            linear_gates_of.entry(*witness).or_default().push(linear_gates.len());
+                        }
+                        linear_gates.push(terms);
+                    }
+                }
+                Gate::Directive(Directive::Split { a, b, .. }) => splits.push((*a, b)),
+                _ => (),
+            }
+        }
+
+        for (a, bits) in splits {
+            let recomposition = recomposition_terms(a, bits);
+            let recomposed = linear_gates_of
+                .get(bits.first().unwrap_or(&a))
+                .into_iter()
+                .flatten()
+                .any(|index| same_constraint(&linear_gates[*index], &recomposition));
+            if recomposed && bits.iter().all(|bit| booleans.contains(bit)) {
+                tighten(&mut implied, a, bits.len() as u32);
+            }
+        }
+        RangeOptimiser { ranges, implied }
+    }
+
+    /// Removes the redundant range gates of the circuit.
+    /// Also returns, for each remaining gate, its index in the original circuit
+    pub fn optimise(self, circuit: Circuit) -> (Circuit, Vec<usize>) {
+        let mut constrained = BTreeSet::new();
+        let mut gates = Vec::new();
+        let mut origins = Vec::new();
+        for (index, gate) in circuit.gates.into_iter().enumerate() {
+            if let Gate::Range(witness, num_bits) = gate {
+                let implied = matches!(self.implied.get(&witness), Some(bits) if *bits <= num_bits);
+                let tightest = self.ranges[&witness] == num_bits;
+                // Among several tightest range gates, only the first one is kept
+                if implied || !tightest || !constrained.insert(witness) {
+                    continue;
+                }
+            }
+            gates.push(gate);
+            origins.push(index);
+        }
+
+        let circuit = Circuit {
+            current_witness_index: circuit.current_witness_index,
+            gates,
+            public_inputs: circuit.public_inputs,
+        };
+        (circuit, origins)
+    }
+}
+
+fn tighten(bounds: &mut BTreeMap<Witness, u32>, witness: Witness, num_bits: u32) {
+    let bound = bounds.entry(witness).or_insert(num_bits);
+    *bound = (*bound).min(num_bits);
+}
+
+// Returns the witness b if the gate is the boolean constraint q*(b*b - b) = 0
+fn boolean_witness(expression: &Expression) -> Option<Witness> {
+    match (&expression.mul_terms[..], &expression.linear_combinations[..]) {
+        ([(q_m, lhs, rhs)], [(q_l, witness)])
+            if lhs == rhs
+                && lhs == witness
+                && !q_m.is_zero()
+                && *q_l == -*q_m
+                && expression.q_c.is_zero() =>
+        {
+            Some(*witness)
+        }
+        _ => None,
+    }
+}
+
+// Returns the coefficient of each witness of a linear gate without a constant term
+fn linear_terms(expression: &Expression) -> Option<BTreeMap<Witness, FieldElement>> {
+    if !expression.is_linear() || !expression.q_c.is_zero() {
+        return None;
+    }
+    let mut terms = BTreeMap::new();
+    for (coefficient, witness) in &expression.linear_combinations {
+        *terms.entry(*witness).or_insert_with(FieldElement::zero) += *coefficient;
+    }
+    terms.retain(|_, coefficient| !coefficient.is_zero());
+    Some(terms)
+}
+
+// The terms of a - Σ 2^i b_i
+fn recomposition_terms(a: Witness, bits: &[Witness]) -> BTreeMap<Witness, FieldElement> {
+    let mut terms = BTreeMap::new();
+    terms.insert(a, FieldElement::one());
+    let mut power = FieldElement::one();
+    for bit in bits {
+        *terms.entry(*bit).or_insert_with(FieldElement::zero) -= power;
+        power = power + power;
+    }
+    terms.retain(|_, coefficient| !coefficient.is_zero());
+    terms
+}
+
+// Whether the two linear gates are the same constraint, up to their sign
+fn same_constraint(
+    lhs: &BTreeMap<Witness, FieldElement>,
+    rhs: &BTreeMap<Witness, FieldElement>,
+) -> bool {
+    lhs == rhs
+        || (lhs.len() == rhs.len() && lhs.iter().all(|(witness, q)| rhs.get(witness) == Some(&-*q)))
+}
+
+#[cfg(test)]
+mod test {
+    use noir_field::FieldElement;
+
+    use super::RangeOptimiser;
+    use crate::circuit::{
+        gate::{AndGate, Directive},
+        Circuit, Gate, PublicInputs,
+    };
+    use crate::native_types::{Expression, Witness};
+
+    // bit * bit - bit = 0
+    fn boolean(bit: Witness) -> Gate {
+        Gate::Arithmetic(Expression {
+            mul_terms: vec![(FieldElement::one(), bit, bit)],
+            linear_combinations: vec![(-FieldElement::one(), bit)],
+            q_c: FieldElement::zero(),
+        })
+    }
+
+    // Σ 2^i bits_i - a = 0
+    fn recomposition(a: Witness, bits: &[Witness]) -> Gate {
+        let mut linear_combinations = vec![(-FieldElement::one(), a)];
+        for (i, bit) in bits.iter().enumerate() {
+            linear_combinations.push((FieldElement::from(1_i128 << i), *bit));
+        }
+        Gate::Arithmetic(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations,
+            q_c: FieldElement::zero(),
+        })
+    }
+
+    #[test]
+    fn keeps_the_tightest_range() {
+        let circuit = Circuit {
+            current_witness_index: 6,
+            gates: vec![
+                Gate::Range(Witness(1), 32),
+                Gate::Range(Witness(1), 16),
+                Gate::Range(Witness(2), 8),
+                Gate::Range(Witness(1), 16),
+                Gate::And(AndGate {
+                    a: Witness(2),
+                    b: Witness(3),
+                    result: Witness(4),
+                    num_bits: 8,
+                }),
+                Gate::Range(Witness(3), 4),
+                Gate::Directive(Directive::Split {
+                    a: Witness(5),
+                    b: vec![Witness(6), Witness(7)],
+                    bit_size: 2,
+                }),
+                boolean(Witness(6)),
+                boolean(Witness(7)),
+                recomposition(Witness(5), &[Witness(6), Witness(7)]),
+                Gate::Range(Witness(5), 8),
+            ],
+            public_inputs: PublicInputs(vec![]),
+        };
+
+        let (optimised, origins) = RangeOptimiser::new(&circuit).optimise(circuit.clone());
+        assert_eq!(origins, vec![1, 4, 5, 6, 7, 8, 9]);
+        let expected: Vec<_> = origins.iter().map(|index| circuit.gates[*index].clone()).collect();
+        assert_eq!(optimised.gates, expected);
+    }
+
+    #[test]
+    fn split_without_its_constraints_does_not_bound() {
+        let a = Witness(1);
+        let bits = [Witness(2), Witness(3)];
+        let split = Gate::Directive(Directive::Split { a, b: bits.to_vec(), bit_size: 2 });
+        let circuits = [
+            vec![split.clone(), Gate::Range(a, 8)],
+            // The bits are not constrained to be boolean
+            vec![split.clone(), recomposition(a, &bits), Gate::Range(a, 8)],
+            // The bits are not constrained to recompose the witness
+            vec![split.clone(), boolean(bits[0]), boolean(bits[1]), Gate::Range(a, 8)],
+            // The bits recompose another witness
+            vec![
+                split.clone(),
+                boolean(bits[0]),
+                boolean(bits[1]),
+                recomposition(Witness(4), &bits),
+                Gate::Range(a, 8),
+            ],
+        ];
+        for gates in circuits {
+            let circuit =
+                Circuit { current_witness_index: 4, gates, public_inputs: PublicInputs(vec![]) };
+            let (optimised, _) = RangeOptimiser::new(&circuit).optimise(circuit.clone());
+            assert_eq!(optimised.gates, circuit.gates);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+// Binary format of the ACIR artifacts
+//
+// An artifact is a header followed by its compressed payload:
+// - 4 magic bytes, which tell circuits and witness maps apart
+// - the format version, as a little endian u16
+// - the value encoded with MessagePack, then compressed with deflate
+//
+// Artifacts written before the header was introduced are only the compressed payload.
+// They are read as version 0 and migrated to the current version.
+use std::io::Read;
+
+use flate2::{
+    bufread::{DeflateDecoder, DeflateEncoder},
+    Compression,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Version of the binary format which is written by this crate.
+/// It must be increased whenever the serialized representation of a circuit or of a witness map changes,
+/// and a migration from the previous version must be added in `decode_payload`
+pub const ACIR_FORMAT_VERSION: u16 = 1;
+
+/// Version given to the artifacts which have no header
+const LEGACY_VERSION: u16 = 0;
+
+pub(crate) const CIRCUIT_MAGIC: [u8; 4] = *b"ACIR";
+pub(crate) const WITNESS_MAGIC: [u8; 4] = *b"ACWT";
+
+const HEADER_LEN: usize = 6;
+
+#[derive(Debug)]
+pub enum SerializationError {
+    /// The artifact was written by a newer version of ACIR
+    UnsupportedVersion {
+        found: u16,
+        supported: u16,
+    },
+    /// The artifact is a witness map where a circuit was expected, or the converse
+    WrongArtifact {
+        expected: [u8; 4],
+        found: [u8; 4],
+    },
+    /// The artifact ends before the end of its header
+    Truncated,
+    Decompression(std::io::Error),
+    Decoding(rmp_serde::decode::Error),
+}
+
+impl std::fmt::Display for SerializationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SerializationError::UnsupportedVersion { found, supported } => write!(
+                f,
+                "ACIR format version {} is not supported, the latest supported version is {}",
+                found, supported
+            ),
+            SerializationError::WrongArtifact { expected, found } => write!(
+                f,
+                "expected an artifact of kind {} but found one of kind {}",
+                String::from_utf8_lossy(expected),
+                String::from_utf8_lossy(found)
+            ),
+            SerializationError::Truncated => write!(f, "the ACIR header is truncated"),
+            SerializationError::Decompression(err) => {
+                write!(f, "could not decompress the ACIR payload: {}", err)
+            }
+            SerializationError::Decoding(err) => {
+                write!(f, "could not decode the ACIR payload: {}", err)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SerializationError {}
+
+pub(crate) fn serialize<T: Serialize>(magic: [u8; 4], value: &T) -> Vec<u8> {
+    let buf = rmp_serde::to_vec(value).unwrap();
+    let mut deflater = DeflateEncoder::new(buf.as_slice(), Compression::best());
+
+    let mut bytes = Vec::with_capacity(HEADER_LEN);
+    bytes.extend_from_slice(&magic);
+    bytes.extend_from_slice(&ACIR_FORMAT_VERSION.to_le_bytes());
+    deflater.read_to_end(&mut bytes).unwrap();
+    bytes
+}
+
+pub(crate) fn deserialize<T: DeserializeOwned>(
+    magic: [u8; 4],
+    bytes: &[u8],
+) -> Result<T, SerializationError> {
+    let (version, payload) = read_header(magic, bytes)?;
+    decode_payload(version, payload)
+}
+
+// Splits the artifact into its version and its payload
+fn read_header(magic: [u8; 4], bytes: &[u8]) -> Result<(u16, &[u8]), SerializationError> {
+    let found = match bytes.get(..4) {
+        Some(found) if found == CIRCUIT_MAGIC || found == WITNESS_MAGIC => found,
+        _ => return Ok((LEGACY_VERSION, bytes)),
+    };
+    if found != magic {
+        let mut found_magic = [0; 4];
+        found_magic.copy_from_slice(found);
+        return Err(SerializationError::WrongArtifact { expected: magic, found: found_magic });
+    }
+    if bytes.len() < HEADER_LEN {
+        return Err(SerializationError::Truncated);
+    }
+    let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+    Ok((version, &bytes[HEADER_LEN..]))
+}
+
+fn decode_payload<T: DeserializeOwned>(
+    version: u16,
+    payload: &[u8],
+) -> Result<T, SerializationError> {
+    match version {
+        // Version 1 only added the header, so the legacy payload is decoded as it is
+        LEGACY_VERSION | ACIR_FORMAT_VERSION => {
+            let mut deflater = DeflateDecoder::new(payload);
+            let mut buf_d = Vec::new();
+            deflater.read_to_end(&mut buf_d).map_err(SerializationError::Decompression)?;
+            rmp_serde::from_slice(buf_d.as_slice()).map_err(SerializationError::Decoding)
+        }
+        found => {
+            Err(SerializationError::UnsupportedVersion { found, supported: ACIR_FORMAT_VERSION })
+        }
+    }
+}
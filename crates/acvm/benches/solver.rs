@@ -0,0 +1,61 @@
+use std::collections::BTreeMap;
+
+use acvm::acir::{
+    circuit::Gate,
+    native_types::{Expression, Witness},
+};
+use acvm::{test_backend::MinimalBackend, FieldElement, GateResolution, PartialWitnessGenerator};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+const NUM_GATES: u32 = 1_000_000;
+
+// The gate i constrains x(i+1) = x(i) + 1, so each gate can only be solved once the previous one is
+fn chain() -> Vec<Gate> {
+    (1..=NUM_GATES)
+        .map(|i| {
+            let expression = &Expression::from(&Witness(i + 1)) - &Witness(i);
+            Gate::Arithmetic(expression - &FieldElement::one())
+        })
+        .collect()
+}
+
+// The gate i constrains x(i+1) = x(1) * x(1) + i, so the gates can be solved in any order
+fn independent() -> Vec<Gate> {
+    (1..=NUM_GATES)
+        .map(|i| {
+            let mut expression = Expression::from(&Witness(i + 1)) - &FieldElement::from(i as i128);
+            expression.mul_terms.push((-FieldElement::one(), Witness(1), Witness(1)));
+            Gate::Arithmetic(expression)
+        })
+        .collect()
+}
+
+fn bench_solve(c: &mut Criterion, name: &str, gates: Vec<Gate>) {
+    let mut group = c.benchmark_group("solve");
+    group.sample_size(10);
+    group.bench_function(name, |b| {
+        b.iter_batched(
+            || (BTreeMap::from([(Witness(1), FieldElement::from(3_i128))]), gates.clone()),
+            |(mut witness, gates)| {
+                assert_eq!(MinimalBackend.solve(&mut witness, gates), GateResolution::Resolved);
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+fn solver(c: &mut Criterion) {
+    let gates = chain();
+    bench_solve(c, "chain", gates.clone());
+
+    // The worst order for a solver which scans the gates in order, as only the last gate can be solved at first
+    let mut reversed = gates;
+    reversed.reverse();
+    bench_solve(c, "reversed chain", reversed);
+
+    bench_solve(c, "independent", independent());
+}
+
+criterion_group!(benches, solver);
+criterion_main!(benches);
@@ -1,27 +1,102 @@
+mod fallback;
+
+use std::collections::{BTreeMap, BTreeSet};
+
 use indexmap::IndexMap;
 
 use crate::Language;
 use acir::{
-    circuit::{Circuit, Gate},
+    circuit::{Circuit, Gate, ValidationError},
     native_types::{Expression, Witness},
-    optimiser::{CSatOptimiser, GeneralOptimiser},
+    optimiser::{CSatOptimiser, GeneralOptimiser, RangeOptimiser},
 };
 
-pub fn compile(acir: Circuit, np_language: Language) -> Circuit {
+/// The optimised circuit returned by `compile`
+#[derive(Debug, Clone)]
+pub struct CompiledCircuit {
+    pub circuit: Circuit,
+    /// For each gate of the optimised circuit, the index in the input circuit of the gate it comes from
+    pub origins: Vec<usize>,
+    /// The number of range gates which were removed because they were redundant
+    pub removed_range_gates: usize,
+}
+
+/// The reasons for which `compile` rejects a circuit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompileError {
+    Invalid(ValidationError),
+    /// The backend does not support the gate, and the gate cannot be rewritten into supported gates
+    UnsupportedGate {
+        gate_index: usize,
+        opcode: String,
+    },
+}
+
+impl std::fmt::Display for CompileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompileError::Invalid(error) => write!(f, "{}", error),
+            CompileError::UnsupportedGate { gate_index, opcode } => write!(
+                f,
+                "gate {} uses {} which the backend does not support and which has no fallback",
+                gate_index, opcode
+            ),
+        }
+    }
+}
+
+pub use fallback::has_fallback;
+
+/// Optimises the circuit for the given language.
+/// The gates for which `is_supported` returns false are rewritten into arithmetic gates when they have a fallback.
+/// The circuit is rejected if it is malformed, or if it has unsupported gates without a fallback.
+pub fn compile(
+    acir: Circuit,
+    np_language: Language,
+    is_supported: &dyn Fn(&Gate) -> bool,
+) -> Result<CompiledCircuit, Vec<CompileError>> {
+    acir.validate()
+        .map_err(|errors| errors.into_iter().map(CompileError::Invalid).collect::<Vec<_>>())?;
+
+    let unsupported: Vec<_> = acir
+        .gates
+        .iter()
+        .enumerate()
+        .filter(|(_, gate)| !is_supported(gate) && !has_fallback(gate))
+        .map(|(gate_index, gate)| CompileError::UnsupportedGate {
+            gate_index,
+            opcode: gate.name().to_string(),
+        })
+        .collect();
+    if !unsupported.is_empty() {
+        return Err(unsupported);
+    }
+
+    let num_gates = acir.gates.len();
+    let (acir, range_origins) = RangeOptimiser::new(&acir).optimise(acir);
+    let removed_range_gates = num_gates - acir.gates.len();
+
+    let (acir, range_origins) =
+        fallback::replace_unsupported_gates(acir, range_origins, is_supported);
+
     // Instantiate the optimiser.
     // Currently the optimiser and reducer are one in the same
     // for CSAT
     let optimiser = match np_language {
-        crate::Language::R1CS => return optimise_r1cs(acir),
+        crate::Language::R1CS => {
+            let circuit = optimise_r1cs(acir);
+            return Ok(CompiledCircuit { circuit, origins: range_origins, removed_range_gates });
+        }
         crate::Language::PLONKCSat { width } => CSatOptimiser::new(width),
     };
 
     // Optimise the arithmetic gates by reducing them into the correct width and
     // creating intermediate variables when necessary
     let mut optimised_gates = Vec::new();
+    let mut origins = Vec::new();
 
     let mut next_witness_index = acir.current_witness_index + 1;
-    for gate in acir.gates {
+    for (gate, index) in acir.gates.into_iter().zip(range_origins) {
         match gate {
             Gate::Arithmetic(arith_expr) => {
                 let mut intermediate_variables: IndexMap<Witness, Expression> = IndexMap::new();
@@ -34,20 +109,23 @@ pub fn compile(acir: Circuit, np_language: Language) -> Circuit {
 
                 for (_, gate) in intermediate_variables.into_iter() {
                     optimised_gates.push(Gate::Arithmetic(gate));
+                    origins.push(index);
                 }
                 optimised_gates.push(Gate::Arithmetic(arith_expr));
             }
             other_gate => optimised_gates.push(other_gate),
         }
+        origins.push(index);
     }
 
     let current_witness_index = next_witness_index - 1;
 
-    Circuit {
+    let circuit = Circuit {
         current_witness_index,
         gates: optimised_gates,
         public_inputs: acir.public_inputs, // The optimiser does not add public inputs
-    }
+    };
+    Ok(CompiledCircuit { circuit, origins, removed_range_gates })
 }
 
 // R1CS optimisations uses the general optimiser.
@@ -64,12 +142,140 @@ fn optimise_r1cs(acir: Circuit) -> Circuit {
         .collect();
 
     Circuit {
-        // The general optimiser may remove enough gates that a witness is no longer used
-        // however, we cannot decrement the number of witnesses, as that
-        // would require a linear scan over all gates in order to decrement all witness indices
-        // above the witness which was removed
+        // The general optimiser may remove enough gates that a witness is no longer used,
+        // those witnesses are removed by `compact_witnesses`
         current_witness_index: acir.current_witness_index,
         gates: optimised_arith_gates,
         public_inputs: acir.public_inputs,
     }
 }
+
+/// Maps the witnesses of a circuit before `compact_witnesses` to their index after it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WitnessRemap(pub BTreeMap<Witness, Witness>);
+
+impl WitnessRemap {
+    /// Returns the new index of the witness, or None if it was removed from the circuit
+    pub fn get(&self, witness: Witness) -> Option<Witness> {
+        self.0.get(&witness).copied()
+    }
+
+    /// Returns the index which the witness of the compacted circuit had before `compact_witnesses`
+    pub fn original(&self, witness: Witness) -> Option<Witness> {
+        self.0.iter().find(|(_, new)| **new == witness).map(|(old, _)| *old)
+    }
+
+    /// Translates a witness map of the original circuit into a witness map of the compacted circuit.
+    /// The values of the removed witnesses are dropped
+    pub fn translate<T: Clone>(&self, witness_map: &BTreeMap<Witness, T>) -> BTreeMap<Witness, T> {
+        witness_map
+            .iter()
+            .filter_map(|(witness, value)| Some((self.get(*witness)?, value.clone())))
+            .collect()
+    }
+}
+
+/// Removes the witnesses which are not used by any gate, and renumbers the other ones densely.
+/// The public inputs and the `preserved` witnesses, such as the parameters of the ABI, keep their index,
+/// and the other witnesses are given the lowest indices which are left in increasing order.
+/// The index 0 is never given to a witness, since backends reserve it.
+pub fn compact_witnesses(
+    mut circuit: Circuit,
+    preserved: &BTreeSet<Witness>,
+) -> (Circuit, WitnessRemap) {
+    let mut fixed: BTreeSet<Witness> = preserved.clone();
+    fixed.extend(circuit.public_inputs.0.iter().copied());
+
+    let used: BTreeSet<_> = circuit.gates.iter().flat_map(Gate::witnesses).collect();
+
+    let mut remap: BTreeMap<_, _> = fixed.iter().map(|witness| (*witness, *witness)).collect();
+    let mut next_index = 1;
+    for witness in used.difference(&fixed) {
+        while fixed.contains(&Witness(next_index)) {
+            next_index += 1;
+        }
+        remap.insert(*witness, Witness(next_index));
+        next_index += 1;
+    }
+
+    for witness in circuit.gates.iter_mut().flat_map(Gate::witnesses_mut) {
+        *witness = remap[witness];
+    }
+    circuit.current_witness_index = remap.values().map(|witness| witness.0).max().unwrap_or(0);
+    (circuit, WitnessRemap(remap))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{compact_witnesses, compile, CompileError};
+    use crate::Language;
+    use acir::{
+        circuit::{
+            gate::{GadgetCall, GadgetInput},
+            Circuit, Gate, PublicInputs,
+        },
+        native_types::{Expression, Witness},
+        OPCODE,
+    };
+    use std::collections::{BTreeMap, BTreeSet};
+
+    #[test]
+    fn compacts_unused_witnesses() {
+        // x1 is an unused parameter, x2 a public input and x7 an intermediate variable
+        let circuit = Circuit {
+            current_witness_index: 9,
+            gates: vec![
+                Gate::Arithmetic(&Expression::from(&Witness(2)) - &Witness(7)),
+                Gate::Range(Witness(9), 8),
+            ],
+            public_inputs: PublicInputs(vec![Witness(2)]),
+        };
+        let preserved = BTreeSet::from([Witness(1)]);
+
+        let (compacted, remap) = compact_witnesses(circuit, &preserved);
+        assert_eq!(compacted.current_witness_index, 4);
+        assert_eq!(
+            compacted.gates,
+            vec![
+                Gate::Arithmetic(&Expression::from(&Witness(2)) - &Witness(3)),
+                Gate::Range(Witness(4), 8),
+            ]
+        );
+        assert_eq!(remap.get(Witness(7)), Some(Witness(3)));
+        assert_eq!(remap.get(Witness(5)), None);
+        assert_eq!(remap.original(Witness(3)), Some(Witness(7)));
+        assert_eq!(remap.original(Witness(5)), None);
+
+        let witness_map = BTreeMap::from([(Witness(1), 1), (Witness(5), 5), (Witness(9), 9)]);
+        assert_eq!(
+            remap.translate(&witness_map),
+            BTreeMap::from([(Witness(1), 1), (Witness(4), 9)])
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_gates_without_fallback() {
+        let pedersen = Gate::GadgetCall(GadgetCall {
+            name: OPCODE::Pedersen,
+            inputs: vec![GadgetInput { witness: Witness(1), num_bits: 254 }],
+            outputs: vec![Witness(2), Witness(3)],
+        });
+        let circuit = Circuit {
+            current_witness_index: 3,
+            gates: vec![Gate::Range(Witness(1), 8), pedersen],
+            public_inputs: PublicInputs(Vec::new()),
+        };
+
+        // The range gate has a fallback, but the pedersen gate has none
+        let only_arithmetic = |gate: &Gate| matches!(gate, Gate::Arithmetic(_));
+        let errors = compile(circuit.clone(), Language::PLONKCSat { width: 3 }, &only_arithmetic)
+            .unwrap_err();
+        assert_eq!(
+            errors,
+            vec![CompileError::UnsupportedGate { gate_index: 1, opcode: "pedersen".to_string() }]
+        );
+
+        let no_range = |gate: &Gate| !matches!(gate, Gate::Range(..));
+        assert!(compile(circuit, Language::PLONKCSat { width: 3 }, &no_range).is_ok());
+    }
+}
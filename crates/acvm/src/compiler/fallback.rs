@@ -0,0 +1,447 @@
+// Rewrites the gates which are not supported by a backend into arithmetic gates.
+//
+// Witnesses are decomposed into bits with a Split directive, whose bits are constrained to be boolean
+// and to recompose the witness. The logic gates and the hash functions then operate on these bits,
+// each non linear operation on two unknown bits creating a new witness.
+mod blake2s;
+mod sha256;
+
+use acir::{
+    circuit::{
+        gate::{AndGate, Directive, GadgetCall, XorGate},
+        Circuit, Gate,
+    },
+    native_types::{Expression, Witness},
+    OPCODE,
+};
+use noir_field::FieldElement;
+
+/// Returns true if the gate can be rewritten into arithmetic gates
+pub fn has_fallback(gate: &Gate) -> bool {
+    match gate {
+        Gate::Range(..) | Gate::And(_) | Gate::Xor(_) => true,
+        Gate::GadgetCall(call) => matches!(call.name, OPCODE::SHA256 | OPCODE::Blake2s),
+        Gate::Arithmetic(_) | Gate::Directive(_) => false,
+    }
+}
+
+/// Replaces the gates which are not supported, and which have a fallback, by arithmetic gates.
+/// `origins` gives the origin of each gate of the circuit, and the origins of the new circuit are returned along with it.
+pub fn replace_unsupported_gates(
+    circuit: Circuit,
+    origins: Vec<usize>,
+    is_supported: &dyn Fn(&Gate) -> bool,
+) -> (Circuit, Vec<usize>) {
+    let mut builder = Builder::new(circuit.current_witness_index + 1);
+    let mut new_origins = Vec::new();
+    for (gate, origin) in circuit.gates.into_iter().zip(origins) {
+        if is_supported(&gate) || !has_fallback(&gate) {
+            builder.gates.push(gate);
+        } else {
+            builder.lower(gate);
+        }
+        new_origins.resize(builder.gates.len(), origin);
+    }
+
+    let circuit = Circuit {
+        current_witness_index: builder.next_witness_index - 1,
+        gates: builder.gates,
+        public_inputs: circuit.public_inputs,
+    };
+    (circuit, new_origins)
+}
+
+/// A boolean value, which is either known at compile time or is the value of a witness, possibly negated
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Bit {
+    Constant(bool),
+    Witness { witness: Witness, negated: bool },
+}
+
+impl Bit {
+    fn not(self) -> Bit {
+        match self {
+            Bit::Constant(value) => Bit::Constant(!value),
+            Bit::Witness { witness, negated } => Bit::Witness { witness, negated: !negated },
+        }
+    }
+
+    // Returns the bit as k + s*w
+    fn to_affine(self) -> (FieldElement, Option<(FieldElement, Witness)>) {
+        match self {
+            Bit::Constant(value) => (FieldElement::from(value as i128), None),
+            Bit::Witness { witness, negated: false } => {
+                (FieldElement::zero(), Some((FieldElement::one(), witness)))
+            }
+            Bit::Witness { witness, negated: true } => {
+                (FieldElement::one(), Some((-FieldElement::one(), witness)))
+            }
+        }
+    }
+
+    fn to_expression(self) -> Expression {
+        let (constant, term) = self.to_affine();
+        Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: term.into_iter().collect(),
+            q_c: constant,
+        }
+    }
+}
+
+/// 32 bits, the least significant first
+pub(super) type Word = [Bit; 32];
+
+pub(super) fn constant_word(value: u32) -> Word {
+    let mut word = [Bit::Constant(false); 32];
+    for (i, bit) in word.iter_mut().enumerate() {
+        *bit = Bit::Constant(value >> i & 1 == 1);
+    }
+    word
+}
+
+pub(super) fn constant_byte(value: u8) -> [Bit; 8] {
+    let mut byte = [Bit::Constant(false); 8];
+    for (i, bit) in byte.iter_mut().enumerate() {
+        *bit = Bit::Constant(value >> i & 1 == 1);
+    }
+    byte
+}
+
+pub(super) fn rotate_right(word: &Word, n: usize) -> Word {
+    let mut result = *word;
+    for (i, bit) in result.iter_mut().enumerate() {
+        *bit = word[(i + n) % 32];
+    }
+    result
+}
+
+pub(super) fn shift_right(word: &Word, n: usize) -> Word {
+    let mut result = [Bit::Constant(false); 32];
+    result[..32 - n].copy_from_slice(&word[n..]);
+    result
+}
+
+pub(super) fn not_word(word: &Word) -> Word {
+    let mut result = *word;
+    for bit in result.iter_mut() {
+        *bit = bit.not();
+    }
+    result
+}
+
+pub(super) struct Builder {
+    gates: Vec<Gate>,
+    next_witness_index: u32,
+}
+
+impl Builder {
+    fn new(next_witness_index: u32) -> Builder {
+        Builder { gates: Vec::new(), next_witness_index }
+    }
+
+    fn new_witness(&mut self) -> Witness {
+        let witness = Witness(self.next_witness_index);
+        self.next_witness_index += 1;
+        witness
+    }
+
+    // Creates a witness which is constrained to the value of the expression
+    fn materialise(&mut self, expression: Expression) -> Witness {
+        let witness = self.new_witness();
+        self.gates.push(Gate::Arithmetic(&expression - &witness));
+        witness
+    }
+
+    /// Decomposes the witness into `num_bits` bits, the least significant first.
+    /// The witness is constrained to fit in `num_bits` bits
+    fn decompose(&mut self, witness: Witness, num_bits: u32) -> Vec<Bit> {
+        let bits: Vec<_> = (0..num_bits).map(|_| self.new_witness()).collect();
+        self.gates.push(Gate::Directive(Directive::Split {
+            a: witness,
+            b: bits.clone(),
+            bit_size: num_bits,
+        }));
+        for bit in &bits {
+            // bit * bit - bit = 0
+            self.gates.push(Gate::Arithmetic(Expression {
+                mul_terms: vec![(FieldElement::one(), *bit, *bit)],
+                linear_combinations: vec![(-FieldElement::one(), *bit)],
+                q_c: FieldElement::zero(),
+            }));
+        }
+        let bits: Vec<_> =
+            bits.into_iter().map(|witness| Bit::Witness { witness, negated: false }).collect();
+        let recomposed = recompose(&bits);
+        self.gates.push(Gate::Arithmetic(&recomposed - &witness));
+        bits
+    }
+
+    // Constrains the witness to be the number whose bits are given, the least significant first
+    fn constrain_bits(&mut self, bits: &[Bit], witness: Witness) {
+        self.gates.push(Gate::Arithmetic(&recompose(bits) - &witness));
+    }
+
+    pub(super) fn xor(&mut self, lhs: Bit, rhs: Bit) -> Bit {
+        match (lhs, rhs) {
+            (Bit::Constant(value), bit) | (bit, Bit::Constant(value)) => {
+                if value {
+                    bit.not()
+                } else {
+                    bit
+                }
+            }
+            (
+                Bit::Witness { witness: lhs, negated: lhs_negated },
+                Bit::Witness { witness: rhs, negated: rhs_negated },
+            ) => {
+                let negated = lhs_negated != rhs_negated;
+                if lhs == rhs {
+                    return Bit::Constant(negated);
+                }
+                // lhs + rhs - 2*lhs*rhs
+                let witness = self.materialise(Expression {
+                    mul_terms: vec![(-FieldElement::from(2_i128), lhs, rhs)],
+                    linear_combinations: vec![
+                        (FieldElement::one(), lhs),
+                        (FieldElement::one(), rhs),
+                    ],
+                    q_c: FieldElement::zero(),
+                });
+                Bit::Witness { witness, negated }
+            }
+        }
+    }
+
+    pub(super) fn and(&mut self, lhs: Bit, rhs: Bit) -> Bit {
+        match (lhs, rhs) {
+            (Bit::Constant(value), bit) | (bit, Bit::Constant(value)) => {
+                if value {
+                    bit
+                } else {
+                    Bit::Constant(false)
+                }
+            }
+            _ if lhs == rhs => lhs,
+            _ if lhs == rhs.not() => Bit::Constant(false),
+            _ => {
+                // (k1 + s1*w1)*(k2 + s2*w2)
+                let (k1, term1) = lhs.to_affine();
+                let (k2, term2) = rhs.to_affine();
+                let ((s1, w1), (s2, w2)) = (term1.unwrap(), term2.unwrap());
+                let witness = self.materialise(Expression {
+                    mul_terms: vec![(s1 * s2, w1, w2)],
+                    linear_combinations: vec![(k2 * s1, w1), (k1 * s2, w2)],
+                    q_c: k1 * k2,
+                });
+                Bit::Witness { witness, negated: false }
+            }
+        }
+    }
+
+    pub(super) fn xor_words(&mut self, lhs: &Word, rhs: &Word) -> Word {
+        let mut result = *lhs;
+        for (bit, rhs) in result.iter_mut().zip(rhs) {
+            *bit = self.xor(*bit, *rhs);
+        }
+        result
+    }
+
+    pub(super) fn and_words(&mut self, lhs: &Word, rhs: &Word) -> Word {
+        let mut result = *lhs;
+        for (bit, rhs) in result.iter_mut().zip(rhs) {
+            *bit = self.and(*bit, *rhs);
+        }
+        result
+    }
+
+    /// Adds the words modulo 2^32
+    pub(super) fn add_words(&mut self, words: &[Word]) -> Word {
+        let sum = words.iter().fold(Expression::default(), |sum, word| &sum + &recompose(word));
+        if sum.linear_combinations.is_empty() {
+            let value = sum.q_c.try_into_u128().expect("the sum of constant words fits in u128");
+            return constant_word(value as u32);
+        }
+
+        // The sum of n words fits in 32 + log2(n) bits
+        let carry_bits = usize::BITS - (words.len() - 1).leading_zeros();
+        let witness = self.materialise(sum);
+        let bits = self.decompose(witness, 32 + carry_bits);
+        let mut word = [Bit::Constant(false); 32];
+        word.copy_from_slice(&bits[..32]);
+        word
+    }
+
+    /// Decomposes the inputs of a hash function into bytes, in the order in which they are hashed
+    pub(super) fn input_bytes(&mut self, call: &GadgetCall) -> Vec<[Bit; 8]> {
+        let mut bytes = Vec::new();
+        for input in &call.inputs {
+            // The solver hashes the least significant bytes of the input which cover its number of bits,
+            // the least significant byte first
+            let num_bytes = input.num_bits / 8 + u32::from(input.num_bits % 8 != 0);
+            let bits = self.decompose(input.witness, num_bytes * 8);
+            for chunk in bits.chunks(8) {
+                let mut byte = [Bit::Constant(false); 8];
+                byte.copy_from_slice(chunk);
+                bytes.push(byte);
+            }
+        }
+        bytes
+    }
+
+    /// Constrains each output of the hash function to be a byte of the digest
+    pub(super) fn output_bytes(&mut self, call: &GadgetCall, digest: &[[Bit; 8]]) {
+        for (output, byte) in call.outputs.iter().zip(digest) {
+            self.constrain_bits(byte, *output);
+        }
+    }
+
+    fn lower(&mut self, gate: Gate) {
+        match gate {
+            Gate::Range(witness, num_bits) => {
+                self.decompose(witness, num_bits);
+            }
+            Gate::And(AndGate { a, b, result, num_bits }) => {
+                let a = self.decompose(a, num_bits);
+                let b = self.decompose(b, num_bits);
+                let bits: Vec<_> = a.into_iter().zip(b).map(|(a, b)| self.and(a, b)).collect();
+                self.constrain_bits(&bits, result);
+            }
+            Gate::Xor(XorGate { a, b, result, num_bits }) => {
+                let a = self.decompose(a, num_bits);
+                let b = self.decompose(b, num_bits);
+                let bits: Vec<_> = a.into_iter().zip(b).map(|(a, b)| self.xor(a, b)).collect();
+                self.constrain_bits(&bits, result);
+            }
+            Gate::GadgetCall(call) => match call.name {
+                OPCODE::SHA256 => sha256::sha256(self, &call),
+                OPCODE::Blake2s => blake2s::blake2s(self, &call),
+                name => unreachable!("{} has no fallback", name),
+            },
+            Gate::Arithmetic(_) | Gate::Directive(_) => {
+                unreachable!("arithmetic gates and directives have no fallback")
+            }
+        }
+    }
+}
+
+// Returns the number whose bits are given, the least significant first
+fn recompose(bits: &[Bit]) -> Expression {
+    let mut result = Expression::default();
+    let mut power = FieldElement::one();
+    for bit in bits {
+        result = &result + &(&bit.to_expression() * &power);
+        power = power + power;
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use acir::{
+        circuit::{
+            gate::{AndGate, GadgetCall, GadgetInput, XorGate},
+            Circuit, Gate, PublicInputs,
+        },
+        native_types::Witness,
+        OPCODE,
+    };
+    use blake2::{Blake2s, Digest};
+    use noir_field::FieldElement;
+    use sha2::Sha256;
+
+    use super::replace_unsupported_gates;
+    use crate::{
+        test_backend::MinimalBackend, GateResolution, OpcodeNotSolvable, OpcodeResolutionError,
+        PartialWitnessGenerator,
+    };
+
+    // Lowers every gate of the circuit and solves it with the given inputs
+    fn solve(
+        gates: Vec<Gate>,
+        current_witness_index: u32,
+        inputs: &[u128],
+    ) -> BTreeMap<Witness, FieldElement> {
+        let circuit =
+            Circuit { current_witness_index, gates, public_inputs: PublicInputs(Vec::new()) };
+        let origins = (0..circuit.gates.len()).collect();
+        let (circuit, _) = replace_unsupported_gates(circuit, origins, &|_| false);
+        assert!(circuit
+            .gates
+            .iter()
+            .all(|gate| matches!(gate, Gate::Arithmetic(_) | Gate::Directive(_))));
+
+        let mut witness: BTreeMap<_, _> = inputs
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (Witness(i as u32 + 1), FieldElement::from(*value)))
+            .collect();
+        assert_eq!(MinimalBackend.solve(&mut witness, circuit.gates), GateResolution::Resolved);
+        witness
+    }
+
+    #[test]
+    fn logic_and_range() {
+        let gates = vec![
+            Gate::And(AndGate { a: Witness(1), b: Witness(2), result: Witness(3), num_bits: 8 }),
+            Gate::Xor(XorGate { a: Witness(1), b: Witness(2), result: Witness(4), num_bits: 8 }),
+            Gate::Range(Witness(3), 6),
+        ];
+        let witness = solve(gates.clone(), 4, &[0b1011_0110, 0b0110_0011]);
+        assert_eq!(witness[&Witness(3)], FieldElement::from(0b0010_0010_u128));
+        assert_eq!(witness[&Witness(4)], FieldElement::from(0b1101_0101_u128));
+
+        let circuit =
+            Circuit { current_witness_index: 4, gates, public_inputs: PublicInputs(Vec::new()) };
+        let (circuit, _) = replace_unsupported_gates(circuit, vec![0, 1, 2], &|_| false);
+        let mut witness = BTreeMap::from([
+            (Witness(1), FieldElement::from(0b1111_0000_u128)),
+            (Witness(2), FieldElement::from(0b1111_0000_u128)),
+        ]);
+        assert!(matches!(
+            MinimalBackend.solve(&mut witness, circuit.gates),
+            GateResolution::Failed(OpcodeResolutionError {
+                reason: OpcodeNotSolvable::UnsatisfiedConstraint,
+                ..
+            })
+        ));
+    }
+
+    fn hash_gate(name: OPCODE, message: &[u8]) -> Gate {
+        let num_inputs = message.len() as u32;
+        Gate::GadgetCall(GadgetCall {
+            name,
+            inputs: (1..=num_inputs)
+                .map(|i| GadgetInput { witness: Witness(i), num_bits: 8 })
+                .collect(),
+            outputs: (1..=32).map(|i| Witness(num_inputs + i)).collect(),
+        })
+    }
+
+    fn check_hash<D: Digest>(name: OPCODE) {
+        // Messages of several blocks, and messages which leave no room for the padding in their last block
+        for length in [0, 55, 56, 65] {
+            let message: Vec<u8> = (0..length).map(|i| (i * 7 + 3) as u8).collect();
+            let inputs: Vec<_> = message.iter().map(|byte| *byte as u128).collect();
+            let witness = solve(vec![hash_gate(name, &message)], length + 32, &inputs);
+
+            let expected = D::digest(&message);
+            let digest: Vec<_> = (1..=32)
+                .map(|i| witness[&Witness(length + i)].try_into_u128().unwrap() as u8)
+                .collect();
+            assert_eq!(digest, expected.to_vec(), "{} of {} bytes", name, length);
+        }
+    }
+
+    #[test]
+    fn sha256() {
+        check_hash::<Sha256>(OPCODE::SHA256);
+    }
+
+    #[test]
+    fn blake2s() {
+        check_hash::<Blake2s>(OPCODE::Blake2s);
+    }
+}
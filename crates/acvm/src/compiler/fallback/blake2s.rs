@@ -0,0 +1,111 @@
+use acir::circuit::gate::GadgetCall;
+
+use super::{constant_byte, constant_word, rotate_right, Bit, Builder, Word};
+
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+const BLOCK_LEN: usize = 64;
+
+pub(super) fn blake2s(builder: &mut Builder, call: &GadgetCall) {
+    let mut bytes = builder.input_bytes(call);
+    let length = bytes.len();
+
+    // The message is padded with zeros to a whole number of blocks, an empty message being one block of zeros
+    let num_blocks = if length == 0 { 1 } else { (length - 1) / BLOCK_LEN + 1 };
+    bytes.resize(num_blocks * BLOCK_LEN, constant_byte(0));
+
+    // The parameter block for a 32 bytes digest without key
+    let mut state = IV.map(constant_word);
+    state[0] = constant_word(IV[0] ^ 0x0101_0020);
+
+    for (i, block) in bytes.chunks(BLOCK_LEN).enumerate() {
+        let is_last = i + 1 == num_blocks;
+        // The counter is the number of bytes of the message which have been hashed at the end of the block
+        let counter = if is_last { length } else { (i + 1) * BLOCK_LEN } as u64;
+        let words: Vec<_> = block.chunks(4).map(little_endian_word).collect();
+        compress(builder, &mut state, &words, counter, is_last);
+    }
+
+    let digest: Vec<_> = state.iter().flat_map(little_endian_bytes).collect();
+    builder.output_bytes(call, &digest);
+}
+
+fn compress(
+    builder: &mut Builder,
+    state: &mut [Word; 8],
+    block: &[Word],
+    counter: u64,
+    is_last: bool,
+) {
+    let mut v = [constant_word(0); 16];
+    v[..8].copy_from_slice(state);
+    for i in 0..8 {
+        v[8 + i] = constant_word(IV[i]);
+    }
+    v[12] = constant_word(IV[4] ^ counter as u32);
+    v[13] = constant_word(IV[5] ^ (counter >> 32) as u32);
+    if is_last {
+        v[14] = constant_word(!IV[6]);
+    }
+
+    for sigma in &SIGMA {
+        mix(builder, &mut v, [0, 4, 8, 12], block[sigma[0]], block[sigma[1]]);
+        mix(builder, &mut v, [1, 5, 9, 13], block[sigma[2]], block[sigma[3]]);
+        mix(builder, &mut v, [2, 6, 10, 14], block[sigma[4]], block[sigma[5]]);
+        mix(builder, &mut v, [3, 7, 11, 15], block[sigma[6]], block[sigma[7]]);
+        mix(builder, &mut v, [0, 5, 10, 15], block[sigma[8]], block[sigma[9]]);
+        mix(builder, &mut v, [1, 6, 11, 12], block[sigma[10]], block[sigma[11]]);
+        mix(builder, &mut v, [2, 7, 8, 13], block[sigma[12]], block[sigma[13]]);
+        mix(builder, &mut v, [3, 4, 9, 14], block[sigma[14]], block[sigma[15]]);
+    }
+
+    for i in 0..8 {
+        let word = builder.xor_words(&state[i], &v[i]);
+        state[i] = builder.xor_words(&word, &v[i + 8]);
+    }
+}
+
+// The G function of BLAKE2s, which mixes two words of the message into four words of the working vector
+fn mix(builder: &mut Builder, v: &mut [Word; 16], [a, b, c, d]: [usize; 4], x: Word, y: Word) {
+    v[a] = builder.add_words(&[v[a], v[b], x]);
+    v[d] = rotate_right(&builder.xor_words(&v[d], &v[a]), 16);
+    v[c] = builder.add_words(&[v[c], v[d]]);
+    v[b] = rotate_right(&builder.xor_words(&v[b], &v[c]), 12);
+    v[a] = builder.add_words(&[v[a], v[b], y]);
+    v[d] = rotate_right(&builder.xor_words(&v[d], &v[a]), 8);
+    v[c] = builder.add_words(&[v[c], v[d]]);
+    v[b] = rotate_right(&builder.xor_words(&v[b], &v[c]), 7);
+}
+
+fn little_endian_word(bytes: &[[Bit; 8]]) -> Word {
+    let mut word = [Bit::Constant(false); 32];
+    for (i, byte) in bytes.iter().enumerate() {
+        word[i * 8..i * 8 + 8].copy_from_slice(byte);
+    }
+    word
+}
+
+fn little_endian_bytes(word: &Word) -> Vec<[Bit; 8]> {
+    word.chunks(8)
+        .map(|chunk| {
+            let mut byte = [Bit::Constant(false); 8];
+            byte.copy_from_slice(chunk);
+            byte
+        })
+        .collect()
+}
@@ -0,0 +1,109 @@
+use acir::circuit::gate::GadgetCall;
+
+use super::{
+    constant_byte, constant_word, not_word, rotate_right, shift_right, Bit, Builder, Word,
+};
+
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+pub(super) fn sha256(builder: &mut Builder, call: &GadgetCall) {
+    let mut bytes = builder.input_bytes(call);
+
+    // The message is padded with a one bit, then zeros, then its length in bits as a big endian u64,
+    // so that its length is a multiple of 64 bytes
+    let length_in_bits = bytes.len() as u64 * 8;
+    bytes.push(constant_byte(0x80));
+    while bytes.len() % 64 != 56 {
+        bytes.push(constant_byte(0));
+    }
+    bytes.extend(length_in_bits.to_be_bytes().iter().map(|byte| constant_byte(*byte)));
+
+    let mut state = IV.map(constant_word);
+    for block in bytes.chunks(64) {
+        let words: Vec<_> = block.chunks(4).map(big_endian_word).collect();
+        compress(builder, &mut state, &words);
+    }
+
+    let digest: Vec<_> = state.iter().flat_map(big_endian_bytes).collect();
+    builder.output_bytes(call, &digest);
+}
+
+fn compress(builder: &mut Builder, state: &mut [Word; 8], block: &[Word]) {
+    let mut schedule = block.to_vec();
+    for i in 16..64 {
+        let w15 = schedule[i - 15];
+        let w2 = schedule[i - 2];
+        let s0 =
+            xor3(builder, &rotate_right(&w15, 7), &rotate_right(&w15, 18), &shift_right(&w15, 3));
+        let s1 =
+            xor3(builder, &rotate_right(&w2, 17), &rotate_right(&w2, 19), &shift_right(&w2, 10));
+        let word = builder.add_words(&[schedule[i - 16], s0, schedule[i - 7], s1]);
+        schedule.push(word);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for i in 0..64 {
+        let s1 = xor3(builder, &rotate_right(&e, 6), &rotate_right(&e, 11), &rotate_right(&e, 25));
+        let e_and_f = builder.and_words(&e, &f);
+        let not_e_and_g = builder.and_words(&not_word(&e), &g);
+        let ch = builder.xor_words(&e_and_f, &not_e_and_g);
+        let temp1 = builder.add_words(&[h, s1, ch, constant_word(K[i]), schedule[i]]);
+
+        let s0 = xor3(builder, &rotate_right(&a, 2), &rotate_right(&a, 13), &rotate_right(&a, 22));
+        let a_and_b = builder.and_words(&a, &b);
+        let a_and_c = builder.and_words(&a, &c);
+        let b_and_c = builder.and_words(&b, &c);
+        let maj = xor3(builder, &a_and_b, &a_and_c, &b_and_c);
+
+        h = g;
+        g = f;
+        f = e;
+        e = builder.add_words(&[d, temp1]);
+        d = c;
+        c = b;
+        b = a;
+        a = builder.add_words(&[temp1, s0, maj]);
+    }
+
+    for (word, value) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *word = builder.add_words(&[*word, value]);
+    }
+}
+
+fn xor3(builder: &mut Builder, a: &Word, b: &Word, c: &Word) -> Word {
+    let a_xor_b = builder.xor_words(a, b);
+    builder.xor_words(&a_xor_b, c)
+}
+
+fn big_endian_word(bytes: &[[Bit; 8]]) -> Word {
+    let mut word = [Bit::Constant(false); 32];
+    for (i, byte) in bytes.iter().enumerate() {
+        let position = (3 - i) * 8;
+        word[position..position + 8].copy_from_slice(byte);
+    }
+    word
+}
+
+fn big_endian_bytes(word: &Word) -> Vec<[Bit; 8]> {
+    (0..4)
+        .map(|i| {
+            let position = (3 - i) * 8;
+            let mut byte = [Bit::Constant(false); 8];
+            byte.copy_from_slice(&word[position..position + 8]);
+            byte
+        })
+        .collect()
+}
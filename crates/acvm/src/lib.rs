@@ -3,8 +3,11 @@
 
 pub mod compiler;
 pub mod pwg;
+pub mod satisfiability;
+#[doc(hidden)]
+pub mod test_backend;
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 
 use acir::{
     circuit::{
@@ -15,23 +18,108 @@ use acir::{
     OPCODE,
 };
 
-use crate::pwg::{arithmetic::ArithmeticSolver, logic::LogicSolver};
+use crate::pwg::{
+    arithmetic::ArithmeticSolver, hint::HintSolver, log::LogSolver, logic::LogicSolver,
+    sorting::SortingSolver,
+};
 use num_bigint::BigUint;
 use num_traits::One;
 
 // re-export acir
 pub use acir;
 pub use acir::FieldElement;
+pub use satisfiability::{check_satisfiability, Unsatisfied};
 
 #[derive(PartialEq, Eq, Debug)]
 pub enum GateResolution {
-    Resolved,                  //Gate is solved
-    Skip,                      //Gate cannot be solved
-    UnknownError(String),      //Generic error
-    UnsupportedOpcode(OPCODE), //Unsupported Opcode
-    UnsatisfiedConstrain,      //Gate is not satisfied
+    Resolved, //Gate is solved
+    Skip,     //Gate cannot be solved
+    //These gates, given by their index in the circuit, wait on witnesses which no gate can assign
+    Stalled { gates: Vec<usize>, missing_witnesses: Vec<Witness> },
+    Failed(OpcodeResolutionError), //A gate cannot be solved, or its witnesses do not satisfy it
+}
+
+/// The reason for which the solver of a gate failed
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum OpcodeNotSolvable {
+    MissingWitness(Witness),
+    UnsatisfiedConstraint,
+    DivisionByZero,
+    UnsupportedOpcode(OPCODE),
+    // The gate does not have the shape its solver expects, for instance the wrong number of outputs
+    MalformedGate(String),
+    // The program of a hint directive failed while it was running
+    HintFailure(String),
+}
+
+impl std::fmt::Display for OpcodeNotSolvable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OpcodeNotSolvable::MissingWitness(witness) => {
+                write!(f, "the witness x{} has no assignment", witness.0)
+            }
+            OpcodeNotSolvable::UnsatisfiedConstraint => {
+                write!(f, "the constraint is not satisfied")
+            }
+            OpcodeNotSolvable::DivisionByZero => write!(f, "division by zero"),
+            OpcodeNotSolvable::UnsupportedOpcode(opcode) => {
+                write!(f, "the backend does not support the {} opcode", opcode)
+            }
+            OpcodeNotSolvable::MalformedGate(message) => write!(f, "malformed gate: {}", message),
+            OpcodeNotSolvable::HintFailure(message) => write!(f, "hint failed: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for OpcodeNotSolvable {}
+
+/// A gate of the circuit which the solver could not solve, along with the values of its witnesses
+/// at the time of the failure, `None` for the witnesses which were not assigned yet
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct OpcodeResolutionError {
+    pub gate_index: usize,
+    pub gate_kind: String,
+    pub witnesses: Vec<(Witness, Option<FieldElement>)>,
+    pub reason: OpcodeNotSolvable,
 }
 
+impl OpcodeResolutionError {
+    fn new(
+        gate_index: usize,
+        gate: &Gate,
+        initial_witness: &BTreeMap<Witness, FieldElement>,
+        reason: OpcodeNotSolvable,
+    ) -> OpcodeResolutionError {
+        let mut seen = BTreeSet::new();
+        let witnesses = gate
+            .witnesses()
+            .into_iter()
+            .filter(|witness| seen.insert(*witness))
+            .map(|witness| (witness, initial_witness.get(&witness).copied()))
+            .collect();
+        OpcodeResolutionError { gate_index, gate_kind: gate.name().to_string(), witnesses, reason }
+    }
+}
+
+impl std::fmt::Display for OpcodeResolutionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cannot solve the {} gate {}: {}", self.gate_kind, self.gate_index, self.reason)?;
+        for (i, (witness, value)) in self.witnesses.iter().enumerate() {
+            let separator = if i == 0 { " (" } else { ", " };
+            match value {
+                Some(value) => write!(f, "{}x{} = {}", separator, witness.0, value)?,
+                None => write!(f, "{}x{} is unknown", separator, witness.0)?,
+            }
+        }
+        if !self.witnesses.is_empty() {
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for OpcodeResolutionError {}
+
 pub trait Backend: SmartContract + ProofSystemCompiler + PartialWitnessGenerator {}
 
 /// This component will generate the backend specific output for
@@ -43,150 +131,231 @@ pub trait PartialWitnessGenerator {
         initial_witness: &mut BTreeMap<Witness, FieldElement>,
         gates: Vec<Gate>,
     ) -> GateResolution {
-        if gates.is_empty() {
-            return GateResolution::Resolved;
-        }
-        let mut unsolved_gates: Vec<Gate> = Vec::new();
-
-        for gate in gates.into_iter() {
-            let unsolved = match &gate {
-                Gate::Arithmetic(arith) => {
-                    let result = ArithmeticSolver::solve(initial_witness, arith);
-                    match result {
-                        GateResolution::Resolved => false,
-                        GateResolution::Skip => true,
-                        _ => return result,
+        self.solve_with_logger(initial_witness, gates, &mut |_| ())
+    }
+
+    /// Solves the gates like `solve`, and passes the messages of the log directives to the logger
+    /// as soon as their inputs are known
+    fn solve_with_logger(
+        &self,
+        initial_witness: &mut BTreeMap<Witness, FieldElement>,
+        gates: Vec<Gate>,
+        logger: &mut dyn FnMut(&str),
+    ) -> GateResolution {
+        self.solve_indexed(initial_witness, gates.into_iter().enumerate().collect(), logger)
+    }
+
+    /// Solves the gates, which are given with their index in the circuit
+    /// so that a gate which cannot be solved is reported with its index in `GateResolution::Failed`.
+    ///
+    /// Every gate is tried once in order. A gate which cannot be solved yet waits on its unknown
+    /// witnesses, and is tried again only once one of them has been assigned by another gate.
+    /// When no gate is left to try, the gates still waiting are reported with `GateResolution::Stalled`
+    fn solve_indexed(
+        &self,
+        initial_witness: &mut BTreeMap<Witness, FieldElement>,
+        gates: Vec<(usize, Gate)>,
+        logger: &mut dyn FnMut(&str),
+    ) -> GateResolution {
+        let mut queue: VecDeque<usize> = (0..gates.len()).collect();
+        let mut queued = vec![true; gates.len()];
+        let mut solved = vec![false; gates.len()];
+        // The gates, by their position in `gates`, which are waiting for a witness to be assigned
+        let mut waiting: HashMap<Witness, Vec<usize>> = HashMap::new();
+
+        while let Some(position) = queue.pop_front() {
+            queued[position] = false;
+            let (index, gate) = &gates[position];
+            match Self::solve_gate(initial_witness, gate, logger) {
+                Ok(GateResolution::Resolved) => {
+                    solved[position] = true;
+                    // A gate only assigns its own witnesses, so they are the only ones which can wake up other gates
+                    for witness in gate.witnesses() {
+                        for dependent in waiting.remove(&witness).unwrap_or_default() {
+                            if !solved[dependent] && !queued[dependent] {
+                                queued[dependent] = true;
+                                queue.push_back(dependent);
+                            }
+                        }
                     }
                 }
-                Gate::Range(w, r) => {
-                    if let Some(w_value) = initial_witness.get(w) {
-                        if w_value.num_bits() > *r {
-                            return GateResolution::UnsatisfiedConstrain;
+                Ok(GateResolution::Skip) => {
+                    for witness in gate.witnesses() {
+                        if !initial_witness.contains_key(&witness) {
+                            waiting.entry(witness).or_default().push(position);
                         }
-                        false
-                    } else {
-                        true
                     }
                 }
-                Gate::And(and_gate) => {
-                    !LogicSolver::solve_and_gate(initial_witness, and_gate)
-                    // We compute the result because the other gates may want to use the assignment to generate their assignments
+                Ok(resolution) => return resolution,
+                Err(reason) => {
+                    let error = OpcodeResolutionError::new(*index, gate, initial_witness, reason);
+                    return GateResolution::Failed(error);
                 }
-                Gate::Xor(xor_gate) => {
-                    !LogicSolver::solve_xor_gate(initial_witness, xor_gate)
-                    // We compute the result because the other gates may want to use the assignment to generate their assignments
+            }
+        }
+
+        let mut unsolved_gates = Vec::new();
+        let mut missing_witnesses = BTreeSet::new();
+        for ((index, gate), _) in gates.iter().zip(solved).filter(|(_, solved)| !solved) {
+            unsolved_gates.push(*index);
+            missing_witnesses.extend(
+                gate.witnesses()
+                    .into_iter()
+                    .filter(|witness| !initial_witness.contains_key(witness)),
+            );
+        }
+        if unsolved_gates.is_empty() {
+            GateResolution::Resolved
+        } else {
+            GateResolution::Stalled {
+                gates: unsolved_gates,
+                missing_witnesses: missing_witnesses.into_iter().collect(),
+            }
+        }
+    }
+
+    /// Tries to solve a single gate with the witnesses which are known so far.
+    /// Returns `GateResolution::Skip` when the gate needs witnesses which are not known yet
+    fn solve_gate(
+        initial_witness: &mut BTreeMap<Witness, FieldElement>,
+        gate: &Gate,
+        logger: &mut dyn FnMut(&str),
+    ) -> Result<GateResolution, OpcodeNotSolvable> {
+        match gate {
+            Gate::Arithmetic(arith) => ArithmeticSolver::solve(initial_witness, arith),
+            Gate::Range(w, r) => match initial_witness.get(w) {
+                Some(w_value) if w_value.num_bits() > *r => {
+                    Err(OpcodeNotSolvable::UnsatisfiedConstraint)
                 }
-                Gate::GadgetCall(gc) => {
-                    if let Err(op) = Self::solve_gadget_call(initial_witness, gc) {
-                        return GateResolution::UnsupportedOpcode(op);
-                    }
-                    false
+                Some(_) => Ok(GateResolution::Resolved),
+                None => Ok(GateResolution::Skip),
+            },
+            // We compute the result because the other gates may want to use the assignment to generate their assignments
+            Gate::And(and_gate) => {
+                Ok(resolved_if(LogicSolver::solve_and_gate(initial_witness, and_gate)))
+            }
+            Gate::Xor(xor_gate) => {
+                Ok(resolved_if(LogicSolver::solve_xor_gate(initial_witness, xor_gate)))
+            }
+            Gate::GadgetCall(gc) => {
+                if gc.inputs.iter().any(|input| !initial_witness.contains_key(&input.witness)) {
+                    return Ok(GateResolution::Skip);
                 }
-                Gate::Directive(directive) => match directive {
-                    Directive::Invert { x, result } => match initial_witness.get(x) {
-                        None => true,
-                        Some(val) => {
-                            let inverse = val.inverse();
-                            initial_witness.insert(*result, inverse);
-                            false
-                        }
-                    },
-                    Directive::Quotient { a, b, q, r } => {
-                        match (
-                            Self::get_value(a, initial_witness),
-                            Self::get_value(b, initial_witness),
-                        ) {
-                            (Some(val_a), Some(val_b)) => {
-                                let int_a = BigUint::from_bytes_be(&val_a.to_bytes());
-                                let int_b = BigUint::from_bytes_be(&val_b.to_bytes());
-                                let int_r = &int_a % &int_b;
-                                let int_q = &int_a / &int_b;
-
-                                initial_witness.insert(
-                                    *q,
-                                    FieldElement::from_be_bytes_reduce(&int_q.to_bytes_be()),
-                                );
-                                initial_witness.insert(
-                                    *r,
-                                    FieldElement::from_be_bytes_reduce(&int_r.to_bytes_be()),
-                                );
-                                false
-                            }
-                            _ => true,
-                        }
+                Self::solve_gadget_call(initial_witness, gc)?;
+                Ok(GateResolution::Resolved)
+            }
+            Gate::Directive(directive) => match directive {
+                Directive::Invert { x, result } => match initial_witness.get(x) {
+                    None => Ok(GateResolution::Skip),
+                    Some(val) => {
+                        let inverse = val.inverse();
+                        initial_witness.insert(*result, inverse);
+                        Ok(GateResolution::Resolved)
                     }
-                    Directive::Truncate { a, b, c, bit_size } => match initial_witness.get(a) {
-                        Some(val_a) => {
-                            let pow: BigUint = BigUint::one() << bit_size;
-
-                            let int_a = BigUint::from_bytes_be(&val_a.to_bytes());
-                            let int_b: BigUint = &int_a % &pow;
-                            let int_c: BigUint = (&int_a - &int_b) / &pow;
-
-                            initial_witness.insert(
-                                *b,
-                                FieldElement::from_be_bytes_reduce(&int_b.to_bytes_be()),
-                            );
-                            initial_witness.insert(
-                                *c,
-                                FieldElement::from_be_bytes_reduce(&int_c.to_bytes_be()),
-                            );
-                            false
-                        }
-                        _ => true,
-                    },
-                    Directive::Split { a, b, bit_size } => match initial_witness.get(a) {
-                        Some(val_a) => {
-                            let a_big = BigUint::from_bytes_be(&val_a.to_bytes());
-                            for i in 0..*bit_size {
-                                let j = i as usize;
-                                let v = if a_big.bit(j as u64) {
-                                    FieldElement::one()
-                                } else {
-                                    FieldElement::zero()
-                                };
-                                initial_witness.insert(b[j], v);
-                            }
-                            false
+                },
+                Directive::Quotient { a, b, q, r } => {
+                    match (Self::get_value(a, initial_witness), Self::get_value(b, initial_witness))
+                    {
+                        (Some(_), Some(val_b)) if val_b.is_zero() => {
+                            Err(OpcodeNotSolvable::DivisionByZero)
                         }
-                        _ => true,
-                    },
-                    Directive::Oddrange { a, b, r, bit_size } => match initial_witness.get(a) {
-                        Some(val_a) => {
+                        (Some(val_a), Some(val_b)) => {
                             let int_a = BigUint::from_bytes_be(&val_a.to_bytes());
-                            let pow: BigUint = BigUint::one() << (bit_size - 1);
-                            if int_a >= (&pow << 1) {
-                                return GateResolution::UnsatisfiedConstrain;
-                            }
-                            let bb = &int_a & &pow;
-                            let int_r = &int_a - &bb;
-                            let int_b = &bb >> (bit_size - 1);
+                            let int_b = BigUint::from_bytes_be(&val_b.to_bytes());
+                            let int_r = &int_a % &int_b;
+                            let int_q = &int_a / &int_b;
 
                             initial_witness.insert(
-                                *b,
-                                FieldElement::from_be_bytes_reduce(&int_b.to_bytes_be()),
+                                *q,
+                                FieldElement::from_be_bytes_reduce(&int_q.to_bytes_be()),
                             );
                             initial_witness.insert(
                                 *r,
                                 FieldElement::from_be_bytes_reduce(&int_r.to_bytes_be()),
                             );
-                            false
+                            Ok(GateResolution::Resolved)
                         }
-                        _ => true,
-                    },
+                        _ => Ok(GateResolution::Skip),
+                    }
+                }
+                Directive::Truncate { a, b, c, bit_size } => match initial_witness.get(a) {
+                    Some(val_a) => {
+                        let pow: BigUint = BigUint::one() << bit_size;
+
+                        let int_a = BigUint::from_bytes_be(&val_a.to_bytes());
+                        let int_b: BigUint = &int_a % &pow;
+                        let int_c: BigUint = (&int_a - &int_b) / &pow;
+
+                        initial_witness
+                            .insert(*b, FieldElement::from_be_bytes_reduce(&int_b.to_bytes_be()));
+                        initial_witness
+                            .insert(*c, FieldElement::from_be_bytes_reduce(&int_c.to_bytes_be()));
+                        Ok(GateResolution::Resolved)
+                    }
+                    _ => Ok(GateResolution::Skip),
                 },
-            };
-            if unsolved {
-                unsolved_gates.push(gate);
-            }
+                Directive::Split { b, bit_size, .. } if b.len() < *bit_size as usize => {
+                    Err(OpcodeNotSolvable::MalformedGate(format!(
+                        "{} bits are split into only {} witnesses",
+                        bit_size,
+                        b.len()
+                    )))
+                }
+                Directive::Split { a, b, bit_size } => match initial_witness.get(a) {
+                    Some(val_a) => {
+                        let a_big = BigUint::from_bytes_be(&val_a.to_bytes());
+                        for i in 0..*bit_size {
+                            let j = i as usize;
+                            let v = if a_big.bit(j as u64) {
+                                FieldElement::one()
+                            } else {
+                                FieldElement::zero()
+                            };
+                            initial_witness.insert(b[j], v);
+                        }
+                        Ok(GateResolution::Resolved)
+                    }
+                    _ => Ok(GateResolution::Skip),
+                },
+                Directive::Oddrange { bit_size: 0, .. } => Err(OpcodeNotSolvable::MalformedGate(
+                    "the bit size of an oddrange cannot be zero".to_string(),
+                )),
+                Directive::Oddrange { a, b, r, bit_size } => match initial_witness.get(a) {
+                    Some(val_a) => {
+                        let int_a = BigUint::from_bytes_be(&val_a.to_bytes());
+                        let pow: BigUint = BigUint::one() << (bit_size - 1);
+                        if int_a >= (&pow << 1) {
+                            return Err(OpcodeNotSolvable::UnsatisfiedConstraint);
+                        }
+                        let bb = &int_a & &pow;
+                        let int_r = &int_a - &bb;
+                        let int_b = &bb >> (bit_size - 1);
+
+                        initial_witness
+                            .insert(*b, FieldElement::from_be_bytes_reduce(&int_b.to_bytes_be()));
+                        initial_witness
+                            .insert(*r, FieldElement::from_be_bytes_reduce(&int_r.to_bytes_be()));
+                        Ok(GateResolution::Resolved)
+                    }
+                    _ => Ok(GateResolution::Skip),
+                },
+                Directive::Hint { inputs, outputs, program } => {
+                    HintSolver::solve::<Self>(initial_witness, inputs, outputs, program)
+                }
+                Directive::PermutationSort { inputs, bits, sort_by } => {
+                    SortingSolver::solve::<Self>(initial_witness, inputs, bits, sort_by)
+                }
+                Directive::Log { format, inputs, predicate } => {
+                    LogSolver::solve::<Self>(initial_witness, format, inputs, predicate, logger)
+                }
+            },
         }
-        self.solve(initial_witness, unsolved_gates)
     }
 
     fn solve_gadget_call(
         initial_witness: &mut BTreeMap<Witness, FieldElement>,
         gc: &GadgetCall,
-    ) -> Result<(), OPCODE>;
+    ) -> Result<(), OpcodeNotSolvable>;
 
     fn get_value(
         a: &Expression,
@@ -211,6 +380,14 @@ pub trait PartialWitnessGenerator {
     }
 }
 
+fn resolved_if(solved: bool) -> GateResolution {
+    if solved {
+        GateResolution::Resolved
+    } else {
+        GateResolution::Skip
+    }
+}
+
 pub trait SmartContract {
     // Takes a verification  key and produces a smart contract
     // The platform indicator allows a backend to support multiple smart contract platforms
@@ -236,6 +413,13 @@ pub trait ProofSystemCompiler {
     /// if the language and proof system does not line up.
     fn np_language(&self) -> Language;
 
+    /// Returns true if the proof system can prove the gate as it is.
+    /// The gates which are not supported are rewritten into arithmetic gates by `compiler::compile`
+    /// when they have a fallback, see `compiler::has_fallback`, and the circuit is rejected otherwise.
+    fn supports_gate(&self, _gate: &Gate) -> bool {
+        true
+    }
+
     /// Creates a Proof given the circuit description and the witness values.
     /// It is important to note that the intermediate witnesses for blackbox functions will not generated
     /// This is the responsibility of the proof system.
@@ -276,3 +460,92 @@ pub fn hash_constraint_system(cs: &Circuit) {
     let result = hasher.finalize();
     println!("hash of constraint system : {:x?}", &result[..]);
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use acir::{
+        circuit::{gate::Directive, Gate},
+        native_types::{Expression, Witness},
+        FieldElement,
+    };
+
+    use crate::{
+        test_backend::MinimalBackend, GateResolution, OpcodeNotSolvable, OpcodeResolutionError,
+        PartialWitnessGenerator,
+    };
+
+    // x(to) = x(from) + 1
+    fn increment(from: u32, to: u32) -> Gate {
+        let expression = &Expression::from(&Witness(to)) - &Witness(from);
+        Gate::Arithmetic(expression - &FieldElement::one())
+    }
+
+    #[test]
+    fn solves_gates_out_of_order() {
+        // Each gate needs the witness assigned by the next one
+        let gates: Vec<_> = (1..100).rev().map(|i| increment(i, i + 1)).collect();
+        let mut witness = BTreeMap::from([(Witness(1), FieldElement::zero())]);
+        assert_eq!(MinimalBackend.solve(&mut witness, gates), GateResolution::Resolved);
+        assert_eq!(witness[&Witness(100)], FieldElement::from(99_i128));
+    }
+
+    #[test]
+    fn reports_stalled_gates() {
+        let gates = vec![
+            increment(1, 2),
+            // x4 = x3 + 1 can never be solved since x3 is not assigned by any gate
+            increment(3, 4),
+            Gate::Range(Witness(4), 8),
+            Gate::Range(Witness(2), 8),
+        ];
+        let mut witness = BTreeMap::from([(Witness(1), FieldElement::zero())]);
+        assert_eq!(
+            MinimalBackend.solve(&mut witness, gates),
+            GateResolution::Stalled {
+                gates: vec![1, 2],
+                missing_witnesses: vec![Witness(3), Witness(4)]
+            }
+        );
+        assert_eq!(witness[&Witness(2)], FieldElement::one());
+    }
+
+    #[test]
+    fn reports_failing_gate() {
+        let gates = vec![
+            increment(1, 2),
+            Gate::Directive(Directive::Quotient {
+                a: Expression::from(&Witness(2)),
+                b: Expression::from(&Witness(3)),
+                q: Witness(4),
+                r: Witness(5),
+            }),
+        ];
+        let mut witness = BTreeMap::from([
+            (Witness(1), FieldElement::zero()),
+            (Witness(3), FieldElement::zero()),
+        ]);
+        let error = OpcodeResolutionError {
+            gate_index: 1,
+            gate_kind: "quotient".to_string(),
+            witnesses: vec![
+                (Witness(2), Some(FieldElement::one())),
+                (Witness(3), Some(FieldElement::zero())),
+                (Witness(4), None),
+                (Witness(5), None),
+            ],
+            reason: OpcodeNotSolvable::DivisionByZero,
+        };
+        assert_eq!(MinimalBackend.solve(&mut witness, gates), GateResolution::Failed(error));
+
+        let mut witness = BTreeMap::from([(Witness(1), FieldElement::from(256_i128))]);
+        match MinimalBackend.solve(&mut witness, vec![Gate::Range(Witness(1), 8)]) {
+            GateResolution::Failed(error) => assert_eq!(
+                error.to_string(),
+                "cannot solve the range gate 0: the constraint is not satisfied (x1 = 256)"
+            ),
+            resolution => panic!("the range gate should fail, found {:?}", resolution),
+        }
+    }
+}
@@ -2,7 +2,7 @@ use acir::native_types::{Expression, Witness};
 use noir_field::FieldElement;
 use std::collections::BTreeMap;
 
-use crate::GateResolution;
+use crate::{GateResolution, OpcodeNotSolvable};
 
 /// An Arithmetic solver will take a Circuit's arithmetic gates with witness assignments
 /// and create the other witness variables
@@ -26,32 +26,34 @@ impl ArithmeticSolver {
     pub fn solve(
         initial_witness: &mut BTreeMap<Witness, FieldElement>,
         gate: &Expression,
-    ) -> GateResolution {
+    ) -> Result<GateResolution, OpcodeNotSolvable> {
         // Evaluate multiplication term
-        let mul_result = ArithmeticSolver::solve_mul_term(gate, initial_witness);
+        let mul_result = ArithmeticSolver::solve_mul_term(gate, initial_witness)?;
         // Evaluate the fan-in terms
         let gate_status = ArithmeticSolver::solve_fan_in_term(gate, initial_witness);
 
         match (mul_result, gate_status) {
-            (MulTerm::TooManyUnknowns, _) | (_, GateStatus::GateUnsolvable) => GateResolution::Skip,
+            (MulTerm::TooManyUnknowns, _) | (_, GateStatus::GateUnsolvable) => {
+                Ok(GateResolution::Skip)
+            }
             (MulTerm::OneUnknown(q, w1), GateStatus::GateSolvable(a, (b, w2))) => {
                 if w1 == w2 {
                     // We have one unknown so we can solve the equation
                     let total_sum = a + gate.q_c;
                     if (q + b).is_zero() {
                         if !total_sum.is_zero() {
-                            GateResolution::UnsatisfiedConstrain
+                            Err(OpcodeNotSolvable::UnsatisfiedConstraint)
                         } else {
-                            GateResolution::Resolved
+                            Ok(GateResolution::Resolved)
                         }
                     } else {
                         let assignment = -total_sum / (q + b);
                         // Add this into the witness assignments
                         initial_witness.insert(w1, assignment);
-                        GateResolution::Resolved
+                        Ok(GateResolution::Resolved)
                     }
                 } else {
-                    GateResolution::Skip
+                    Ok(GateResolution::Skip)
                 }
             }
             (MulTerm::OneUnknown(partial_prod, unknown_var), GateStatus::GateSatisfied(sum)) => {
@@ -62,24 +64,24 @@ impl ArithmeticSolver {
                 let total_sum = sum + gate.q_c;
                 if partial_prod.is_zero() {
                     if !total_sum.is_zero() {
-                        GateResolution::UnsatisfiedConstrain
+                        Err(OpcodeNotSolvable::UnsatisfiedConstraint)
                     } else {
-                        GateResolution::Resolved
+                        Ok(GateResolution::Resolved)
                     }
                 } else {
                     let assignment = -(total_sum / partial_prod);
                     // Add this into the witness assignments
                     initial_witness.insert(unknown_var, assignment);
-                    GateResolution::Resolved
+                    Ok(GateResolution::Resolved)
                 }
             }
             (MulTerm::Solved(a), GateStatus::GateSatisfied(b)) => {
                 // All the variables in the MulTerm are solved and the Fan-in is also solved
                 // There is nothing to solve
                 if !(a + b + gate.q_c).is_zero() {
-                    GateResolution::UnsatisfiedConstrain
+                    Err(OpcodeNotSolvable::UnsatisfiedConstraint)
                 } else {
-                    GateResolution::Resolved
+                    Ok(GateResolution::Resolved)
                 }
             }
             (
@@ -92,15 +94,15 @@ impl ArithmeticSolver {
                 let total_sum = total_prod + partial_sum + gate.q_c;
                 if coeff.is_zero() {
                     if !total_sum.is_zero() {
-                        GateResolution::UnsatisfiedConstrain
+                        Err(OpcodeNotSolvable::UnsatisfiedConstraint)
                     } else {
-                        GateResolution::Resolved
+                        Ok(GateResolution::Resolved)
                     }
                 } else {
                     let assignment = -(total_sum / coeff);
                     // Add this into the witness assignments
                     initial_witness.insert(unknown_var, assignment);
-                    GateResolution::Resolved
+                    Ok(GateResolution::Resolved)
                 }
             }
         }
@@ -113,11 +115,11 @@ impl ArithmeticSolver {
     fn solve_mul_term(
         arith_gate: &Expression,
         witness_assignments: &BTreeMap<Witness, FieldElement>,
-    ) -> MulTerm {
+    ) -> Result<MulTerm, OpcodeNotSolvable> {
         // First note that the mul term can only contain one/zero term
         // We are assuming it has been optimised.
         match arith_gate.mul_terms.len() {
-            0 => MulTerm::Solved(FieldElement::zero()),
+            0 => Ok(MulTerm::Solved(FieldElement::zero())),
             1 => {
                 let q_m = &arith_gate.mul_terms[0].0;
                 let w_l = &arith_gate.mul_terms[0].1;
@@ -127,14 +129,17 @@ impl ArithmeticSolver {
                 let w_l_value = witness_assignments.get(w_l);
                 let w_r_value = witness_assignments.get(w_r);
 
-                match (w_l_value, w_r_value) {
+                Ok(match (w_l_value, w_r_value) {
                     (None, None) => MulTerm::TooManyUnknowns,
                     (Some(w_l), Some(w_r)) => MulTerm::Solved(*q_m * *w_l * *w_r),
                     (None, Some(w_r)) => MulTerm::OneUnknown(*q_m * *w_r, *w_l),
                     (Some(w_l), None) => MulTerm::OneUnknown(*q_m * *w_l, *w_r),
-                }
+                })
             }
-            _ => panic!("Mul term in the arithmetic gate must contain either zero or one term"),
+            _ => Err(OpcodeNotSolvable::MalformedGate(
+                "the mul term of an arithmetic gate must contain either zero or one term"
+                    .to_string(),
+            )),
         }
     }
 
@@ -217,8 +222,8 @@ fn arithmetic_smoke_test() {
     values.insert(c, FieldElement::from(1_i128));
     values.insert(d, FieldElement::from(1_i128));
 
-    assert_eq!(ArithmeticSolver::solve(&mut values, &gate_a), GateResolution::Resolved);
-    assert_eq!(ArithmeticSolver::solve(&mut values, &gate_b), GateResolution::Resolved);
+    assert_eq!(ArithmeticSolver::solve(&mut values, &gate_a), Ok(GateResolution::Resolved));
+    assert_eq!(ArithmeticSolver::solve(&mut values, &gate_b), Ok(GateResolution::Resolved));
 
     assert_eq!(values.get(&a).unwrap(), &FieldElement::from(4_i128));
 }
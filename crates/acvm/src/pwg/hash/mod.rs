@@ -4,33 +4,43 @@ use acir::{circuit::gate::GadgetCall, native_types::Witness};
 use blake2::{Blake2s, Digest};
 use noir_field::FieldElement;
 use sha2::Sha256;
+use sha3::Keccak256;
 
-pub fn blake2s(initial_witness: &mut BTreeMap<Witness, FieldElement>, gadget_call: &GadgetCall) {
+use crate::pwg::{check_output_count, input_to_value};
+use crate::OpcodeNotSolvable;
+
+pub fn blake2s(
+    initial_witness: &mut BTreeMap<Witness, FieldElement>,
+    gadget_call: &GadgetCall,
+) -> Result<(), OpcodeNotSolvable> {
     generic_hash_256::<Blake2s>(initial_witness, gadget_call)
 }
 
-pub fn sha256(initial_witness: &mut BTreeMap<Witness, FieldElement>, gadget_call: &GadgetCall) {
+pub fn sha256(
+    initial_witness: &mut BTreeMap<Witness, FieldElement>,
+    gadget_call: &GadgetCall,
+) -> Result<(), OpcodeNotSolvable> {
     generic_hash_256::<Sha256>(initial_witness, gadget_call)
 }
 
+pub fn keccak256(
+    initial_witness: &mut BTreeMap<Witness, FieldElement>,
+    gadget_call: &GadgetCall,
+) -> Result<(), OpcodeNotSolvable> {
+    generic_hash_256::<Keccak256>(initial_witness, gadget_call)
+}
+
 fn generic_hash_256<D: Digest>(
     initial_witness: &mut BTreeMap<Witness, FieldElement>,
     gadget_call: &GadgetCall,
-) {
+) -> Result<(), OpcodeNotSolvable> {
+    check_output_count(gadget_call, 32)?;
     let mut hasher = D::new();
 
     // For each input in the vector of inputs, check if we have their witness assignments (Can do this outside of match, since they all have inputs)
     for input_index in gadget_call.inputs.iter() {
-        let witness = &input_index.witness;
-        let num_bits = input_index.num_bits;
-
-        let witness_assignment = initial_witness.get(witness);
-        let assignment = match witness_assignment {
-            None => panic!("cannot find witness assignment for {:?}", witness),
-            Some(assignment) => assignment,
-        };
-
-        let bytes = assignment.fetch_nearest_bytes(num_bits as usize);
+        let assignment = input_to_value(initial_witness, input_index)?;
+        let bytes = assignment.fetch_nearest_bytes(input_index.num_bits as usize);
         hasher.update(bytes);
     }
     let result = hasher.finalize();
@@ -38,4 +48,76 @@ fn generic_hash_256<D: Digest>(
         initial_witness
             .insert(gadget_call.outputs[i], FieldElement::from_be_bytes_reduce(&[result[i]]));
     }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use acir::{
+        circuit::gate::{GadgetCall, GadgetInput},
+        native_types::Witness,
+        OPCODE,
+    };
+    use noir_field::FieldElement;
+
+    use super::keccak256;
+
+    // Hashes the bytes with the keccak256 gadget and returns the digest in hexadecimal
+    fn solve_keccak256(message: &[u8]) -> String {
+        let num_inputs = message.len() as u32;
+        let call = GadgetCall {
+            name: OPCODE::Keccak256,
+            inputs: (1..=num_inputs)
+                .map(|i| GadgetInput { witness: Witness(i), num_bits: 8 })
+                .collect(),
+            outputs: (1..=32).map(|i| Witness(num_inputs + i)).collect(),
+        };
+        let mut witness: BTreeMap<_, _> = message
+            .iter()
+            .zip(&call.inputs)
+            .map(|(byte, input)| (input.witness, FieldElement::from(*byte as u128)))
+            .collect();
+
+        keccak256(&mut witness, &call).unwrap();
+        let digest: Vec<u8> =
+            call.outputs.iter().map(|output| witness[output].to_u128() as u8).collect();
+        hex::encode(digest)
+    }
+
+    #[test]
+    fn ethereum_test_vectors() {
+        // The hash of empty code
+        assert_eq!(
+            solve_keccak256(b""),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        // The root of an empty trie, which is the hash of the RLP encoding of the empty string
+        assert_eq!(
+            solve_keccak256(&[0x80]),
+            "56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+        );
+        // The hash of an empty list of uncles, which is RLP encoded as 0xc0
+        assert_eq!(
+            solve_keccak256(&[0xc0]),
+            "1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347"
+        );
+        // The topic of the ERC-20 Transfer event
+        assert_eq!(
+            solve_keccak256(b"Transfer(address,address,uint256)"),
+            "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_output_count() {
+        let call = GadgetCall {
+            name: OPCODE::Keccak256,
+            inputs: vec![GadgetInput { witness: Witness(1), num_bits: 8 }],
+            outputs: vec![Witness(2)],
+        };
+        let mut witness = BTreeMap::from([(Witness(1), FieldElement::one())]);
+        assert!(keccak256(&mut witness, &call).is_err());
+    }
 }
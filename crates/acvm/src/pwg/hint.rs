@@ -0,0 +1,379 @@
+use acir::circuit::hint::{HintBinaryOp, HintNumeric, HintOp, HintProgram, Register};
+use acir::native_types::{Expression, Witness};
+use noir_field::FieldElement;
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::{One, Zero};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+use crate::{GateResolution, OpcodeNotSolvable, PartialWitnessGenerator};
+
+/// Upper bound on the number of instructions a hint program may execute,
+/// so that a non-terminating hint cannot hang the solver.
+const MAX_HINT_STEPS: usize = 1 << 24;
+
+/// Interprets the program of a Hint directive in order to assign its output witnesses
+pub struct HintSolver;
+
+impl HintSolver {
+    /// Returns Skip if some inputs are not yet known
+    pub fn solve<P: PartialWitnessGenerator + ?Sized>(
+        initial_witness: &mut BTreeMap<Witness, FieldElement>,
+        inputs: &[Expression],
+        outputs: &[Witness],
+        program: &HintProgram,
+    ) -> Result<GateResolution, OpcodeNotSolvable> {
+        let mut values = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            match P::get_value(input, initial_witness) {
+                Some(value) => values.push(value),
+                None => return Ok(GateResolution::Skip),
+            }
+        }
+
+        let results = HintSolver::execute(program, &values)?;
+        if results.len() != outputs.len() {
+            return Err(OpcodeNotSolvable::MalformedGate(format!(
+                "hint program returned {} values but {} outputs were expected",
+                results.len(),
+                outputs.len()
+            )));
+        }
+        for (witness, value) in outputs.iter().zip(results) {
+            initial_witness.insert(*witness, value);
+        }
+        Ok(GateResolution::Resolved)
+    }
+
+    /// Runs the program on the given input values and returns the values of its output registers
+    pub fn execute(
+        program: &HintProgram,
+        inputs: &[FieldElement],
+    ) -> Result<Vec<FieldElement>, OpcodeNotSolvable> {
+        if inputs.len() != program.inputs.len() {
+            return Err(OpcodeNotSolvable::MalformedGate(format!(
+                "hint program expects {} inputs but got {}",
+                program.inputs.len(),
+                inputs.len()
+            )));
+        }
+        let mut registers = vec![FieldElement::zero(); program.num_registers as usize];
+        for (register, value) in program.inputs.iter().zip(inputs) {
+            *get_mut(&mut registers, *register)? = *value;
+        }
+
+        let mut pc = 0;
+        let mut steps = 0;
+        while pc < program.instructions.len() {
+            steps += 1;
+            if steps > MAX_HINT_STEPS {
+                return Err(OpcodeNotSolvable::HintFailure(
+                    "hint program exceeded the maximum number of steps".to_string(),
+                ));
+            }
+
+            match &program.instructions[pc] {
+                HintOp::Const { destination, value } => {
+                    *get_mut(&mut registers, *destination)? = *value
+                }
+                HintOp::Mov { destination, source } => {
+                    let value = get(&registers, *source)?;
+                    *get_mut(&mut registers, *destination)? = value;
+                }
+                HintOp::Binary { op, numeric, lhs, rhs, destination } => {
+                    let lhs = get(&registers, *lhs)?;
+                    let rhs = get(&registers, *rhs)?;
+                    let result = evaluate_binary(*op, *numeric, lhs, rhs)?;
+                    *get_mut(&mut registers, *destination)? = result;
+                }
+                HintOp::Not { numeric, source, destination } => {
+                    let value = get(&registers, *source)?;
+                    let result = match numeric {
+                        HintNumeric::Field => {
+                            return Err(OpcodeNotSolvable::MalformedGate(
+                                "cannot apply a bitwise not to a field element".to_string(),
+                            ))
+                        }
+                        HintNumeric::Unsigned(bit_size) | HintNumeric::Signed(bit_size) => {
+                            let mask = (BigUint::one() << *bit_size) - BigUint::one();
+                            to_field(&(mask ^ to_biguint(value)))
+                        }
+                    };
+                    *get_mut(&mut registers, *destination)? = result;
+                }
+                HintOp::Truncate { source, destination, bit_size } => {
+                    let value = to_biguint(get(&registers, *source)?);
+                    let result = value % (BigUint::one() << *bit_size);
+                    *get_mut(&mut registers, *destination)? = to_field(&result);
+                }
+                HintOp::Load { destination, base, index } => {
+                    let address = address(base, get(&registers, *index)?)?;
+                    let value = get(&registers, address)?;
+                    *get_mut(&mut registers, *destination)? = value;
+                }
+                HintOp::Store { base, index, source } => {
+                    let address = address(base, get(&registers, *index)?)?;
+                    let value = get(&registers, *source)?;
+                    *get_mut(&mut registers, address)? = value;
+                }
+                HintOp::Jump { target } => {
+                    pc = *target;
+                    continue;
+                }
+                HintOp::JumpIfNot { condition, target } => {
+                    if get(&registers, *condition)?.is_zero() {
+                        pc = *target;
+                        continue;
+                    }
+                }
+                HintOp::Trap => return Err(OpcodeNotSolvable::UnsatisfiedConstraint),
+            }
+            pc += 1;
+        }
+
+        program.outputs.iter().map(|register| get(&registers, *register)).collect()
+    }
+}
+
+fn get(registers: &[FieldElement], register: Register) -> Result<FieldElement, OpcodeNotSolvable> {
+    registers.get(register.0 as usize).copied().ok_or_else(|| invalid_register(register))
+}
+
+fn get_mut(
+    registers: &mut [FieldElement],
+    register: Register,
+) -> Result<&mut FieldElement, OpcodeNotSolvable> {
+    registers.get_mut(register.0 as usize).ok_or_else(|| invalid_register(register))
+}
+
+fn invalid_register(register: Register) -> OpcodeNotSolvable {
+    OpcodeNotSolvable::MalformedGate(format!("hint program accessed invalid register {}", register))
+}
+
+fn address(base: &Register, index: FieldElement) -> Result<Register, OpcodeNotSolvable> {
+    let offset = index.try_to_u64().and_then(|index| u32::try_from(index).ok());
+    match offset.and_then(|offset| base.0.checked_add(offset)) {
+        Some(address) => Ok(Register(address)),
+        None => Err(OpcodeNotSolvable::HintFailure(format!("index {} is out of bounds", index))),
+    }
+}
+
+fn to_biguint(value: FieldElement) -> BigUint {
+    BigUint::from_bytes_be(&value.to_bytes())
+}
+
+fn to_field(value: &BigUint) -> FieldElement {
+    FieldElement::from_be_bytes_reduce(&value.to_bytes_be())
+}
+
+/// Interprets a bit_size integer as a two's complement signed integer
+/// A 0-bit integer can only hold 0
+fn to_bigint(value: FieldElement, bit_size: u32) -> BigInt {
+    if bit_size == 0 {
+        return BigInt::zero();
+    }
+    let value = to_biguint(value);
+    if value.bit(bit_size as u64 - 1) {
+        BigInt::from(value) - (BigInt::one() << bit_size)
+    } else {
+        BigInt::from(value)
+    }
+}
+
+/// Converts a signed integer back to its two's complement representation on bit_size bits
+fn from_bigint(value: &BigInt, bit_size: u32) -> FieldElement {
+    let modulus = BigInt::one() << bit_size;
+    let value = ((value % &modulus) + &modulus) % &modulus;
+    let (_, bytes) = value.to_bytes_be();
+    FieldElement::from_be_bytes_reduce(&bytes)
+}
+
+fn evaluate_binary(
+    op: HintBinaryOp,
+    numeric: HintNumeric,
+    lhs: FieldElement,
+    rhs: FieldElement,
+) -> Result<FieldElement, OpcodeNotSolvable> {
+    let bool_to_field = |b: bool| if b { FieldElement::one() } else { FieldElement::zero() };
+    let division_by_zero = || OpcodeNotSolvable::DivisionByZero;
+
+    let bit_size = match numeric {
+        HintNumeric::Field => {
+            return match op {
+                HintBinaryOp::Add => Ok(lhs + rhs),
+                HintBinaryOp::Sub => Ok(lhs - rhs),
+                HintBinaryOp::Mul => Ok(lhs * rhs),
+                HintBinaryOp::Div => {
+                    if rhs.is_zero() {
+                        Err(division_by_zero())
+                    } else {
+                        Ok(lhs / rhs)
+                    }
+                }
+                HintBinaryOp::Eq => Ok(bool_to_field(lhs == rhs)),
+                HintBinaryOp::Lt => Ok(bool_to_field(to_biguint(lhs) < to_biguint(rhs))),
+                HintBinaryOp::Le => Ok(bool_to_field(to_biguint(lhs) <= to_biguint(rhs))),
+                _ => Err(OpcodeNotSolvable::MalformedGate(format!(
+                    "operation {:?} is not supported on field elements",
+                    op
+                ))),
+            };
+        }
+        HintNumeric::Unsigned(bit_size) | HintNumeric::Signed(bit_size) => bit_size,
+    };
+
+    let modulus = BigUint::one() << bit_size;
+    let (l, r) = (to_biguint(lhs), to_biguint(rhs));
+    let result = match op {
+        HintBinaryOp::Add => (l + r) % &modulus,
+        HintBinaryOp::Sub => (l + &modulus - (r % &modulus)) % &modulus,
+        HintBinaryOp::Mul => (l * r) % &modulus,
+        HintBinaryOp::And => l & r,
+        HintBinaryOp::Or => l | r,
+        HintBinaryOp::Xor => l ^ r,
+        HintBinaryOp::Eq => return Ok(bool_to_field(l == r)),
+        HintBinaryOp::Shl => match r.to_u32_digits().as_slice() {
+            [] => l,
+            [shift] if *shift < bit_size => (l << *shift) % &modulus,
+            _ => BigUint::zero(),
+        },
+        HintBinaryOp::Div
+        | HintBinaryOp::Rem
+        | HintBinaryOp::Lt
+        | HintBinaryOp::Le
+        | HintBinaryOp::Shr => {
+            if let HintNumeric::Signed(_) = numeric {
+                let (l, r) = (to_bigint(lhs, bit_size), to_bigint(rhs, bit_size));
+                return match op {
+                    HintBinaryOp::Div if r.is_zero() => Err(division_by_zero()),
+                    HintBinaryOp::Rem if r.is_zero() => Err(division_by_zero()),
+                    HintBinaryOp::Div => Ok(from_bigint(&(l / r), bit_size)),
+                    HintBinaryOp::Rem => Ok(from_bigint(&(l % r), bit_size)),
+                    HintBinaryOp::Lt => Ok(bool_to_field(l < r)),
+                    HintBinaryOp::Le => Ok(bool_to_field(l <= r)),
+                    _ => {
+                        // Arithmetic shift, which rounds towards negative infinity
+                        let shift = rhs.try_to_u64().unwrap_or(u64::MAX).min(bit_size as u64);
+                        let divisor = BigInt::one() << shift;
+                        let quotient = if l.sign() == Sign::Minus {
+                            (l - &divisor + BigInt::one()) / divisor
+                        } else {
+                            l / divisor
+                        };
+                        Ok(from_bigint(&quotient, bit_size))
+                    }
+                };
+            }
+            match op {
+                HintBinaryOp::Div | HintBinaryOp::Rem if r.is_zero() => {
+                    return Err(division_by_zero())
+                }
+                HintBinaryOp::Div => l / r,
+                HintBinaryOp::Rem => l % r,
+                HintBinaryOp::Lt => return Ok(bool_to_field(l < r)),
+                HintBinaryOp::Le => return Ok(bool_to_field(l <= r)),
+                _ => match r.to_u32_digits().as_slice() {
+                    [] => l,
+                    [shift] if *shift < bit_size => l >> *shift,
+                    _ => BigUint::zero(),
+                },
+            }
+        }
+    };
+    Ok(to_field(&result))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn integer_square_root() {
+        // r2 = 0; while (r2+1)*(r2+1) <= r0 { r2 += 1 }
+        let numeric = HintNumeric::Unsigned(32);
+        let r = Register;
+        let program = HintProgram {
+            num_registers: 5,
+            inputs: vec![r(0)],
+            outputs: vec![r(2)],
+            instructions: vec![
+                HintOp::Const { destination: r(1), value: FieldElement::one() },
+                HintOp::Const { destination: r(2), value: FieldElement::zero() },
+                // loop:
+                HintOp::Binary {
+                    op: HintBinaryOp::Add,
+                    numeric,
+                    lhs: r(2),
+                    rhs: r(1),
+                    destination: r(3),
+                },
+                HintOp::Binary {
+                    op: HintBinaryOp::Mul,
+                    numeric,
+                    lhs: r(3),
+                    rhs: r(3),
+                    destination: r(4),
+                },
+                HintOp::Binary {
+                    op: HintBinaryOp::Le,
+                    numeric,
+                    lhs: r(4),
+                    rhs: r(0),
+                    destination: r(4),
+                },
+                HintOp::JumpIfNot { condition: r(4), target: 8 },
+                HintOp::Binary {
+                    op: HintBinaryOp::Add,
+                    numeric,
+                    lhs: r(2),
+                    rhs: r(1),
+                    destination: r(2),
+                },
+                HintOp::Jump { target: 2 },
+            ],
+        };
+
+        let result = HintSolver::execute(&program, &[FieldElement::from(1000_u128)]).unwrap();
+        assert_eq!(result, vec![FieldElement::from(31_u128)]);
+    }
+
+    #[test]
+    fn signed_division_and_trap() {
+        let numeric = HintNumeric::Signed(8);
+        let minus_seven = FieldElement::from(249_u128); // -7 on 8 bits
+        let two = FieldElement::from(2_u128);
+        let quotient = evaluate_binary(HintBinaryOp::Div, numeric, minus_seven, two).unwrap();
+        assert_eq!(quotient, FieldElement::from(253_u128)); // -3
+        let shifted = evaluate_binary(HintBinaryOp::Shr, numeric, minus_seven, FieldElement::one());
+        assert_eq!(shifted, Ok(FieldElement::from(252_u128))); // -4
+
+        let program = HintProgram {
+            num_registers: 1,
+            inputs: vec![Register(0)],
+            outputs: vec![],
+            instructions: vec![
+                HintOp::JumpIfNot { condition: Register(0), target: 2 },
+                HintOp::Trap,
+            ],
+        };
+        assert!(HintSolver::execute(&program, &[FieldElement::zero()]).is_ok());
+        assert_eq!(
+            HintSolver::execute(&program, &[FieldElement::one()]),
+            Err(OpcodeNotSolvable::UnsatisfiedConstraint)
+        );
+    }
+
+    #[test]
+    fn out_of_bounds_addresses_and_empty_integers() {
+        let index = FieldElement::from(u32::MAX as u128);
+        assert_eq!(address(&Register(0), index), Ok(Register(u32::MAX)));
+        assert!(matches!(address(&Register(1), index), Err(OpcodeNotSolvable::HintFailure(_))));
+        let index = FieldElement::from(u32::MAX as u128 + 1);
+        assert!(matches!(address(&Register(0), index), Err(OpcodeNotSolvable::HintFailure(_))));
+
+        let numeric = HintNumeric::Signed(0);
+        let zero = FieldElement::zero();
+        assert_eq!(evaluate_binary(HintBinaryOp::Lt, numeric, zero, zero), Ok(zero));
+        assert_eq!(evaluate_binary(HintBinaryOp::Shr, numeric, zero, zero), Ok(zero));
+    }
+}
@@ -0,0 +1,108 @@
+use acir::native_types::{Expression, Witness};
+use noir_field::FieldElement;
+use std::collections::BTreeMap;
+
+use crate::{GateResolution, OpcodeNotSolvable, PartialWitnessGenerator};
+
+/// Formats the values of a log directive and passes the message to the logger of the host
+pub struct LogSolver;
+
+impl LogSolver {
+    /// Returns Skip if the predicate or some inputs are not yet known
+    pub fn solve<P: PartialWitnessGenerator + ?Sized>(
+        initial_witness: &BTreeMap<Witness, FieldElement>,
+        format: &str,
+        inputs: &[Expression],
+        predicate: &Expression,
+        logger: &mut dyn FnMut(&str),
+    ) -> Result<GateResolution, OpcodeNotSolvable> {
+        match P::get_value(predicate, initial_witness) {
+            None => return Ok(GateResolution::Skip),
+            Some(predicate) if predicate.is_zero() => return Ok(GateResolution::Resolved),
+            Some(_) => (),
+        }
+        let mut values = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            match P::get_value(input, initial_witness) {
+                Some(value) => values.push(value),
+                None => return Ok(GateResolution::Skip),
+            }
+        }
+        let message = format_message(format, &values).map_err(OpcodeNotSolvable::MalformedGate)?;
+        logger(&message);
+        Ok(GateResolution::Resolved)
+    }
+}
+
+/// Replaces the placeholders {i} of the format string by the i-th value
+pub fn format_message(format: &str, values: &[FieldElement]) -> Result<String, String> {
+    let mut message = String::new();
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next() {
+                        Some('{') if placeholder.is_empty() => {
+                            message.push('{');
+                            break;
+                        }
+                        Some('}') => {
+                            let value = placeholder
+                                .parse::<usize>()
+                                .ok()
+                                .and_then(|i| values.get(i))
+                                .ok_or_else(|| {
+                                    format!("invalid placeholder {{{}}} in log format", placeholder)
+                                })?;
+                            message.push_str(&format_value(*value));
+                            break;
+                        }
+                        Some(c) => placeholder.push(c),
+                        None => {
+                            return Err(format!("unclosed placeholder in log format {:?}", format))
+                        }
+                    }
+                }
+            }
+            '}' => {
+                if chars.next() != Some('}') {
+                    return Err(format!("unmatched }} in log format {:?}", format));
+                }
+                message.push('}');
+            }
+            c => message.push(c),
+        }
+    }
+    Ok(message)
+}
+
+// Small values are printed in decimal, and the other ones in hexadecimal like in the input files
+fn format_value(value: FieldElement) -> String {
+    match value.try_into_u128() {
+        Some(value) => value.to_string(),
+        None => format!("0x{}", value.to_hex()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn placeholders_and_escapes() {
+        let values = [FieldElement::from(3_u128), FieldElement::from(42_u128)];
+        let message = format_message("{{x}}: [{0}, {1}], {1}", &values).unwrap();
+        assert_eq!(message, "{x}: [3, 42], 42");
+        assert!(format_message("{2}", &values).is_err());
+        assert!(format_message("{0", &values).is_err());
+        assert!(format_message("}", &values).is_err());
+    }
+
+    #[test]
+    fn large_values_are_hexadecimal() {
+        let message = format_message("{0}", &[-FieldElement::one()]).unwrap();
+        assert!(message.starts_with("0x") && message.len() > 34);
+    }
+}
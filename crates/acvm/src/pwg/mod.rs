@@ -1,21 +1,43 @@
-use acir::{circuit::gate::GadgetInput, native_types::Witness};
+use acir::{
+    circuit::gate::{GadgetCall, GadgetInput},
+    native_types::Witness,
+};
 use noir_field::FieldElement;
 use std::collections::BTreeMap;
 
+use crate::OpcodeNotSolvable;
+
 // Re-usable methods that backends can use to implement their PWG
 // XXX: This can possible be refactored to be default trait methods
 
 pub mod arithmetic;
 pub mod hash;
+pub mod hint;
+pub mod log;
 pub mod logic;
 pub mod signature;
+pub mod sorting;
 
 pub fn input_to_value<'a>(
     witness_map: &'a BTreeMap<Witness, FieldElement>,
     input: &GadgetInput,
-) -> &'a FieldElement {
-    match witness_map.get(&input.witness) {
-        None => panic!("Cannot find witness assignment for {:?}", input),
-        Some(assignment) => assignment,
+) -> Result<&'a FieldElement, OpcodeNotSolvable> {
+    witness_map.get(&input.witness).ok_or(OpcodeNotSolvable::MissingWitness(input.witness))
+}
+
+/// Checks that a gadget call has the number of outputs that its solver assigns
+pub fn check_output_count(
+    gadget_call: &GadgetCall,
+    expected: usize,
+) -> Result<(), OpcodeNotSolvable> {
+    if gadget_call.outputs.len() == expected {
+        Ok(())
+    } else {
+        Err(OpcodeNotSolvable::MalformedGate(format!(
+            "{} returns {} outputs but the call has {}",
+            gadget_call.name,
+            expected,
+            gadget_call.outputs.len()
+        )))
     }
 }
@@ -1,4 +1,5 @@
-use crate::pwg::input_to_value;
+use crate::pwg::{check_output_count, input_to_value};
+use crate::OpcodeNotSolvable;
 use acir::{circuit::gate::GadgetCall, native_types::Witness};
 use noir_field::FieldElement;
 use std::collections::BTreeMap;
@@ -6,39 +7,34 @@ use std::collections::BTreeMap;
 pub fn secp256k1_prehashed(
     initial_witness: &mut BTreeMap<Witness, FieldElement>,
     gadget_call: &GadgetCall,
-) {
+) -> Result<(), OpcodeNotSolvable> {
+    check_output_count(gadget_call, 1)?;
     let mut inputs_iter = gadget_call.inputs.iter();
 
     let mut pub_key_x = [0u8; 32];
     for (i, pkx) in pub_key_x.iter_mut().enumerate() {
-        let _x_i = inputs_iter
-            .next()
-            .unwrap_or_else(|| panic!("pub_key_x should be 32 bytes long, found only {} bytes", i));
-        let x_i = input_to_value(initial_witness, _x_i);
+        let _x_i = inputs_iter.next().ok_or_else(|| too_short("pub_key_x", 32, i))?;
+        let x_i = input_to_value(initial_witness, _x_i)?;
         *pkx = *x_i.to_bytes().last().unwrap()
     }
 
     let mut pub_key_y = [0u8; 32];
     for (i, pky) in pub_key_y.iter_mut().enumerate() {
-        let _y_i = inputs_iter
-            .next()
-            .unwrap_or_else(|| panic!("pub_key_y should be 32 bytes long, found only {} bytes", i));
-        let y_i = input_to_value(initial_witness, _y_i);
+        let _y_i = inputs_iter.next().ok_or_else(|| too_short("pub_key_y", 32, i))?;
+        let y_i = input_to_value(initial_witness, _y_i)?;
         *pky = *y_i.to_bytes().last().unwrap()
     }
 
     let mut signature = [0u8; 64];
     for (i, sig) in signature.iter_mut().enumerate() {
-        let _sig_i = inputs_iter
-            .next()
-            .unwrap_or_else(|| panic!("signature should be 64 bytes long, found only {} bytes", i));
-        let sig_i = input_to_value(initial_witness, _sig_i);
+        let _sig_i = inputs_iter.next().ok_or_else(|| too_short("signature", 64, i))?;
+        let sig_i = input_to_value(initial_witness, _sig_i)?;
         *sig = *sig_i.to_bytes().last().unwrap()
     }
 
     let mut hashed_message = Vec::new();
     for msg in inputs_iter {
-        let msg_i_field = input_to_value(initial_witness, msg);
+        let msg_i_field = input_to_value(initial_witness, msg)?;
         let msg_i = *msg_i_field.to_bytes().last().unwrap();
         hashed_message.push(msg_i);
     }
@@ -56,6 +52,14 @@ pub fn secp256k1_prehashed(
     };
 
     initial_witness.insert(gadget_call.outputs[0], result);
+    Ok(())
+}
+
+fn too_short(name: &str, expected: usize, found: usize) -> OpcodeNotSolvable {
+    OpcodeNotSolvable::MalformedGate(format!(
+        "{} should be {} bytes long, found only {} bytes",
+        name, expected, found
+    ))
 }
 
 mod ecdsa_secp256k1 {
@@ -0,0 +1,211 @@
+use acir::native_types::{Expression, Witness};
+use noir_field::FieldElement;
+use num_bigint::BigUint;
+use std::collections::BTreeMap;
+
+use crate::{GateResolution, OpcodeNotSolvable, PartialWitnessGenerator};
+
+/// Computes the control bits of a Benes network which sorts tuples of values
+pub struct SortingSolver;
+
+impl SortingSolver {
+    /// Returns Skip if some inputs are not yet known
+    pub fn solve<P: PartialWitnessGenerator + ?Sized>(
+        initial_witness: &mut BTreeMap<Witness, FieldElement>,
+        inputs: &[Vec<Expression>],
+        bits: &[Witness],
+        sort_by: &[u32],
+    ) -> Result<GateResolution, OpcodeNotSolvable> {
+        if !inputs.len().is_power_of_two() {
+            return Err(OpcodeNotSolvable::MalformedGate(format!(
+                "cannot sort {} tuples, the number of tuples must be a power of two",
+                inputs.len()
+            )));
+        }
+        let mut keys = Vec::with_capacity(inputs.len());
+        for tuple in inputs {
+            let mut key = Vec::with_capacity(sort_by.len());
+            for i in sort_by {
+                let element = tuple.get(*i as usize).ok_or_else(|| {
+                    OpcodeNotSolvable::MalformedGate(format!(
+                        "cannot sort by the element {} of a tuple of {} elements",
+                        i,
+                        tuple.len()
+                    ))
+                })?;
+                match P::get_value(element, initial_witness) {
+                    Some(value) => key.push(BigUint::from_bytes_be(&value.to_bytes())),
+                    None => return Ok(GateResolution::Skip),
+                }
+            }
+            keys.push(key);
+        }
+
+        // src[j] is the input which is routed to the output j
+        let mut src: Vec<usize> = (0..inputs.len()).collect();
+        src.sort_by(|a, b| keys[*a].cmp(&keys[*b]));
+
+        let switches = route(&src);
+        if switches.len() != bits.len() {
+            return Err(OpcodeNotSolvable::MalformedGate(format!(
+                "sorting {} tuples requires {} bits but {} were provided",
+                inputs.len(),
+                switches.len(),
+                bits.len()
+            )));
+        }
+        for (witness, swap) in bits.iter().zip(switches) {
+            let value = if swap { FieldElement::one() } else { FieldElement::zero() };
+            initial_witness.insert(*witness, value);
+        }
+        Ok(GateResolution::Resolved)
+    }
+}
+
+/// Number of switches of a Benes network over n inputs, n being a power of two
+pub fn network_size(n: usize) -> usize {
+    if n <= 1 {
+        0
+    } else if n == 2 {
+        1
+    } else {
+        n + 2 * network_size(n / 2)
+    }
+}
+
+/// Routes the permutation src, where src[j] is the input going to the output j, through a Benes network.
+/// Returns whether each switch swaps its inputs, listing first the input layer, then the upper and lower
+/// sub-networks and finally the output layer.
+/// The switch k of the input layer sends inputs 2k and 2k+1 into the sub-networks, the upper one receiving
+/// the input 2k unless the switch is set. Symmetrically the switch k of the output layer takes the outputs k of
+/// the sub-networks into the outputs 2k and 2k+1.
+pub fn route(src: &[usize]) -> Vec<bool> {
+    let n = src.len();
+    assert!(n.is_power_of_two(), "a Benes network requires a power of two inputs");
+    if n <= 1 {
+        return Vec::new();
+    }
+    if n == 2 {
+        return vec![src[0] == 1];
+    }
+
+    let mut dest = vec![0; n];
+    for (j, i) in src.iter().enumerate() {
+        dest[*i] = j;
+    }
+
+    // Looping algorithm: the two inputs of a switch go to different sub-networks,
+    // and so do the two outputs of a switch.
+    let mut lower = vec![None; n];
+    for start in (0..n).step_by(2) {
+        if lower[start].is_some() {
+            continue;
+        }
+        let mut i = start;
+        loop {
+            lower[i] = Some(false);
+            lower[i ^ 1] = Some(true);
+            let next = src[dest[i ^ 1] ^ 1];
+            if lower[next].is_some() {
+                break;
+            }
+            i = next;
+        }
+    }
+    let lower: Vec<bool> = lower.into_iter().map(Option::unwrap).collect();
+
+    let half = n / 2;
+    let mut upper_src = vec![0; half];
+    let mut lower_src = vec![0; half];
+    let mut output_bits = Vec::with_capacity(half);
+    for q in 0..half {
+        let (a, b) = (src[2 * q], src[2 * q + 1]);
+        let (up, low) = if lower[a] { (b, a) } else { (a, b) };
+        upper_src[q] = up / 2;
+        lower_src[q] = low / 2;
+        output_bits.push(lower[a]);
+    }
+
+    let mut result: Vec<bool> = (0..half).map(|p| lower[2 * p]).collect();
+    result.extend(route(&upper_src));
+    result.extend(route(&lower_src));
+    result.extend(output_bits);
+    result
+}
+
+/// Sends the inputs through the Benes network whose switches are set by bits, following the layout of route()
+/// The number of inputs must be a power of two, and bits must hold the network_size() switches of the network
+pub fn apply<T: Clone>(bits: &[bool], inputs: &[T]) -> Vec<T> {
+    let n = inputs.len();
+    if n <= 1 {
+        return inputs.to_vec();
+    }
+    let switch = |swap: bool, a: &T, b: &T| {
+        if swap {
+            (b.clone(), a.clone())
+        } else {
+            (a.clone(), b.clone())
+        }
+    };
+    if n == 2 {
+        let (x, y) = switch(bits[0], &inputs[0], &inputs[1]);
+        return vec![x, y];
+    }
+    let half = n / 2;
+    let sub = network_size(half);
+    let (mut upper, mut lower) = (Vec::new(), Vec::new());
+    for p in 0..half {
+        let (x, y) = switch(bits[p], &inputs[2 * p], &inputs[2 * p + 1]);
+        upper.push(x);
+        lower.push(y);
+    }
+    let upper = apply(&bits[half..half + sub], &upper);
+    let lower = apply(&bits[half + sub..half + 2 * sub], &lower);
+    let mut outputs = Vec::new();
+    for q in 0..half {
+        let (x, y) = switch(bits[half + 2 * sub + q], &upper[q], &lower[q]);
+        outputs.push(x);
+        outputs.push(y);
+    }
+    outputs
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn routes_all_permutations() {
+        fn permutations(n: usize) -> Vec<Vec<usize>> {
+            if n == 0 {
+                return vec![vec![]];
+            }
+            let mut result = Vec::new();
+            for p in permutations(n - 1) {
+                for i in 0..n {
+                    let mut q = p.clone();
+                    q.insert(i, n - 1);
+                    result.push(q);
+                }
+            }
+            result
+        }
+
+        for n in [1, 2, 4, 8] {
+            let inputs: Vec<usize> = (0..n).collect();
+            for src in permutations(n) {
+                let bits = route(&src);
+                assert_eq!(bits.len(), network_size(n));
+                assert_eq!(apply(&bits, &inputs), src);
+            }
+        }
+    }
+
+    #[test]
+    fn routes_large_permutation() {
+        let n = 256;
+        let src: Vec<usize> = (0..n).map(|i| (i * 113 + 7) % n).collect();
+        let inputs: Vec<usize> = (0..n).collect();
+        assert_eq!(apply(&route(&src), &inputs), src);
+    }
+}
@@ -0,0 +1,407 @@
+use std::collections::BTreeMap;
+
+use acir::{
+    circuit::{
+        gate::{Directive, GadgetCall},
+        Circuit, Gate,
+    },
+    native_types::{Expression, Witness},
+    OPCODE,
+};
+
+use crate::pwg::{self, sorting};
+use crate::{FieldElement, GateResolution, OpcodeNotSolvable, PartialWitnessGenerator};
+
+/// A gate of the circuit which is not satisfied by the witness
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Unsatisfied {
+    pub gate_index: usize,
+    pub gate_kind: String,
+    pub reason: UnsatisfiedReason,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UnsatisfiedReason {
+    MissingWitness(Witness),
+    // The arithmetic gate evaluates to this value instead of zero
+    NonZero(FieldElement),
+    TooManyBits { witness: Witness, num_bits: u32 },
+    // The witness does not have the value which the gate defines from its inputs
+    WrongValue { witness: Witness, expected: FieldElement, found: FieldElement },
+    // The bits of a permutation sort do not route its inputs into sorted order
+    Unsorted,
+    // No value can be defined from the inputs of the gate, for instance because of a division by zero
+    Unsolvable(OpcodeNotSolvable),
+}
+
+impl std::fmt::Display for UnsatisfiedReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnsatisfiedReason::MissingWitness(witness) => {
+                write!(f, "the witness x{} has no value", witness.0)
+            }
+            UnsatisfiedReason::NonZero(value) => write!(f, "evaluates to {} instead of 0", value),
+            UnsatisfiedReason::TooManyBits { witness, num_bits } => {
+                write!(f, "x{} does not fit in {} bits", witness.0, num_bits)
+            }
+            UnsatisfiedReason::WrongValue { witness, expected, found } => {
+                write!(f, "x{} is {} but should be {}", witness.0, found, expected)
+            }
+            UnsatisfiedReason::Unsorted => write!(f, "the inputs are not routed into sorted order"),
+            UnsatisfiedReason::Unsolvable(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl std::fmt::Display for Unsatisfied {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the {} gate {} is not satisfied: {}",
+            self.gate_kind, self.gate_index, self.reason
+        )
+    }
+}
+
+impl std::error::Error for Unsatisfied {}
+
+/// Returns true if acvm can compute the outputs of the black box function, so that
+/// `check_satisfiability` checks its gates. The other black box functions are left to the backend
+pub fn has_reference_implementation(opcode: OPCODE) -> bool {
+    matches!(opcode, OPCODE::SHA256 | OPCODE::Blake2s | OPCODE::Keccak256 | OPCODE::EcdsaSecp256k1)
+}
+
+/// Checks that the witness satisfies every gate of the circuit, without a proof system.
+///
+/// The arithmetic, range and logic gates are evaluated. The outputs of the directives and of the
+/// black box functions are computed again from their inputs and compared with the witness, except for
+/// permutation sorts whose bits are checked to sort their inputs.
+/// The gates calling a black box function without a reference implementation are not checked.
+pub fn check_satisfiability(
+    circuit: &Circuit,
+    witness: &BTreeMap<Witness, FieldElement>,
+) -> Result<(), Vec<Unsatisfied>> {
+    let errors: Vec<_> = circuit
+        .gates
+        .iter()
+        .enumerate()
+        .filter_map(|(gate_index, gate)| {
+            check_gate(gate, witness).err().map(|reason| Unsatisfied {
+                gate_index,
+                gate_kind: gate.name().to_string(),
+                reason,
+            })
+        })
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn check_gate(
+    gate: &Gate,
+    witness: &BTreeMap<Witness, FieldElement>,
+) -> Result<(), UnsatisfiedReason> {
+    match gate {
+        Gate::Arithmetic(expression) => {
+            let value = evaluate(expression, witness)?;
+            if value.is_zero() {
+                Ok(())
+            } else {
+                Err(UnsatisfiedReason::NonZero(value))
+            }
+        }
+        Gate::Range(w, num_bits) => check_bits(witness, *w, *num_bits),
+        Gate::And(and_gate) => {
+            check_bits(witness, and_gate.a, and_gate.num_bits)?;
+            check_bits(witness, and_gate.b, and_gate.num_bits)?;
+            recompute(gate, &[and_gate.result], witness)
+        }
+        Gate::Xor(xor_gate) => {
+            check_bits(witness, xor_gate.a, xor_gate.num_bits)?;
+            check_bits(witness, xor_gate.b, xor_gate.num_bits)?;
+            recompute(gate, &[xor_gate.result], witness)
+        }
+        Gate::GadgetCall(call) if has_reference_implementation(call.name) => {
+            recompute(gate, &call.outputs, witness)
+        }
+        Gate::GadgetCall(_) => Ok(()),
+        Gate::Directive(directive) => match directive {
+            Directive::Invert { result, .. } => recompute(gate, &[*result], witness),
+            Directive::Quotient { q, r, .. } => recompute(gate, &[*q, *r], witness),
+            Directive::Truncate { b, c, .. } => recompute(gate, &[*b, *c], witness),
+            Directive::Oddrange { b, r, .. } => recompute(gate, &[*b, *r], witness),
+            Directive::Split { b, .. } => recompute(gate, b, witness),
+            Directive::Hint { outputs, .. } => recompute(gate, outputs, witness),
+            Directive::PermutationSort { inputs, bits, sort_by } => {
+                check_sort(inputs, bits, sort_by, witness)
+            }
+            Directive::Log { .. } => Ok(()),
+        },
+    }
+}
+
+fn value_of(
+    witness: &BTreeMap<Witness, FieldElement>,
+    w: Witness,
+) -> Result<FieldElement, UnsatisfiedReason> {
+    witness.get(&w).copied().ok_or(UnsatisfiedReason::MissingWitness(w))
+}
+
+fn evaluate(
+    expression: &Expression,
+    witness: &BTreeMap<Witness, FieldElement>,
+) -> Result<FieldElement, UnsatisfiedReason> {
+    let mut result = expression.q_c;
+    for (q_m, lhs, rhs) in &expression.mul_terms {
+        result += *q_m * value_of(witness, *lhs)? * value_of(witness, *rhs)?;
+    }
+    for (q_l, w) in &expression.linear_combinations {
+        result += *q_l * value_of(witness, *w)?;
+    }
+    Ok(result)
+}
+
+fn check_bits(
+    witness: &BTreeMap<Witness, FieldElement>,
+    w: Witness,
+    num_bits: u32,
+) -> Result<(), UnsatisfiedReason> {
+    if value_of(witness, w)?.num_bits() > num_bits {
+        Err(UnsatisfiedReason::TooManyBits { witness: w, num_bits })
+    } else {
+        Ok(())
+    }
+}
+
+// Solves the gate again from the values of its inputs only, and compares the outputs it assigns with the witness
+fn recompute(
+    gate: &Gate,
+    outputs: &[Witness],
+    witness: &BTreeMap<Witness, FieldElement>,
+) -> Result<(), UnsatisfiedReason> {
+    let mut inputs = BTreeMap::new();
+    for w in gate.witnesses() {
+        if !outputs.contains(&w) {
+            inputs.insert(w, value_of(witness, w)?);
+        }
+    }
+
+    match ReferenceSolver::solve_gate(&mut inputs, gate, &mut |_| ()) {
+        Ok(GateResolution::Resolved) => (),
+        Ok(_) => {
+            return Err(UnsatisfiedReason::Unsolvable(OpcodeNotSolvable::MalformedGate(
+                "the gate reads its own outputs".to_string(),
+            )))
+        }
+        Err(reason) => return Err(UnsatisfiedReason::Unsolvable(reason)),
+    }
+
+    for output in outputs {
+        // A split into more witnesses than its bit size does not assign the extra ones
+        if let Some(expected) = inputs.get(output) {
+            let found = value_of(witness, *output)?;
+            if found != *expected {
+                return Err(UnsatisfiedReason::WrongValue {
+                    witness: *output,
+                    expected: *expected,
+                    found,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+// The switches of the Benes network may differ from the ones chosen by the solver when some keys are equal,
+// so the bits are checked by routing the keys through the network
+fn check_sort(
+    inputs: &[Vec<Expression>],
+    bits: &[Witness],
+    sort_by: &[u32],
+    witness: &BTreeMap<Witness, FieldElement>,
+) -> Result<(), UnsatisfiedReason> {
+    if !inputs.len().is_power_of_two() || bits.len() != sorting::network_size(inputs.len()) {
+        return Err(UnsatisfiedReason::Unsolvable(OpcodeNotSolvable::MalformedGate(format!(
+            "{} tuples cannot be sorted with {} bits",
+            inputs.len(),
+            bits.len()
+        ))));
+    }
+
+    let mut switches = Vec::with_capacity(bits.len());
+    for bit in bits {
+        check_bits(witness, *bit, 1)?;
+        switches.push(value_of(witness, *bit)?.is_one());
+    }
+
+    let mut keys = Vec::with_capacity(inputs.len());
+    for tuple in inputs {
+        let mut key = Vec::with_capacity(sort_by.len());
+        for i in sort_by {
+            let element = tuple.get(*i as usize).ok_or_else(|| {
+                UnsatisfiedReason::Unsolvable(OpcodeNotSolvable::MalformedGate(format!(
+                    "cannot sort by the element {} of a tuple of {} elements",
+                    i,
+                    tuple.len()
+                )))
+            })?;
+            key.push(num_bigint::BigUint::from_bytes_be(&evaluate(element, witness)?.to_bytes()));
+        }
+        keys.push(key);
+    }
+
+    let sorted = sorting::apply(&switches, &keys);
+    if sorted.windows(2).all(|pair| pair[0] <= pair[1]) {
+        Ok(())
+    } else {
+        Err(UnsatisfiedReason::Unsorted)
+    }
+}
+
+// Solves the gates with the reference implementations of the black box functions
+struct ReferenceSolver;
+
+impl PartialWitnessGenerator for ReferenceSolver {
+    fn solve_gadget_call(
+        initial_witness: &mut BTreeMap<Witness, FieldElement>,
+        gc: &GadgetCall,
+    ) -> Result<(), OpcodeNotSolvable> {
+        match gc.name {
+            OPCODE::SHA256 => pwg::hash::sha256(initial_witness, gc),
+            OPCODE::Blake2s => pwg::hash::blake2s(initial_witness, gc),
+            OPCODE::Keccak256 => pwg::hash::keccak256(initial_witness, gc),
+            OPCODE::EcdsaSecp256k1 => {
+                pwg::signature::ecdsa::secp256k1_prehashed(initial_witness, gc)
+            }
+            name => Err(OpcodeNotSolvable::UnsupportedOpcode(name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use acir::{
+        circuit::{
+            gate::{Directive, GadgetCall, GadgetInput},
+            Circuit, Gate, PublicInputs,
+        },
+        native_types::{Expression, Witness},
+        OPCODE,
+    };
+
+    use super::{check_satisfiability, ReferenceSolver, Unsatisfied, UnsatisfiedReason};
+    use crate::{FieldElement, GateResolution, PartialWitnessGenerator};
+
+    #[test]
+    fn detects_tampered_witnesses() {
+        let mut product = Expression::default();
+        product.mul_terms.push((FieldElement::one(), Witness(1), Witness(2)));
+        let circuit = Circuit {
+            current_witness_index: 37,
+            gates: vec![
+                Gate::Arithmetic(&product - &Witness(3)),
+                Gate::Range(Witness(3), 8),
+                Gate::Directive(Directive::Quotient {
+                    a: Expression::from(&Witness(3)),
+                    b: Expression::from(&Witness(2)),
+                    q: Witness(4),
+                    r: Witness(5),
+                }),
+                Gate::GadgetCall(GadgetCall {
+                    name: OPCODE::SHA256,
+                    inputs: vec![GadgetInput { witness: Witness(1), num_bits: 8 }],
+                    outputs: (6..38).map(Witness).collect(),
+                }),
+            ],
+            public_inputs: PublicInputs(vec![]),
+        };
+        let mut witness = BTreeMap::from([
+            (Witness(1), FieldElement::from(3_i128)),
+            (Witness(2), FieldElement::from(5_i128)),
+        ]);
+        assert_eq!(
+            ReferenceSolver.solve(&mut witness, circuit.gates.clone()),
+            GateResolution::Resolved
+        );
+        assert_eq!(check_satisfiability(&circuit, &witness), Ok(()));
+
+        let digest = witness[&Witness(6)];
+        witness.insert(Witness(4), FieldElement::from(4_i128));
+        witness.insert(Witness(6), digest + FieldElement::one());
+        witness.remove(&Witness(5));
+        assert_eq!(
+            check_satisfiability(&circuit, &witness),
+            Err(vec![
+                Unsatisfied {
+                    gate_index: 2,
+                    gate_kind: "quotient".to_string(),
+                    reason: UnsatisfiedReason::WrongValue {
+                        witness: Witness(4),
+                        expected: FieldElement::from(3_i128),
+                        found: FieldElement::from(4_i128)
+                    }
+                },
+                Unsatisfied {
+                    gate_index: 3,
+                    gate_kind: "sha256".to_string(),
+                    reason: UnsatisfiedReason::WrongValue {
+                        witness: Witness(6),
+                        expected: digest,
+                        found: digest + FieldElement::one()
+                    }
+                },
+            ])
+        );
+
+        witness.insert(Witness(3), FieldElement::from(300_i128));
+        witness.insert(Witness(4), FieldElement::from(60_i128));
+        let errors = check_satisfiability(&circuit, &witness).unwrap_err();
+        assert_eq!(errors[0].reason, UnsatisfiedReason::NonZero(-FieldElement::from(285_i128)));
+        assert_eq!(
+            errors[1].reason,
+            UnsatisfiedReason::TooManyBits { witness: Witness(3), num_bits: 8 }
+        );
+        assert_eq!(errors[2].reason, UnsatisfiedReason::MissingWitness(Witness(5)));
+    }
+
+    #[test]
+    fn checks_sorting_bits() {
+        let inputs: Vec<_> = (1..=4).map(|i| vec![Expression::from(&Witness(i))]).collect();
+        let circuit = Circuit {
+            current_witness_index: 10,
+            gates: vec![Gate::Directive(Directive::PermutationSort {
+                inputs,
+                bits: (5..=10).map(Witness).collect(),
+                sort_by: vec![0],
+            })],
+            public_inputs: PublicInputs(vec![]),
+        };
+        let mut witness: BTreeMap<_, _> = [7, 7, 2, 9]
+            .iter()
+            .zip(1..)
+            .map(|(v, i)| (Witness(i), FieldElement::from(*v as i128)))
+            .collect();
+        assert_eq!(
+            ReferenceSolver.solve(&mut witness, circuit.gates.clone()),
+            GateResolution::Resolved
+        );
+        assert_eq!(check_satisfiability(&circuit, &witness), Ok(()));
+
+        // Swapping the two equal keys still sorts the inputs
+        let first = witness[&Witness(5)];
+        witness.insert(Witness(5), FieldElement::one() - first);
+        assert_eq!(check_satisfiability(&circuit, &witness), Ok(()));
+
+        witness.insert(Witness(10), FieldElement::from(2_i128));
+        let errors = check_satisfiability(&circuit, &witness).unwrap_err();
+        assert_eq!(
+            errors[0].reason,
+            UnsatisfiedReason::TooManyBits { witness: Witness(10), num_bits: 1 }
+        );
+    }
+}
@@ -0,0 +1,19 @@
+//! A backend for the tests and benchmarks of the ACVM, which does not depend on a proof system
+
+use std::collections::BTreeMap;
+
+use acir::{circuit::gate::GadgetCall, native_types::Witness, FieldElement};
+
+use crate::{OpcodeNotSolvable, PartialWitnessGenerator};
+
+/// A backend which only solves arithmetic gates and directives
+pub struct MinimalBackend;
+
+impl PartialWitnessGenerator for MinimalBackend {
+    fn solve_gadget_call(
+        _initial_witness: &mut BTreeMap<Witness, FieldElement>,
+        gc: &GadgetCall,
+    ) -> Result<(), OpcodeNotSolvable> {
+        Err(OpcodeNotSolvable::UnsupportedOpcode(gc.name))
+    }
+}
@@ -1,5 +1,5 @@
 use codespan_reporting::files::{SimpleFile, SimpleFiles};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::FileManager;
 
@@ -50,6 +50,10 @@ impl<'input> File<'input> {
     pub fn get_source(self) -> &'input str {
         self.0.source()
     }
+
+    pub fn path(self) -> &'input Path {
+        &self.0.name().0
+    }
 }
 
 impl FileMap {
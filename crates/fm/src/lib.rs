@@ -6,6 +6,7 @@ pub use file_map::{File, FileId, FileMap};
 pub mod util;
 use std::{
     collections::HashMap,
+    ffi::OsStr,
     path::{Path, PathBuf},
 };
 pub use util::*;
@@ -16,7 +17,7 @@ pub const FILE_EXTENSION: &str = "nr";
 /// An enum to differentiate between the root file
 /// which the compiler starts at, and the others.
 /// This is so that submodules of the root, can live alongside the
-/// root file as files.
+/// root file as files. The same applies to `foo/mod.nr` files.
 pub enum FileType {
     Root,
     Normal,
@@ -25,6 +26,17 @@ pub enum FileType {
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 struct VirtualPath(PathBuf);
 
+/// The reasons a module declaration can fail to resolve to a file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ResolvePathError {
+    /// None of the candidate files could be read
+    NotFound { candidates: Vec<PathBuf> },
+    /// Both `foo.nr` and `foo/mod.nr` exist
+    Ambiguous { file: PathBuf, mod_file: PathBuf },
+    /// A path attribute points to a file which is not a noir file
+    InvalidExtension { path: PathBuf },
+}
+
 #[derive(Debug)]
 pub struct FileManager {
     file_map: file_map::FileMap,
@@ -53,11 +65,19 @@ impl FileManager {
 
         let source = file_reader::read_file_to_string(path_to_file).ok()?;
 
+        Some(self.add_file_with_source(path_to_file, source, file_type))
+    }
+
+    fn add_file_with_source(
+        &mut self,
+        path_to_file: &Path,
+        source: String,
+        file_type: FileType,
+    ) -> FileId {
         let file_id = self.file_map.add_file(path_to_file.to_path_buf().into(), source);
         let path_to_file = virtualise_path(path_to_file, file_type);
         self.register_path(file_id, path_to_file);
-
-        Some(file_id)
+        file_id
     }
 
     fn register_path(&mut self, file_id: FileId, path: VirtualPath) {
@@ -80,20 +100,51 @@ impl FileManager {
         self.id_to_path.get(&file_id).unwrap().0.as_path()
     }
 
-    pub fn resolve_path(&mut self, anchor: FileId, mod_name: &str) -> Result<FileId, String> {
-        let mut candidate_files = Vec::new();
-
+    /// Finds the file for the module `mod_name` declared in `anchor`.
+    /// The module either lives in `foo.nr`, with its own submodules in `foo/`,
+    /// or in `foo/mod.nr`, alongside its submodules.
+    pub fn resolve_path(
+        &mut self,
+        anchor: FileId,
+        mod_name: &str,
+    ) -> Result<FileId, ResolvePathError> {
         let dir = self.path(anchor).to_path_buf();
 
-        candidate_files.push(dir.join(&format!("{}.{}", mod_name, FILE_EXTENSION)));
+        let file = dir.join(format!("{}.{}", mod_name, FILE_EXTENSION));
+        let mod_file = dir.join(mod_name).join(format!("mod.{}", FILE_EXTENSION));
+
+        let file_source = file_reader::read_file_to_string(&file).ok();
+        let mod_file_source = file_reader::read_file_to_string(&mod_file).ok();
 
-        for candidate in candidate_files.iter() {
-            if let Some(file_id) = self.add_file(candidate, FileType::Normal) {
-                return Ok(file_id);
+        match (file_source, mod_file_source) {
+            (Some(source), None) => Ok(self.add_file_with_source(&file, source, FileType::Normal)),
+            (None, Some(source)) => {
+                Ok(self.add_file_with_source(&mod_file, source, FileType::Root))
             }
+            (Some(_), Some(_)) => Err(ResolvePathError::Ambiguous { file, mod_file }),
+            (None, None) => Err(ResolvePathError::NotFound { candidates: vec![file, mod_file] }),
+        }
+    }
+
+    /// Loads the file given by a `#[path = "..."]` attribute on a module declared in `anchor`.
+    /// The path is relative to the directory containing `anchor`, and the submodules
+    /// of the loaded file `bar.nr` are looked up in `bar/` like for any other file module.
+    pub fn resolve_path_attribute(
+        &mut self,
+        anchor: FileId,
+        path: &str,
+    ) -> Result<FileId, ResolvePathError> {
+        let anchor_path = self.file_map.get_file(anchor).unwrap().path();
+        let path = anchor_path.parent().unwrap_or_else(|| Path::new("")).join(path);
+
+        if path.extension() != Some(OsStr::new(FILE_EXTENSION)) {
+            return Err(ResolvePathError::InvalidExtension { path });
         }
 
-        Err(candidate_files.remove(0).as_os_str().to_str().unwrap().to_owned())
+        match file_reader::read_file_to_string(&path) {
+            Ok(source) => Ok(self.add_file_with_source(&path, source, FileType::Normal)),
+            Err(_) => Err(ResolvePathError::NotFound { candidates: vec![path] }),
+        }
     }
 }
 
@@ -185,4 +236,78 @@ mod tests {
         // Now check for files in it's subdirectory
         fm.resolve_path(sub_dir_file_id, "foo").unwrap();
     }
+    #[test]
+    fn path_resolve_mod_file() {
+        let mut fm = FileManager::new();
+
+        // We have:
+        // - dir/lib.nr
+        // - dir/foo/mod.nr
+        // - dir/foo/bar.nr
+        // - dir/foo/bar/baz/mod.nr
+        let dir = tempdir().unwrap();
+        let file_path = dummy_file_path(&dir, "lib.nr");
+        std::fs::create_dir_all(dir.path().join("foo/bar/baz")).unwrap();
+        dummy_file_path(&dir, "foo/mod.nr");
+        dummy_file_path(&dir, "foo/bar.nr");
+        dummy_file_path(&dir, "foo/bar/baz/mod.nr");
+
+        let file_id = fm.add_file(&file_path, FileType::Root).unwrap();
+        let foo_file_id = fm.resolve_path(file_id, "foo").unwrap();
+        let bar_file_id = fm.resolve_path(foo_file_id, "bar").unwrap();
+        fm.resolve_path(bar_file_id, "baz").unwrap();
+    }
+
+    #[test]
+    fn path_resolve_ambiguous_module() {
+        let mut fm = FileManager::new();
+
+        let dir = tempdir().unwrap();
+        let file_path = dummy_file_path(&dir, "lib.nr");
+        std::fs::create_dir_all(dir.path().join("foo")).unwrap();
+        let foo_file = dummy_file_path(&dir, "foo.nr");
+        let foo_mod_file = dummy_file_path(&dir, "foo/mod.nr");
+
+        let file_id = fm.add_file(&file_path, FileType::Root).unwrap();
+        let error = fm.resolve_path(file_id, "foo").unwrap_err();
+        assert_eq!(error, ResolvePathError::Ambiguous { file: foo_file, mod_file: foo_mod_file });
+    }
+
+    #[test]
+    fn path_resolve_lists_candidates() {
+        let mut fm = FileManager::new();
+
+        let dir = tempdir().unwrap();
+        let file_path = dummy_file_path(&dir, "lib.nr");
+
+        let file_id = fm.add_file(&file_path, FileType::Root).unwrap();
+        let error = fm.resolve_path(file_id, "foo").unwrap_err();
+        let candidates = vec![dir.path().join("foo.nr"), dir.path().join("foo/mod.nr")];
+        assert_eq!(error, ResolvePathError::NotFound { candidates });
+    }
+
+    #[test]
+    fn path_resolve_path_attribute() {
+        let mut fm = FileManager::new();
+
+        // We have:
+        // - dir/lib.nr
+        // - dir/other/foo.nr, declared as `#[path = "other/foo.nr"] mod bar;`
+        // - dir/other/foo/baz.nr
+        let dir = tempdir().unwrap();
+        let file_path = dummy_file_path(&dir, "lib.nr");
+        std::fs::create_dir_all(dir.path().join("other/foo")).unwrap();
+        dummy_file_path(&dir, "other/foo.nr");
+        dummy_file_path(&dir, "other/foo/baz.nr");
+
+        let file_id = fm.add_file(&file_path, FileType::Root).unwrap();
+        let bar_file_id = fm.resolve_path_attribute(file_id, "other/foo.nr").unwrap();
+        fm.resolve_path(bar_file_id, "baz").unwrap();
+
+        let error = fm.resolve_path_attribute(file_id, "other/foo").unwrap_err();
+        assert_eq!(
+            error,
+            ResolvePathError::InvalidExtension { path: dir.path().join("other/foo") }
+        );
+    }
 }
@@ -0,0 +1,71 @@
+use std::path::{Path, PathBuf};
+
+use acvm::acir::circuit::{Circuit, Gate};
+use acvm::acir::native_types::Witness;
+use acvm::satisfiability::has_reference_implementation;
+use clap::ArgMatches;
+
+use crate::errors::CliError;
+
+use super::{ACIR_EXT, BUILD_DIR, WITNESS_EXT};
+
+pub(crate) fn run(args: ArgMatches) -> Result<(), CliError> {
+    let args = args.subcommand_matches("check-witness").unwrap();
+    let circuit_name = args.value_of("circuit_name").unwrap();
+
+    let mut build_dir = std::env::current_dir().unwrap();
+    build_dir.push(BUILD_DIR);
+    check_witness_with_path(circuit_name, build_dir)
+}
+
+/// Checks the witness written by `nargo compile` against its circuit, without creating a proof
+pub fn check_witness_with_path<P: AsRef<Path>>(
+    circuit_name: &str,
+    build_dir: P,
+) -> Result<(), CliError> {
+    let circuit_bytes = read_artifact(build_dir.as_ref(), circuit_name, ACIR_EXT)?;
+    let circuit = Circuit::from_bytes(&circuit_bytes)
+        .map_err(|err| CliError::Generic(format!("could not read the circuit: {}", err)))?;
+    let witness_bytes = read_artifact(build_dir.as_ref(), circuit_name, WITNESS_EXT)?;
+    let witness = Witness::from_bytes(&witness_bytes)
+        .map_err(|err| CliError::Generic(format!("could not read the witness: {}", err)))?;
+
+    let unchecked = circuit
+        .gates
+        .iter()
+        .filter(|gate| matches!(gate, Gate::GadgetCall(call) if !has_reference_implementation(call.name)))
+        .count();
+    if unchecked > 0 {
+        println!(
+            "{} black box function calls can only be checked by the backend, they are assumed to be satisfied",
+            unchecked
+        );
+    }
+
+    match acvm::check_satisfiability(&circuit, &witness) {
+        Ok(()) => {
+            println!("The witness satisfies every gate of the circuit");
+            Ok(())
+        }
+        Err(unsatisfied) => {
+            let gates: Vec<_> = unsatisfied.iter().map(|gate| gate.to_string()).collect();
+            Err(CliError::Generic(format!(
+                "the witness does not satisfy {} gates:\n{}",
+                unsatisfied.len(),
+                gates.join("\n")
+            )))
+        }
+    }
+}
+
+fn read_artifact(
+    build_dir: &Path,
+    circuit_name: &str,
+    extension: &str,
+) -> Result<Vec<u8>, CliError> {
+    let mut path = PathBuf::from(build_dir);
+    path.push(circuit_name);
+    path.set_extension(extension);
+    std::fs::read(&path)
+        .map_err(|err| CliError::Generic(format!("could not read {}: {}", path.display(), err)))
+}
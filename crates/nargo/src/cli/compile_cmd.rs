@@ -41,6 +41,11 @@ pub fn generate_circuit_to_disk<P: AsRef<Path>>(
     println!("Generated ACIR code into {}", path);
     println!("{:?}", std::fs::canonicalize(&circuit_path));
 
+    circuit_path.pop();
+    circuit_path.push(circuit_name);
+    circuit_path.set_extension(crate::cli::ACIR_TEXT_EXT);
+    write_to_file(compiled_program.circuit.to_string().as_bytes(), &circuit_path);
+
     circuit_path.pop();
     circuit_path.push(circuit_name);
     circuit_path.set_extension(crate::cli::WITNESS_EXT);
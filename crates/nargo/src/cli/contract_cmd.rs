@@ -13,7 +13,11 @@ pub(crate) fn run(args: ArgMatches) -> Result<(), CliError> {
     let driver = Resolver::resolve_root_config(&package_dir)?;
 
     let backend = crate::backends::ConcreteBackend;
-    let compiled_program = driver.into_compiled_program(backend.np_language(), false);
+    let compiled_program = driver.into_compiled_program(
+        backend.np_language(),
+        &|gate| backend.supports_gate(gate),
+        false,
+    );
 
     let smart_contract_string = backend.eth_contract_from_cs(compiled_program.circuit);
 
@@ -11,6 +11,7 @@ use tempdir::TempDir;
 use crate::errors::CliError;
 
 mod build_cmd;
+mod check_witness_cmd;
 mod compile_cmd;
 mod contract_cmd;
 mod new_cmd;
@@ -26,6 +27,7 @@ const PKG_FILE: &str = "Nargo.toml";
 const PROOF_EXT: &str = "proof";
 const BUILD_DIR: &str = "build";
 const ACIR_EXT: &str = "acir";
+const ACIR_TEXT_EXT: &str = "acir.txt";
 const WITNESS_EXT: &str = "tr";
 
 pub fn start_cli() {
@@ -58,6 +60,15 @@ pub fn start_cli() {
                         .help("Emit debug information for the intermediate SSA IR"),
                 ),
         )
+        .subcommand(
+            App::new("check-witness")
+                .about("Check that the witness written by compile satisfies the circuit, without creating a proof")
+                .arg(
+                    Arg::with_name("circuit_name")
+                        .help("The name of the ACIR and witness files")
+                        .required(true),
+                ),
+        )
         .subcommand(
             App::new("compile")
                 .about("Compile the program and its secret execution trace into ACIR format")
@@ -73,6 +84,7 @@ pub fn start_cli() {
         Some("contract") => contract_cmd::run(matches),
         Some("prove") => prove_cmd::run(matches),
         Some("compile") => compile_cmd::run(matches),
+        Some("check-witness") => check_witness_cmd::run(matches),
         Some("verify") => verify_cmd::run(matches),
         None => Err(CliError::Generic("No subcommand was used".to_owned())),
         Some(x) => Err(CliError::Generic(format!("unknown command : {}", x))),
@@ -3,7 +3,7 @@ use std::{collections::BTreeMap, path::PathBuf};
 use acvm::acir::native_types::Witness;
 use acvm::FieldElement;
 use acvm::ProofSystemCompiler;
-use acvm::{GateResolution, PartialWitnessGenerator};
+use acvm::{GateResolution, OpcodeNotSolvable, OpcodeResolutionError, PartialWitnessGenerator};
 use clap::ArgMatches;
 use noirc_abi::{input_parser::InputValue, Abi};
 use std::path::Path;
@@ -50,7 +50,8 @@ fn process_abi_with_input(
             .unwrap_or_else(|| {
                 panic!("ABI expects the parameter `{}`, but this was not found", param_name)
             })
-            .clone();
+            .clone()
+            .into_twos_complement(&param_type)?;
 
         if !value.matches_abi(param_type) {
             return Err(CliError::Generic(format!("The parameters in the main do not match the parameters in the {}.toml file. \n Please check `{}` parameter ", PROVER_INPUT_FILE,param_name)));
@@ -82,15 +83,20 @@ pub fn compile_circuit_and_witness<P: AsRef<Path>>(
 ) -> Result<(noirc_driver::CompiledProgram, BTreeMap<Witness, FieldElement>), CliError> {
     let driver = Resolver::resolve_root_config(program_dir.as_ref())?;
     let backend = crate::backends::ConcreteBackend;
-    let compiled_program = driver.into_compiled_program(backend.np_language(), show_ssa);
+    let compiled_program = driver.into_compiled_program(
+        backend.np_language(),
+        &|gate| backend.supports_gate(gate),
+        show_ssa,
+    );
 
     // Parse the initial witness values
+    let abi = compiled_program.abi.as_ref().unwrap();
     let witness_map = noirc_abi::input_parser::Format::Toml
-        .parse(program_dir, PROVER_INPUT_FILE)
+        .parse(program_dir, PROVER_INPUT_FILE, abi)
         .map_err(CliError::from)?;
 
     // Check that enough witness values were supplied
-    let num_params = compiled_program.abi.as_ref().unwrap().num_parameters();
+    let num_params = abi.num_parameters();
     if num_params != witness_map.len() {
         panic!(
             "Expected {} number of values, but got {} number of values",
@@ -99,19 +105,50 @@ pub fn compile_circuit_and_witness<P: AsRef<Path>>(
         )
     }
 
-    let abi = compiled_program.abi.as_ref().unwrap();
     let mut solved_witness = process_abi_with_input(abi.clone(), witness_map)?;
 
-    let solver_res = backend.solve(&mut solved_witness, compiled_program.circuit.gates.clone());
-
+    // The values printed by the program are shown while the witness is solved
+    let solver_res = backend.solve_with_logger(
+        &mut solved_witness,
+        compiled_program.circuit.gates.clone(),
+        &mut |message| println!("{}", message),
+    );
+
+    // The witnesses are reported with the indices of the circuit printed by --show-ssa,
+    // which is the circuit before its witnesses were compacted
+    let original_witness =
+        |witness: Witness| compiled_program.witness_remap.original(witness).unwrap_or(witness);
     match solver_res {
-            GateResolution::UnsupportedOpcode(opcode) => return Err(CliError::Generic(format!(
-                "backend does not currently support the {} opcode. ACVM does not currently fall back to arithmetic gates.",
+            GateResolution::Failed(OpcodeResolutionError {
+                reason: OpcodeNotSolvable::UnsupportedOpcode(opcode),
+                ..
+            }) => return Err(CliError::Generic(format!(
+                "backend does not currently support the {} opcode, which has no fallback to arithmetic gates.",
                 opcode
             ))),
-            GateResolution::UnsatisfiedConstrain => return Err(CliError::Generic(
-                "could not satisfy all constraints".to_string()
-            )),
+            GateResolution::Failed(mut error) => {
+                for (witness, _) in &mut error.witnesses {
+                    *witness = original_witness(*witness);
+                }
+                let message = match compiled_program.assertions.get(&error.gate_index) {
+                    Some(assertion) if error.reason == OpcodeNotSolvable::UnsatisfiedConstraint => {
+                        format!("could not satisfy all constraints: {}", assertion)
+                    }
+                    _ => error.to_string(),
+                };
+                return Err(CliError::Generic(message));
+            }
+            GateResolution::Stalled { gates, missing_witnesses } => {
+                let witnesses: Vec<_> = missing_witnesses
+                    .into_iter()
+                    .map(|witness| format!("x{}", original_witness(witness).0))
+                    .collect();
+                return Err(CliError::Generic(format!(
+                    "could not solve {} gates, as no gate assigns the witnesses {}",
+                    gates.len(),
+                    witnesses.join(", ")
+                )));
+            }
             GateResolution::Resolved => (),
             _ => unreachable!(),
         }
@@ -46,7 +46,8 @@ fn process_abi_with_verifier_input(
             .unwrap_or_else(|| {
                 panic!("ABI expects the parameter `{}`, but this was not found", param_name)
             })
-            .clone();
+            .clone()
+            .into_twos_complement(&param_type)?;
 
         if !value.matches_abi(param_type) && param_name != RESERVED_PUBLIC_ARR {
             return Err(CliError::Generic(format!("The parameters in the main do not match the parameters in the {}.toml file. \n Please check `{}` parameter. ", VERIFIER_INPUT_FILE,param_name)));
@@ -85,7 +86,11 @@ pub fn verify_with_path<P: AsRef<Path>>(
     let driver = Resolver::resolve_root_config(program_dir.as_ref())?;
     let backend = crate::backends::ConcreteBackend;
 
-    let compiled_program = driver.into_compiled_program(backend.np_language(), show_ssa);
+    let compiled_program = driver.into_compiled_program(
+        backend.np_language(),
+        &|gate| backend.supports_gate(gate),
+        show_ssa,
+    );
 
     let mut public_abi = compiled_program.abi.clone().unwrap().public_abi();
     add_dummy_setpub_arr(&mut public_abi);
@@ -94,7 +99,7 @@ pub fn verify_with_path<P: AsRef<Path>>(
     if num_pub_params != 0 {
         let curr_dir = program_dir;
         public_inputs = noirc_abi::input_parser::Format::Toml
-            .parse(curr_dir, VERIFIER_INPUT_FILE)
+            .parse(curr_dir, VERIFIER_INPUT_FILE, &public_abi)
             .map_err(CliError::from)?;
     }
 
@@ -56,6 +56,10 @@ impl<'a> Resolver<'a> {
         let cfg_path = super::find_package_config(dir_path)?;
         let cfg = super::toml::parse(cfg_path)?;
 
+        driver.set_checked_arithmetic(cfg.package.checked_arithmetic);
+        if let Some(limit) = cfg.package.recursion_limit {
+            driver.set_recursion_limit(limit);
+        }
         let crate_id = driver.create_local_crate(entry_path, crate_type);
 
         let mut resolver = Resolver::with_driver(&mut driver);
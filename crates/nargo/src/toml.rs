@@ -37,6 +37,13 @@ pub struct Package {
     pub compiler_version: Option<String>,
     pub backend: Option<String>,
     pub license: Option<String>,
+    // Integer additions, subtractions and multiplications fail on overflow instead of wrapping around.
+    // This is a property of the package rather than of a command, so that proving and verifying
+    // compile the same circuit
+    #[serde(default)]
+    pub checked_arithmetic: bool,
+    // The number of nested calls up to which a recursion bounded by constant arguments is unrolled
+    pub recursion_limit: Option<u32>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -7,6 +7,9 @@ pub enum InputParserError {
     ParseStr(String),
     ParseHexStr(String),
     DuplicateVariableName(String),
+    SignedIntegerOutOfRange(String, u32),
+    ArrayLengthMismatch(String, u128, usize),
+    ArrayNestingMismatch(String),
 }
 
 impl std::fmt::Display for InputParserError {
@@ -16,7 +19,10 @@ impl std::fmt::Display for InputParserError {
             InputParserError::ParseTomlMap(err_msg) => write!(f, "input.toml file is badly formed, could not parse, {}", err_msg),
             InputParserError::ParseStr(err_msg) => write!(f, "Expected witness values to be integers, provided value causes `{}` error", err_msg),
             InputParserError::ParseHexStr(err_msg) => write!(f, "Could not parse hex value {}", err_msg),
-            InputParserError::DuplicateVariableName(err_msg) => write!(f, "duplicate variable name {}", err_msg)
+            InputParserError::DuplicateVariableName(err_msg) => write!(f, "duplicate variable name {}", err_msg),
+            InputParserError::SignedIntegerOutOfRange(value, width) => write!(f, "{} does not fit in a signed integer of {} bits", value, width),
+            InputParserError::ArrayLengthMismatch(parameter, expected, found) => write!(f, "parameter {} expects arrays of length {}, but an array of length {} was given", parameter, expected, found),
+            InputParserError::ArrayNestingMismatch(parameter) => write!(f, "the nesting of the arrays given for parameter {} does not match its type", parameter),
         }
     }
 }
@@ -5,7 +5,7 @@ use std::{collections::BTreeMap, path::Path};
 use acvm::FieldElement;
 
 use crate::errors::InputParserError;
-use crate::AbiType;
+use crate::{Abi, AbiType, Sign};
 /// This is what all formats eventually transform into
 /// For example, a toml file will parse into TomlTypes
 /// and those TomlTypes will be mapped to Value
@@ -24,10 +24,62 @@ impl InputValue {
             (InputValue::Field(_), AbiType::Array { .. }) => false,
             (InputValue::Field(_), AbiType::Integer { .. }) => true,
             (InputValue::Vec(_), AbiType::Field(_)) => false,
-            (InputValue::Vec(x), AbiType::Array { length, .. }) => x.len() == length as usize,
+            (InputValue::Vec(x), array @ AbiType::Array { .. }) => x.len() == array.num_elements(),
             (InputValue::Vec(_), AbiType::Integer { .. }) => false,
         }
     }
+
+    /// Signed integers are represented in two's complement, so a negative value `-x`
+    /// given for a signed parameter of width `n` is mapped to `2^n - x`.
+    /// Values outside of `[-2^(n-1), 2^(n-1))` are rejected
+    pub fn into_twos_complement(self, abi_param: &AbiType) -> Result<InputValue, InputParserError> {
+        let value = match (self, abi_param) {
+            (InputValue::Field(f), AbiType::Integer { sign: Sign::Signed, width, .. }) => {
+                InputValue::Field(twos_complement(f, *width)?)
+            }
+            (InputValue::Vec(x), array @ AbiType::Array { .. }) => match array.element_type() {
+                AbiType::Integer { sign: Sign::Signed, width, .. } => {
+                    let x: Result<_, _> =
+                        x.into_iter().map(|f| twos_complement(f, *width)).collect();
+                    InputValue::Vec(x?)
+                }
+                _ => InputValue::Vec(x),
+            },
+            (value, _) => value,
+        };
+        Ok(value)
+    }
+}
+
+fn twos_complement(value: FieldElement, width: u32) -> Result<FieldElement, InputParserError> {
+    // Negative values are parsed as field negations, p - x, which do not fit in 128 bits
+    let negative = !value.fits_in_u128();
+    let magnitude = if negative { -value } else { value };
+
+    let in_range = magnitude.fits_in_u128() && {
+        let magnitude = magnitude.to_u128();
+        match width.checked_sub(1) {
+            None => magnitude == 0,
+            Some(bits) if bits >= 128 => true,
+            Some(bits) if negative => magnitude <= 1 << bits,
+            Some(bits) => magnitude < 1 << bits,
+        }
+    };
+    if !in_range {
+        let magnitude = if magnitude.fits_in_u128() {
+            magnitude.to_u128().to_string()
+        } else {
+            magnitude.to_hex()
+        };
+        let value = if negative { format!("-{}", magnitude) } else { magnitude };
+        return Err(InputParserError::SignedIntegerOutOfRange(value, width));
+    }
+
+    if negative {
+        Ok(value + FieldElement::from(2_i128).pow(&FieldElement::from(width as i128)))
+    } else {
+        Ok(value)
+    }
 }
 
 /// Parses the initial Witness Values that are needed to seed the
@@ -55,14 +107,56 @@ impl Format {
         &self,
         path: P,
         file_name: &str,
+        abi: &Abi,
     ) -> Result<BTreeMap<String, InputValue>, InputParserError> {
         match self {
             Format::Toml => {
                 let mut dir_path = path.as_ref().to_path_buf();
                 dir_path.push(file_name);
                 dir_path.set_extension(self.ext());
-                toml::parse(dir_path)
+                toml::parse(dir_path, abi)
             }
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use acvm::FieldElement;
+
+    use super::InputValue;
+    use crate::{AbiFEType, AbiType, Sign};
+
+    fn parse_i8(value: i128) -> Option<FieldElement> {
+        let typ = AbiType::Integer { visibility: AbiFEType::Private, sign: Sign::Signed, width: 8 };
+        match InputValue::Field(FieldElement::from(value)).into_twos_complement(&typ) {
+            Ok(InputValue::Field(value)) => Some(value),
+            Ok(InputValue::Vec(_)) => unreachable!("an integer is parsed into a single value"),
+            Err(_) => None,
+        }
+    }
+
+    #[test]
+    fn signed_integer_bounds() {
+        assert_eq!(parse_i8(-128), Some(FieldElement::from(128_i128)));
+        assert_eq!(parse_i8(-1), Some(FieldElement::from(255_i128)));
+        assert_eq!(parse_i8(0), Some(FieldElement::zero()));
+        assert_eq!(parse_i8(127), Some(FieldElement::from(127_i128)));
+        assert_eq!(parse_i8(-129), None);
+        assert_eq!(parse_i8(128), None);
+        assert_eq!(parse_i8(-200), None);
+        assert_eq!(parse_i8(200), None);
+    }
+
+    #[test]
+    fn signed_array_elements_are_checked() {
+        let element =
+            AbiType::Integer { visibility: AbiFEType::Private, sign: Sign::Signed, width: 8 };
+        let typ =
+            AbiType::Array { visibility: AbiFEType::Private, length: 2, typ: Box::new(element) };
+        let values = vec![FieldElement::from(-3_i128), FieldElement::from(100_i128)];
+        assert!(InputValue::Vec(values).into_twos_complement(&typ).is_ok());
+        let values = vec![FieldElement::from(-3_i128), FieldElement::from(300_i128)];
+        assert!(InputValue::Vec(values).into_twos_complement(&typ).is_err());
+    }
+}
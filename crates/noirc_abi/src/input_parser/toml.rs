@@ -1,11 +1,13 @@
 use super::InputValue;
 use crate::errors::InputParserError;
+use crate::{Abi, AbiType};
 use acvm::FieldElement;
 use serde_derive::Deserialize;
 use std::{collections::BTreeMap, path::Path};
 
 pub(crate) fn parse<P: AsRef<Path>>(
     path_to_toml: P,
+    abi: &Abi,
 ) -> Result<BTreeMap<String, InputValue>, InputParserError> {
     let path_to_toml = path_to_toml.as_ref();
     if !path_to_toml.exists() {
@@ -17,13 +19,14 @@ pub(crate) fn parse<P: AsRef<Path>>(
     // Parse input.toml into a BTreeMap, converting the argument to field elements
     let data: BTreeMap<String, TomlTypes> = toml::from_str(&input_as_string)
         .map_err(|err_msg| InputParserError::ParseTomlMap(err_msg.to_string()))?;
-    toml_map_to_field(data)
+    toml_map_to_field(data, abi)
 }
 
 /// Converts the Toml mapping to the native representation that the compiler
 /// understands for Inputs
 fn toml_map_to_field(
     toml_map: BTreeMap<String, TomlTypes>,
+    abi: &Abi,
 ) -> Result<BTreeMap<String, InputValue>, InputParserError> {
     let mut field_map = BTreeMap::new();
 
@@ -55,12 +58,86 @@ fn toml_map_to_field(
                     InputValue::Vec(array_elements?),
                 )?
             }
+            TomlTypes::ArrayNested(arrays) => {
+                // Arrays of single values are given in the flattened layout, like in the
+                // Verifier.toml written by nargo, so only nested arrays are checked against the ABI
+                let typ = if arrays.iter().any(TomlTypes::is_array) {
+                    abi.parameters.iter().find(|(name, _)| name == &parameter).map(|(_, typ)| typ)
+                } else {
+                    None
+                };
+                let mut array_elements = Vec::new();
+                flatten_toml_array(
+                    &parameter,
+                    TomlTypes::ArrayNested(arrays),
+                    typ,
+                    &mut array_elements,
+                )?;
+                check_toml_map_duplicates(
+                    &mut field_map,
+                    parameter,
+                    InputValue::Vec(array_elements),
+                )?
+            }
         }
     }
 
     Ok(field_map)
 }
 
+/// Multi-dimensional arrays are given as nested arrays, their elements are
+/// laid out row by row like the compiler stores them.
+/// When the type of the parameter is known, each level of nesting must have the length
+/// of the corresponding array type
+fn flatten_toml_array(
+    parameter: &str,
+    value: TomlTypes,
+    typ: Option<&AbiType>,
+    elements: &mut Vec<FieldElement>,
+) -> Result<(), InputParserError> {
+    let items: Vec<_> = match value {
+        TomlTypes::String(string) => {
+            check_single_value(parameter, typ)?;
+            elements.push(parse_str(&string)?);
+            return Ok(());
+        }
+        TomlTypes::Integer(integer) => {
+            check_single_value(parameter, typ)?;
+            elements.push(parse_str(&integer.to_string())?);
+            return Ok(());
+        }
+        TomlTypes::ArrayNum(arr_num) => arr_num.into_iter().map(TomlTypes::Integer).collect(),
+        TomlTypes::ArrayString(arr_str) => arr_str.into_iter().map(TomlTypes::String).collect(),
+        TomlTypes::ArrayNested(arrays) => arrays,
+    };
+
+    let element_type = match typ {
+        Some(AbiType::Array { length, typ, .. }) if *length == items.len() as u128 => Some(&**typ),
+        Some(AbiType::Array { length, .. }) => {
+            return Err(InputParserError::ArrayLengthMismatch(
+                parameter.to_owned(),
+                *length,
+                items.len(),
+            ))
+        }
+        Some(_) => return Err(InputParserError::ArrayNestingMismatch(parameter.to_owned())),
+        None => None,
+    };
+    for item in items {
+        flatten_toml_array(parameter, item, element_type, elements)?;
+    }
+    Ok(())
+}
+
+fn check_single_value(parameter: &str, typ: Option<&AbiType>) -> Result<(), InputParserError> {
+    match typ {
+        Some(AbiType::Array { .. }) => {
+            Err(InputParserError::ArrayNestingMismatch(parameter.to_owned()))
+        }
+        _ => Ok(()),
+    }
+}
+
 fn check_toml_map_duplicates(
     field_map: &mut BTreeMap<String, InputValue>,
     parameter: String,
@@ -78,12 +155,21 @@ enum TomlTypes {
     // This is most likely going to be a hex string
     // But it is possible to support UTF-8
     String(String),
-    // Just a regular integer, that can fit in 128 bits
-    Integer(u64),
+    // Just a regular integer, that can fit in 64 bits
+    // Negative integers are allowed for signed parameters
+    Integer(i64),
     // Array of regular integers
-    ArrayNum(Vec<u64>),
+    ArrayNum(Vec<i64>),
     // Array of hexadecimal integers
     ArrayString(Vec<String>),
+    // Array of arrays, for multi-dimensional arrays
+    ArrayNested(Vec<TomlTypes>),
+}
+
+impl TomlTypes {
+    fn is_array(&self) -> bool {
+        !matches!(self, TomlTypes::String(_) | TomlTypes::Integer(_))
+    }
 }
 
 fn parse_str(value: &str) -> Result<FieldElement, InputParserError> {
@@ -96,3 +182,62 @@ fn parse_str(value: &str) -> Result<FieldElement, InputParserError> {
         Ok(FieldElement::from(val))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use super::{toml_map_to_field, TomlTypes};
+    use crate::errors::InputParserError;
+    use crate::input_parser::InputValue;
+    use crate::{Abi, AbiFEType, AbiType, Sign};
+
+    // The ABI of `fn main(x: [[u32; 3]; 2])`
+    fn matrix_abi() -> Abi {
+        let row = AbiType::Array {
+            visibility: AbiFEType::Private,
+            length: 3,
+            typ: Box::new(AbiType::Integer {
+                visibility: AbiFEType::Private,
+                sign: Sign::Unsigned,
+                width: 32,
+            }),
+        };
+        let matrix =
+            AbiType::Array { visibility: AbiFEType::Private, length: 2, typ: Box::new(row) };
+        Abi { parameters: vec![("x".to_owned(), matrix)] }
+    }
+
+    fn parse(input: &str) -> Result<BTreeMap<String, InputValue>, InputParserError> {
+        let data: BTreeMap<String, TomlTypes> = toml::from_str(input).unwrap();
+        toml_map_to_field(data, &matrix_abi())
+    }
+
+    fn num_elements(input: &str) -> usize {
+        match &parse(input).unwrap()["x"] {
+            InputValue::Vec(elements) => elements.len(),
+            InputValue::Field(_) => unreachable!("x is an array"),
+        }
+    }
+
+    #[test]
+    fn nested_arrays_follow_the_abi() {
+        assert_eq!(num_elements("x = [[1, 2, 3], [4, 5, 6]]"), 6);
+        assert_eq!(num_elements("x = [['0x01', '0x02', '0x03'], [4, 5, 6]]"), 6);
+        // The flattened layout written in Verifier.toml is still accepted
+        assert_eq!(num_elements("x = [1, 2, 3, 4, 5, 6]"), 6);
+
+        assert!(matches!(
+            parse("x = [[1, 2], [3, 4, 5, 6]]"),
+            Err(InputParserError::ArrayLengthMismatch(_, 3, 2))
+        ));
+        assert!(matches!(
+            parse("x = [[1, 2, 3], [4, 5, 6], [7, 8, 9]]"),
+            Err(InputParserError::ArrayLengthMismatch(_, 2, 3))
+        ));
+        assert!(matches!(
+            parse("x = [[[1], [2], [3]], [[4], [5], [6]]]"),
+            Err(InputParserError::ArrayNestingMismatch(_))
+        ));
+    }
+}
@@ -58,7 +58,15 @@ impl AbiType {
     pub fn num_elements(&self) -> usize {
         match self {
             AbiType::Field(_) | AbiType::Integer { .. } => 1,
-            AbiType::Array { visibility: _, length, typ: _ } => *length as usize,
+            AbiType::Array { visibility: _, length, typ } => *length as usize * typ.num_elements(),
+        }
+    }
+
+    /// The type of the elements of an array, or of its innermost arrays if it is multi-dimensional
+    pub fn element_type(&self) -> &AbiType {
+        match self {
+            AbiType::Array { typ, .. } => typ.element_type(),
+            _ => self,
         }
     }
 
@@ -1,29 +1,76 @@
-use acvm::acir::circuit::Circuit;
-use fm::FileType;
+use acvm::acir::circuit::{Circuit, Gate};
+use acvm::compiler::WitnessRemap;
+use fm::{FileManager, FileType};
 use noirc_abi::Abi;
-use noirc_errors::{DiagnosableError, Reporter};
+use noirc_errors::{DiagnosableError, Location, Reporter};
 use noirc_evaluator::create_circuit;
 use noirc_frontend::graph::{CrateId, CrateName, CrateType, LOCAL_CRATE};
 use noirc_frontend::hir::def_map::CrateDefMap;
 use noirc_frontend::hir::Context;
 use noirc_frontend::monomorphisation::monomorphise;
+use noirc_frontend::monomorphisation::recursion::{unroll_recursion, DEFAULT_RECURSION_LIMIT};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 #[derive(Debug)]
 pub struct Driver {
     context: Context,
+    checked_arithmetic: bool,
+    recursion_limit: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CompiledProgram {
     pub circuit: Circuit,
     pub abi: Option<noirc_abi::Abi>,
+    /// The assertions of the program, indexed by the gate of the circuit which enforces them
+    #[serde(default)]
+    pub assertions: BTreeMap<usize, GateAssertion>,
+    /// The indices of the witnesses before they were compacted, which are the ones printed with `show_ssa`
+    #[serde(skip)]
+    pub witness_remap: WitnessRemap,
+}
+
+/// An assertion of the program, used to report where a gate which cannot be satisfied comes from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GateAssertion {
+    pub message: String,
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    /// The source code of the asserted expression
+    pub source: String,
+}
+
+impl std::fmt::Display for GateAssertion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}\n  --> {}:{}:{}\n   | {}",
+            self.message, self.file, self.line, self.column, self.source
+        )
+    }
 }
 
 impl Driver {
     pub fn new() -> Self {
-        Driver { context: Context::default() }
+        Driver {
+            context: Context::default(),
+            checked_arithmetic: false,
+            recursion_limit: DEFAULT_RECURSION_LIMIT,
+        }
+    }
+
+    /// Makes integer additions, subtractions and multiplications fail on overflow
+    /// instead of wrapping around
+    pub fn set_checked_arithmetic(&mut self, checked_arithmetic: bool) {
+        self.checked_arithmetic = checked_arithmetic;
+    }
+
+    /// Sets the number of nested calls up to which a recursion bounded by constant arguments is unrolled
+    pub fn set_recursion_limit(&mut self, recursion_limit: u32) {
+        self.recursion_limit = recursion_limit;
     }
 
     // This is here for backwards compatibility
@@ -31,7 +78,8 @@ impl Driver {
     pub fn compile_file(root_file: PathBuf, np_language: acvm::Language) -> CompiledProgram {
         let mut driver = Driver::new();
         driver.create_local_crate(root_file, CrateType::Binary);
-        driver.into_compiled_program(np_language, false)
+        // Without a backend, every gate is assumed to be supported
+        driver.into_compiled_program(np_language, &|_| true, false)
     }
 
     /// Compiles a file and returns true if compilation was successful
@@ -137,6 +185,7 @@ impl Driver {
     pub fn into_compiled_program(
         mut self,
         np_language: acvm::Language,
+        is_supported: &dyn Fn(&Gate) -> bool,
         show_ssa: bool,
     ) -> CompiledProgram {
         self.build();
@@ -160,25 +209,44 @@ impl Driver {
         let func_meta = self.context.def_interner.function_meta(&main_function);
         let abi = func_meta.into_abi(&self.context.def_interner);
 
-        let ast = monomorphise(main_function, self.context.def_interner);
+        let mut ast = monomorphise(main_function, self.context.def_interner);
+        if let Err(err) = unroll_recursion(&mut ast, self.recursion_limit) {
+            Reporter::with_diagnostics(
+                err.location().file,
+                &self.context.file_manager,
+                &[err.into_diagnostic()],
+            );
+            Reporter::finish(1);
+            unreachable!("reporter will exit before this point")
+        }
 
         // Compile Program
-        let circuit = match create_circuit(ast, np_language, show_ssa) {
-            Ok(circuit) => circuit,
-            Err(err) => {
-                // The FileId here will be the file id of the file with the main file
-                // Errors will be shown at the callsite without a stacktrace
-                Reporter::with_diagnostics(
-                    err.location.file,
-                    &self.context.file_manager,
-                    &[err.to_diagnostic()],
-                );
-                Reporter::finish(1);
-                unreachable!("reporter will exit before this point")
-            }
-        };
+        let (circuit, assertions, witness_remap) =
+            match create_circuit(ast, np_language, is_supported, show_ssa, self.checked_arithmetic)
+            {
+                Ok(compiled) => compiled,
+                Err(err) => {
+                    // The FileId here will be the file id of the file with the main file
+                    // Errors will be shown at the callsite without a stacktrace
+                    Reporter::with_diagnostics(
+                        err.location.file,
+                        &self.context.file_manager,
+                        &[err.to_diagnostic()],
+                    );
+                    Reporter::finish(1);
+                    unreachable!("reporter will exit before this point")
+                }
+            };
+
+        let file_manager = &mut self.context.file_manager;
+        let assertions = assertions
+            .into_iter()
+            .map(|(gate, assertion)| {
+                (gate, gate_assertion(file_manager, assertion.message, assertion.location))
+            })
+            .collect();
 
-        CompiledProgram { circuit, abi: Some(abi) }
+        CompiledProgram { circuit, abi: Some(abi), assertions, witness_remap }
     }
 
     #[cfg(not(feature = "std"))]
@@ -214,6 +282,26 @@ impl Driver {
     }
 }
 
+// Resolves the location of an assertion into a file, a line and a column, so that it can be reported
+// without the file manager
+fn gate_assertion(
+    file_manager: &mut FileManager,
+    message: String,
+    location: Location,
+) -> GateAssertion {
+    let path = file_manager.fetch_file(location.file).path().display().to_string();
+    let source = file_manager.fetch_file(location.file).get_source();
+
+    let start = location.span.start() as usize;
+    let end = location.span.end() as usize;
+    let before = &source[..start];
+    let line = before.matches('\n').count() + 1;
+    let column = start - before.rfind('\n').map_or(0, |newline| newline + 1) + 1;
+
+    let source = source[start..end].to_owned();
+    GateAssertion { message, file: path, line, column, source }
+}
+
 impl Default for Driver {
     fn default() -> Self {
         Self::new()
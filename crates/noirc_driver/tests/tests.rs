@@ -1,3 +1,4 @@
+use acvm::acir::circuit::Gate;
 use noirc_driver::Driver;
 use std::path::PathBuf;
 
@@ -25,3 +26,21 @@ fn pass() {
         assert!(Driver::file_compiles(&path), "path: {}", path.display())
     }
 }
+
+#[test]
+fn constrain_message() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/pass/constrain_message.nr");
+
+    let program = Driver::compile_file(path, acvm::Language::PLONKCSat { width: 3 });
+    let (gate, assertion) = program
+        .assertions
+        .iter()
+        .find(|(_, assertion)| assertion.message == "balance too low")
+        .expect("the message of the constraint is not reported");
+
+    // The constraint is enforced by an arithmetic gate, which fails when it cannot be satisfied
+    assert!(matches!(program.circuit.gates[*gate], Gate::Arithmetic(_)));
+    assert_eq!((assertion.line, assertion.column), (3, 15));
+    assert_eq!(assertion.source, "balance >= amount");
+}
@@ -52,6 +52,9 @@ pub enum RuntimeErrorKind {
 
     #[error("Unsupported operation error")]
     UnsupportedOp { op: String, first_type: String, second_type: String },
+
+    #[error("Division by zero")]
+    DivisionByZero,
 }
 
 impl RuntimeErrorKind {
@@ -93,6 +96,11 @@ impl DiagnosableError for RuntimeError {
                 format!("function {} can only be called in main", func_name),
                 span,
             ),
+            RuntimeErrorKind::DivisionByZero => Diagnostic::simple_error(
+                "division by zero".to_owned(),
+                "attempt to divide by zero".to_owned(),
+                span,
+            ),
         }
     }
 }
@@ -86,6 +86,9 @@ impl<'a> Interpreter<'a> {
                 "The Or operation is currently not implemented. First implement in Barretenberg."
                     .to_owned(),
             )),
+            BinaryOpKind::Modulo => Err(RuntimeErrorKind::Unimplemented(
+                "The Modulo operation is only implemented in the SSA pass.".to_owned(),
+            )),
             BinaryOpKind::ShiftLeft | BinaryOpKind::ShiftRight => {
                 Err(RuntimeErrorKind::Unimplemented(
                     "Bit shift operations are not currently implemented.".to_owned(),
@@ -13,12 +13,16 @@ use acvm::acir::circuit::{
     Circuit, PublicInputs,
 };
 use acvm::acir::native_types::{Expression, Linear, Witness};
+use acvm::compiler::WitnessRemap;
 use acvm::FieldElement;
 use acvm::Language;
 use environment::{Environment, FuncContext};
 use errors::{RuntimeError, RuntimeErrorKind};
+use fm::FileId;
 use noirc_abi::{AbiFEType, AbiType};
+use noirc_errors::{Location, Span};
 use noirc_frontend::monomorphisation::ast::*;
+use std::collections::{BTreeMap, BTreeSet};
 
 use object::{Array, Integer, Object};
 use ssa::{code_gen::IRGenerator, node};
@@ -31,7 +35,19 @@ pub struct Evaluator {
     // to compile wasm64.
     current_witness_index: u32,
     public_inputs: Vec<Witness>,
+    // The witnesses of the parameters of main, whose indices are expected by the ABI
+    parameters: BTreeSet<Witness>,
     gates: Vec<Gate>,
+    // The assertions of the program enforced by a gate, indexed by the gate
+    assertions: BTreeMap<usize, Assertion>,
+}
+
+/// A condition of the program which is enforced by a gate of the circuit,
+/// so that a gate which cannot be satisfied can be reported at its source
+#[derive(Debug, Clone)]
+pub struct Assertion {
+    pub message: String,
+    pub location: Location,
 }
 
 /// Compiles the Program into ACIR and applies optimisations to the arithmetic gates
@@ -39,37 +55,67 @@ pub struct Evaluator {
 // Some of these could have been removed due to optimisations. We need this number because the
 // Standard format requires the number of witnesses. The max number is also fine.
 // If we had a composer object, we would not need it
+//
+// The assertions are returned along with the circuit, indexed by the optimised gate which enforces them,
+// and so is the renumbering of the witnesses which were compacted after the optimisations.
+// When `checked_arithmetic` is set, integer additions, subtractions and multiplications fail on overflow
+// instead of wrapping around.
+// The gates which are not supported by the backend, according to `is_supported`, are rewritten into arithmetic gates.
 pub fn create_circuit(
     program: Program,
     np_language: Language,
+    is_supported: &dyn Fn(&Gate) -> bool,
     enable_logging: bool,
-) -> Result<Circuit, RuntimeError> {
+    checked_arithmetic: bool,
+) -> Result<(Circuit, BTreeMap<usize, Assertion>, WitnessRemap), RuntimeError> {
     let mut evaluator = Evaluator::new();
 
     // create a new environment for the main context
     let mut env = Environment::new(FuncContext::Main);
 
     // First evaluate the main function
-    evaluator.evaluate_main_alt(&mut env, program, enable_logging)?;
+    evaluator.evaluate_main_alt(&mut env, program, enable_logging, checked_arithmetic)?;
 
     let witness_index = evaluator.current_witness_index();
 
-    let optimised_circuit = acvm::compiler::compile(
+    let compiled = acvm::compiler::compile(
         Circuit {
             current_witness_index: witness_index,
             gates: evaluator.gates,
             public_inputs: PublicInputs(evaluator.public_inputs),
         },
         np_language,
-    );
+        is_supported,
+    )
+    .map_err(|errors| {
+        let errors: Vec<_> = errors.iter().map(ToString::to_string).collect();
+        let message = format!("the circuit cannot be compiled:\n{}", errors.join("\n"));
+        // The circuit as a whole has no span, so the error is reported on the main file, which is the first file
+        RuntimeErrorKind::Spanless(message)
+            .add_location(Location::new(Span::single_char(0), FileId::default()))
+    })?;
+    if enable_logging {
+        println!("removed {} redundant range gates", compiled.removed_range_gates);
+    }
+    // The gates are left in place, so the assertions do not need to be remapped
+    let (circuit, witness_remap) =
+        acvm::compiler::compact_witnesses(compiled.circuit, &evaluator.parameters);
 
-    Ok(optimised_circuit)
+    let mut assertions = BTreeMap::new();
+    for (gate_index, origin) in compiled.origins.into_iter().enumerate() {
+        if let Some(assertion) = evaluator.assertions.get(&origin) {
+            assertions.insert(gate_index, assertion.clone());
+        }
+    }
+
+    Ok((circuit, assertions, witness_remap))
 }
 
 impl Evaluator {
     fn new() -> Self {
         Evaluator {
             public_inputs: Vec::new(),
+            parameters: BTreeSet::new(),
             // XXX: Barretenberg, reserves the first index to have value 0.
             // When we increment, we do not use this index at all.
             // This means that every constraint system at the moment, will either need
@@ -80,6 +126,15 @@ impl Evaluator {
             //
             current_witness_index: 0,
             gates: Vec::new(),
+            assertions: BTreeMap::new(),
+        }
+    }
+
+    // Attaches an assertion to the gates generated since first_gate
+    fn add_assertion(&mut self, first_gate: usize, message: String, location: Location) {
+        for gate_index in first_gate..self.gates.len() {
+            let assertion = Assertion { message: message.clone(), location };
+            self.assertions.insert(gate_index, assertion);
         }
     }
 
@@ -111,8 +166,9 @@ impl Evaluator {
         env: &mut Environment,
         program: Program,
         enable_logging: bool,
+        checked_arithmetic: bool,
     ) -> Result<(), RuntimeError> {
-        let mut igen = IRGenerator::new(program);
+        let mut igen = IRGenerator::new(program, checked_arithmetic);
         self.parse_abi_alt(&mut igen);
 
         // Now call the main function
@@ -150,19 +206,24 @@ impl Evaluator {
         match param_type {
             AbiType::Field(visibility) => {
                 let witness = self.add_witness_to_cs();
+                self.parameters.insert(witness);
                 if *visibility == AbiFEType::Public {
                     self.public_inputs.push(witness);
                 }
                 igen.abi_var(name, def, node::ObjectType::NativeField, witness);
             }
-            AbiType::Array { visibility, length, typ } => {
+            AbiType::Array { visibility, .. } => {
+                // Multi-dimensional arrays are given as a flat array of their innermost elements
+                let typ = param_type.element_type();
+                let length = param_type.num_elements() as u128;
                 let mut witnesses = Vec::new();
                 let mut element_width = None;
-                if let AbiType::Integer { width, .. } = typ.as_ref() {
+                if let AbiType::Integer { width, .. } = typ {
                     element_width = Some(*width);
                 }
-                for _ in 0..*length {
+                for _ in 0..length {
                     let witness = self.add_witness_to_cs();
+                    self.parameters.insert(witness);
                     witnesses.push(witness);
                     if let Some(ww) = element_width {
                         ssa::acir_gen::range_constraint(witness, ww, self)?;
@@ -171,10 +232,11 @@ impl Evaluator {
                         self.public_inputs.push(witness);
                     }
                 }
-                igen.abi_array(name, def, typ.as_ref(), *length, witnesses);
+                igen.abi_array(name, def, typ, length, witnesses);
             }
             AbiType::Integer { visibility, sign, width } => {
                 let witness = self.add_witness_to_cs();
+                self.parameters.insert(witness);
                 ssa::acir_gen::range_constraint(witness, *width, self)?;
                 if *visibility == AbiFEType::Public {
                     self.public_inputs.push(witness);
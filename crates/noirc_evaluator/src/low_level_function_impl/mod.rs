@@ -9,6 +9,7 @@ mod blake2s;
 mod ecdsa_secp256k1;
 mod fixed_based_scalar_mul;
 mod hash_to_field;
+mod keccak256;
 mod merkle_membership;
 mod pedersen;
 mod schnorr;
@@ -22,6 +23,7 @@ use blake2s::Blake2sGadget;
 use ecdsa_secp256k1::EcdsaSecp256k1Gadget;
 use fixed_based_scalar_mul::FixedBaseScalarMulGadget;
 use hash_to_field::HashToFieldGadget;
+use keccak256::Keccak256Gadget;
 use merkle_membership::MerkleMembershipGadget;
 use noirc_errors::Location;
 use noirc_frontend::hir_def::expr::HirCallExpression;
@@ -57,6 +59,7 @@ pub fn call_low_level(
         OPCODE::MerkleMembership => MerkleMembershipGadget::call(evaluator, env, call_expr),
         OPCODE::SchnorrVerify => SchnorrVerifyGadget::call(evaluator, env, call_expr),
         OPCODE::Blake2s => Blake2sGadget::call(evaluator, env, call_expr),
+        OPCODE::Keccak256 => Keccak256Gadget::call(evaluator, env, call_expr),
         OPCODE::Pedersen => PedersenGadget::call(evaluator, env, call_expr),
         OPCODE::EcdsaSecp256k1 => EcdsaSecp256k1Gadget::call(evaluator, env, call_expr),
         OPCODE::HashToField => HashToFieldGadget::call(evaluator, env, call_expr),
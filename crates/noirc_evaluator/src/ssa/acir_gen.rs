@@ -17,13 +17,63 @@ use crate::Evaluator;
 use crate::Gate;
 use crate::RuntimeErrorKind;
 use acvm::acir::circuit::gate::{Directive, GadgetCall, GadgetInput};
+use acvm::acir::circuit::hint::{HintOp, HintProgram, Register};
 use acvm::acir::native_types::{Expression, Linear, Witness};
+use acvm::pwg::sorting::network_size;
+use noirc_errors::Location;
+use noirc_frontend::monomorphisation::ast::FuncId;
 use num_bigint::BigUint;
 
 #[derive(Default)]
 pub struct Acir {
     pub arith_cache: HashMap<NodeId, InternalVar>,
     pub memory_map: HashMap<u32, InternalVar>, //maps memory adress to expression
+    memory_traces: HashMap<ArrayId, MemoryTrace>,
+}
+
+//Accesses to an array at witness indices, which are checked all at once by a permutation argument
+//when the trace is closed
+#[derive(Default)]
+struct MemoryTrace {
+    initial: Vec<Expression>,
+    accesses: Vec<MemoryAccess>,
+}
+
+struct MemoryAccess {
+    index: Expression,
+    value: Expression,
+    is_write: bool,
+}
+
+impl MemoryTrace {
+    //Builds a hint program which replays the writes of the trace and then reads the array at the given indices
+    fn replay(&self, reads: &[Expression]) -> (Vec<Expression>, HintProgram) {
+        let mut inputs = self.initial.clone();
+        let mut instructions = Vec::new();
+        for access in self.accesses.iter().filter(|access| access.is_write) {
+            let index = Register(inputs.len() as u32);
+            inputs.push(access.index.clone());
+            inputs.push(access.value.clone());
+            let source = Register(index.0 + 1);
+            instructions.push(HintOp::Store { base: Register(0), index, source });
+        }
+        let first_read = inputs.len() as u32;
+        inputs.extend_from_slice(reads);
+        let num_inputs = inputs.len() as u32;
+        let outputs: Vec<Register> =
+            (0..reads.len() as u32).map(|i| Register(num_inputs + i)).collect();
+        for (i, destination) in outputs.iter().enumerate() {
+            let index = Register(first_read + i as u32);
+            instructions.push(HintOp::Load { destination: *destination, base: Register(0), index });
+        }
+        let program = HintProgram {
+            num_registers: num_inputs + outputs.len() as u32,
+            inputs: (0..num_inputs).map(Register).collect(),
+            outputs,
+            instructions,
+        };
+        (inputs, program)
+    }
 }
 
 #[derive(Default, Clone, Debug)]
@@ -126,10 +176,13 @@ impl Acir {
 
         let mut output = match &ins.operation {
             Operation::Binary(binary) => self.evaluate_binary(binary, ins.res_type, evaluator, ctx),
-            Operation::Constrain(value, ..) => {
+            Operation::Constrain(value, location, message) => {
                 let value = self.substitute(*value, evaluator, ctx);
                 let subtract = subtract(&Expression::one(), FieldElement::one(), &value.expression);
+                let first_gate = evaluator.gates.len();
                 evaluator.gates.push(Gate::Arithmetic(subtract));
+                let message = message.clone().unwrap_or_else(|| "assertion failed".to_string());
+                evaluator.add_assertion(first_gate, message, *location);
                 value
             }
             Operation::Not(value) => {
@@ -146,7 +199,16 @@ impl Acir {
                 )
                 .into()
             }
-            Operation::Cast(value) => self.substitute(*value, evaluator, ctx),
+            Operation::Cast(value) => {
+                let value_type = ctx.get_object_type(*value);
+                let v = self.substitute(*value, evaluator, ctx);
+                match value_type {
+                    ObjectType::Signed(bit_size) if ins.res_type.bits() > bit_size => {
+                        sign_extend(&v, bit_size, ins.res_type, evaluator).into()
+                    }
+                    _ => v,
+                }
+            }
             i @ Operation::Jne(..)
             | i @ Operation::Jeq(..)
             | i @ Operation::Jmp(_)
@@ -162,6 +224,20 @@ impl Acir {
                 let v = self.evaluate_opcode(ins.id, *opcode, args, ins.res_type, ctx, evaluator);
                 InternalVar::from(v)
             }
+            Operation::Hint(func_id, args) => {
+                let v = self.evaluate_hint(*func_id, args, ins.res_type, ctx, evaluator);
+                InternalVar::from(v)
+            }
+            Operation::Log { format, arguments, predicate } => {
+                let inputs = self.flatten_inputs(arguments, ctx, evaluator);
+                let predicate = match predicate {
+                    Some(predicate) => self.substitute(*predicate, evaluator, ctx).expression,
+                    None => Expression::one(),
+                };
+                let format = format.clone();
+                evaluator.gates.push(Gate::Directive(Directive::Log { format, inputs, predicate }));
+                InternalVar::default()
+            }
             Operation::Call { .. } => unreachable!("call instruction should have been inlined"),
             Operation::Return(_) => todo!(), //return from main
             Operation::Cond { condition, val_true: lhs, val_false: rhs } => {
@@ -181,9 +257,13 @@ impl Acir {
                 //retrieves the value from the map if address is known at compile time:
                 //address = l_c and should be constant
                 let index = self.substitute(*index, evaluator, ctx);
-                if let Some(index) = index.to_const() {
+                let mem_array = &ctx.mem[*array_id];
+                if self.memory_traces.contains_key(array_id)
+                    || (index.to_const().is_none() && mem_array.len >= mem::PERMUTATION_THRESHOLD)
+                {
+                    self.trace_read(mem_array, index.expression, evaluator).into()
+                } else if let Some(index) = index.to_const() {
                     let idx = mem::Memory::as_u32(index);
-                    let mem_array = &ctx.mem[*array_id];
                     let absolute_adr = mem_array.absolute_adr(idx);
                     if self.memory_map.contains_key(&absolute_adr) {
                         InternalVar::from(self.memory_map[&absolute_adr].expression.clone())
@@ -197,7 +277,15 @@ impl Acir {
                         }
                     }
                 } else {
-                    unimplemented!("dynamic arrays are not implemented yet");
+                    //multiplexes the array values with the selectors (index==i)
+                    let selectors = index_selectors(&index, mem_array.len, evaluator);
+                    let values = self.load_array(mem_array, false, evaluator);
+                    let mut result = Expression::default();
+                    for (selector, value) in selectors.iter().zip(values) {
+                        let term = mul_with_witness(evaluator, selector, &value.expression);
+                        result = add(&result, FieldElement::one(), &term);
+                    }
+                    result.into()
                 }
             }
 
@@ -205,16 +293,34 @@ impl Acir {
                 //maps the address to the rhs if address is known at compile time
                 let index = self.substitute(*index, evaluator, ctx);
                 let value = self.substitute(*value, evaluator, ctx);
+                let mem_array = &ctx.mem[*array_id];
 
-                if let Some(index) = index.to_const() {
+                if self.memory_traces.contains_key(array_id)
+                    || (index.to_const().is_none() && mem_array.len >= mem::PERMUTATION_THRESHOLD)
+                {
+                    self.trace_write(mem_array, index.expression, value.expression, evaluator);
+                } else if let Some(index) = index.to_const() {
                     let idx = mem::Memory::as_u32(index);
-                    let absolute_adr = ctx.mem[*array_id].absolute_adr(idx);
+                    let absolute_adr = mem_array.absolute_adr(idx);
                     self.memory_map.insert(absolute_adr, value);
-                    //we do not generate constraint, so no output.
-                    InternalVar::default()
                 } else {
-                    todo!("dynamic arrays are not implemented yet");
+                    //every element becomes v_i + (index==i)*(value-v_i)
+                    let selectors = index_selectors(&index, mem_array.len, evaluator);
+                    let values = self.load_array(mem_array, false, evaluator);
+                    for (i, (selector, old)) in selectors.iter().zip(values).enumerate() {
+                        let diff =
+                            subtract(&value.expression, FieldElement::one(), &old.expression);
+                        let new_value = add(
+                            &old.expression,
+                            FieldElement::one(),
+                            &mul_with_witness(evaluator, selector, &diff),
+                        );
+                        let (_, w) = evaluator.create_intermediate_variable(new_value);
+                        self.memory_map.insert(mem_array.absolute_adr(i as u32), w.into());
+                    }
                 }
+                //we do not generate constraint, so no output.
+                InternalVar::default()
             }
         };
         output.id = Some(ins.id);
@@ -232,10 +338,15 @@ impl Acir {
         let r_c = self.substitute(binary.rhs, evaluator, ctx);
 
         match &binary.operator {
-            BinaryOp::Add | BinaryOp::SafeAdd => {
+            BinaryOp::Add => {
                 InternalVar::from(add(&l_c.expression, FieldElement::one(), &r_c.expression))
             }
-            BinaryOp::Sub { max_rhs_value } | BinaryOp::SafeSub { max_rhs_value } => {
+            BinaryOp::SafeAdd { location }
+            | BinaryOp::SafeSub { location, .. }
+            | BinaryOp::SafeMul { location } => {
+                evaluate_checked(&binary.operator, &l_c, &r_c, res_type, *location, evaluator)
+            }
+            BinaryOp::Sub { max_rhs_value } => {
                 if res_type == node::ObjectType::NativeField {
                     InternalVar::from(subtract(
                         &l_c.expression,
@@ -270,19 +381,21 @@ impl Acir {
                     sub_var
                 }
             }
-            BinaryOp::Mul | BinaryOp::SafeMul => {
-                InternalVar::from(evaluate_mul(&l_c, &r_c, evaluator))
-            }
+            BinaryOp::Mul => InternalVar::from(evaluate_mul(&l_c, &r_c, evaluator)),
             BinaryOp::Udiv => {
-                let (q_wit, _) = evaluate_udiv(&l_c, &r_c, evaluator);
+                let (q_wit, _) = evaluate_udiv(&l_c, &r_c, res_type.bits(), evaluator);
                 InternalVar::from(q_wit)
             }
-            BinaryOp::Sdiv => InternalVar::from(evaluate_sdiv(&l_c, &r_c, evaluator).0),
+            BinaryOp::Sdiv { .. } => {
+                InternalVar::from(evaluate_sdiv(&l_c, &r_c, res_type.bits(), evaluator).0)
+            }
             BinaryOp::Urem => {
-                let (_, r_wit) = evaluate_udiv(&l_c, &r_c, evaluator);
+                let (_, r_wit) = evaluate_udiv(&l_c, &r_c, res_type.bits(), evaluator);
                 InternalVar::from(r_wit)
             }
-            BinaryOp::Srem => InternalVar::from(evaluate_sdiv(&l_c, &r_c, evaluator).1),
+            BinaryOp::Srem { .. } => {
+                InternalVar::from(evaluate_sdiv(&l_c, &r_c, res_type.bits(), evaluator).1)
+            }
             BinaryOp::Div => InternalVar::from(mul(
                 &l_c.expression,
                 &from_witness(evaluate_inverse(r_c, evaluator)),
@@ -321,16 +434,44 @@ impl Acir {
                 }
                 .into()
             }
-            BinaryOp::Lt => unimplemented!(
-                "Field comparison is not implemented yet, try to cast arguments to integer type"
-            ),
-            BinaryOp::Lte => unimplemented!(
-                "Field comparison is not implemented yet, try to cast arguments to integer type"
-            ),
+            BinaryOp::Lt => {
+                let max_bits = field_cmp_bound(ctx, binary.lhs, binary.rhs);
+                evaluate_field_cmp(&l_c, &r_c, max_bits, evaluator).into()
+            }
+            BinaryOp::Lte => {
+                let max_bits = field_cmp_bound(ctx, binary.lhs, binary.rhs);
+                let w = evaluate_field_cmp(&r_c, &l_c, max_bits, evaluator);
+                Expression {
+                    mul_terms: Vec::new(),
+                    linear_combinations: vec![(-FieldElement::one(), w)],
+                    q_c: FieldElement::one(),
+                }
+                .into()
+            }
+            BinaryOp::BoundedLt { bit_size, .. } => {
+                //The operands are range checked so that the integer comparison is sound,
+                //constant operands have been checked when the instruction was evaluated
+                for operand in [&l_c, &r_c] {
+                    if operand.to_const().is_none() {
+                        let witness = generate_witness(operand, evaluator);
+                        range_constraint(witness, *bit_size, evaluator).unwrap_or_else(|err| {
+                            dbg!(err);
+                        });
+                    }
+                }
+                evaluate_field_cmp(&l_c, &r_c, Some(*bit_size), evaluator).into()
+            }
             BinaryOp::And => InternalVar::from(evaluate_and(l_c, r_c, res_type.bits(), evaluator)),
             BinaryOp::Or => InternalVar::from(evaluate_or(l_c, r_c, res_type.bits(), evaluator)),
             BinaryOp::Xor => InternalVar::from(evaluate_xor(l_c, r_c, res_type.bits(), evaluator)),
-            BinaryOp::Shl | BinaryOp::Shr => unreachable!(),
+            BinaryOp::Shl => {
+                let r_size = ctx[binary.rhs].size_in_bits();
+                InternalVar::from(evaluate_shl(&l_c, &r_c, res_type.bits(), r_size, evaluator))
+            }
+            BinaryOp::Shr => {
+                let r_size = ctx[binary.rhs].size_in_bits();
+                InternalVar::from(evaluate_shr(&l_c, &r_c, res_type, r_size, evaluator))
+            }
             i @ BinaryOp::Assign => unreachable!("Invalid Instruction: {:?}", i),
         }
     }
@@ -349,6 +490,7 @@ impl Acir {
         create_witness: bool,
         evaluator: &mut Evaluator,
     ) -> Vec<InternalVar> {
+        self.close_trace(array, true, evaluator);
         (0..array.len)
             .map(|i| {
                 let address = array.adr + i;
@@ -367,14 +509,160 @@ impl Acir {
     }
 
     //Map the outputs into the array
-    fn map_array(&mut self, a: ArrayId, outputs: &[Witness], ctx: &SsaContext) {
-        let adr = ctx.mem[a].adr;
+    fn map_array(
+        &mut self,
+        a: ArrayId,
+        outputs: &[Witness],
+        ctx: &SsaContext,
+        evaluator: &mut Evaluator,
+    ) {
+        let array = &ctx.mem[a];
+        self.close_trace(array, (outputs.len() as u32) < array.len, evaluator);
+        let adr = array.adr;
         for i in outputs.iter().enumerate() {
             let var = InternalVar::from(*i.1);
             self.memory_map.insert(adr + i.0 as u32, var);
         }
     }
 
+    fn trace_of(&mut self, array: &MemArray, evaluator: &mut Evaluator) -> &mut MemoryTrace {
+        if !self.memory_traces.contains_key(&array.id) {
+            let initial = self
+                .load_array(array, false, evaluator)
+                .into_iter()
+                .map(|value| value.expression)
+                .collect();
+            self.memory_traces.insert(array.id, MemoryTrace { initial, accesses: Vec::new() });
+        }
+        self.memory_traces.get_mut(&array.id).unwrap()
+    }
+
+    //Reads array[index] from the memory trace of the array; the value is computed by a hint and
+    //will be constrained by the permutation argument
+    fn trace_read(
+        &mut self,
+        array: &MemArray,
+        index: Expression,
+        evaluator: &mut Evaluator,
+    ) -> Witness {
+        bound_check_index(&index, array.len, evaluator);
+        let value = evaluator.add_witness_to_cs();
+        let trace = self.trace_of(array, evaluator);
+        let (inputs, program) = trace.replay(&[index.clone()]);
+        trace.accesses.push(MemoryAccess { index, value: from_witness(value), is_write: false });
+        evaluator.gates.push(Gate::Directive(Directive::Hint {
+            inputs,
+            outputs: vec![value],
+            program,
+        }));
+        value
+    }
+
+    fn trace_write(
+        &mut self,
+        array: &MemArray,
+        index: Expression,
+        value: Expression,
+        evaluator: &mut Evaluator,
+    ) {
+        bound_check_index(&index, array.len, evaluator);
+        let trace = self.trace_of(array, evaluator);
+        trace.accesses.push(MemoryAccess { index, value, is_write: true });
+    }
+
+    //Checks the memory trace of the array, if any, with a permutation argument:
+    //the accesses, preceded by writes of the initial values, are sorted by (index, counter) through a Benes network
+    //and every read must return the value of the previous row, which has the same index.
+    //If materialize is true, the final values of the array are read from the trace into the memory map.
+    fn close_trace(&mut self, array: &MemArray, materialize: bool, evaluator: &mut Evaluator) {
+        let mut trace = match self.memory_traces.remove(&array.id) {
+            Some(trace) => trace,
+            None => return,
+        };
+        if materialize {
+            let indices: Vec<Expression> = (0..array.len)
+                .map(|i| Expression::from_field(FieldElement::from(i as i128)))
+                .collect();
+            let (inputs, program) = trace.replay(&indices);
+            let values: Vec<Witness> =
+                indices.iter().map(|_| evaluator.add_witness_to_cs()).collect();
+            evaluator.gates.push(Gate::Directive(Directive::Hint {
+                inputs,
+                outputs: values.clone(),
+                program,
+            }));
+            for (i, (index, value)) in indices.into_iter().zip(values).enumerate() {
+                let value = from_witness(value);
+                trace.accesses.push(MemoryAccess { index, value: value.clone(), is_write: false });
+                self.memory_map.insert(array.absolute_adr(i as u32), value.into());
+            }
+        }
+
+        //rows are (index, counter, value, is_write), padded to a power of two with writes at index len
+        let field = |x: usize| Expression::from_field(FieldElement::from(x as i128));
+        let mut rows: Vec<Vec<Expression>> = trace
+            .initial
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| vec![field(i), field(0), value, field(1)])
+            .collect();
+        for (counter, access) in trace.accesses.into_iter().enumerate() {
+            let is_write = field(access.is_write as usize);
+            rows.push(vec![access.index, field(counter + 1), access.value, is_write]);
+        }
+        let num_accesses = rows.len() - array.len as usize;
+        let n = rows.len().next_power_of_two().max(2);
+        for counter in num_accesses + 1..num_accesses + 1 + n - rows.len() {
+            rows.push(vec![field(array.len as usize), field(counter), field(0), field(1)]);
+        }
+        for row in rows.iter_mut() {
+            for value in row.iter_mut() {
+                if !value.is_linear() {
+                    let (_, w) = evaluator.create_intermediate_variable(value.clone());
+                    *value = from_witness(w);
+                }
+            }
+        }
+
+        let bits: Vec<Witness> =
+            (0..network_size(n)).map(|_| evaluator.add_witness_to_cs()).collect();
+        evaluator.gates.push(Gate::Directive(Directive::PermutationSort {
+            inputs: rows.clone(),
+            bits: bits.clone(),
+            sort_by: vec![0, 1],
+        }));
+        let sorted = permutation_network(rows, &bits, evaluator);
+
+        //counters are smaller than n=2^counter_bits and indices are at most len
+        let counter_bits = n.trailing_zeros();
+        let key_bits = counter_bits + 32 - array.len.leading_zeros();
+        let shift = FieldElement::from(2_i128).pow(&FieldElement::from(counter_bits as i128));
+        let key = |row: &[Expression]| add(&row[1], shift, &row[0]);
+        evaluator.gates.push(Gate::Arithmetic(subtract(
+            &sorted[0][3],
+            FieldElement::one(),
+            &Expression::one(),
+        )));
+        for t in 1..n {
+            let (prev, row) = (&sorted[t - 1], &sorted[t]);
+            bound_check(&key(prev).into(), &key(row).into(), true, key_bits, evaluator);
+            let is_read = subtract(&Expression::one(), FieldElement::one(), &row[3]);
+            for column in [0, 2] {
+                let diff = subtract(&row[column], FieldElement::one(), &prev[column]);
+                evaluator.gates.push(Gate::Arithmetic(mul(&is_read, &diff)));
+            }
+        }
+    }
+
+    //Checks the memory traces which are still open
+    pub fn close_traces(&mut self, ctx: &SsaContext, evaluator: &mut Evaluator) {
+        let mut arrays: Vec<ArrayId> = self.memory_traces.keys().copied().collect();
+        arrays.sort();
+        for a in arrays {
+            self.close_trace(&ctx.mem[a], false, evaluator);
+        }
+    }
+
     pub fn evaluate_neq(
         &mut self,
         lhs: NodeId,
@@ -464,7 +752,7 @@ impl Acir {
 
     //Transform the arguments of intrinsic functions into witnesses
     pub fn prepare_inputs(
-        &self,
+        &mut self,
         args: &[NodeId],
         cfg: &SsaContext,
         evaluator: &mut Evaluator,
@@ -478,6 +766,7 @@ impl Acir {
                     match l_obj.get_type() {
                         node::ObjectType::Pointer(a) => {
                             let array = &cfg.mem[a];
+                            self.close_trace(array, true, evaluator);
                             let num_bits = array.element_type.bits();
                             for i in 0..array.len {
                                 let address = array.adr + i;
@@ -542,7 +831,7 @@ impl Acir {
                 let l_c = self.substitute(args[0], evaluator, ctx);
                 outputs = split(&l_c, bit_size, evaluator);
                 if let node::ObjectType::Pointer(a) = res_type {
-                    self.map_array(a, &outputs, ctx);
+                    self.map_array(a, &outputs, ctx, evaluator);
                 }
             }
             _ => {
@@ -567,6 +856,72 @@ impl Acir {
         }
     }
 
+    //Runs the hint program of an unconstrained function to compute its results
+    //The results are only constrained to fit into their type, the caller has to constrain them further
+    pub fn evaluate_hint(
+        &mut self,
+        func_id: FuncId,
+        args: &[NodeId],
+        res_type: ObjectType,
+        ctx: &SsaContext,
+        evaluator: &mut Evaluator,
+    ) -> Expression {
+        let inputs = self.flatten_inputs(args, ctx, evaluator);
+        let (len, element_type) = match res_type {
+            ObjectType::Pointer(a) => (ctx.mem[a].len, ctx.mem[a].element_type),
+            _ => (1, res_type),
+        };
+        let outputs: Vec<Witness> = (0..len).map(|_| evaluator.add_witness_to_cs()).collect();
+        let program = ctx.hints[&func_id].clone();
+        evaluator.gates.push(Gate::Directive(Directive::Hint {
+            inputs,
+            outputs: outputs.clone(),
+            program,
+        }));
+
+        if element_type != ObjectType::NativeField {
+            for w in &outputs {
+                range_constraint(*w, element_type.bits(), evaluator).unwrap_or_else(|err| {
+                    dbg!(err);
+                });
+            }
+        }
+
+        if let ObjectType::Pointer(a) = res_type {
+            self.map_array(a, &outputs, ctx, evaluator);
+            Expression::default()
+        } else {
+            from_witness(outputs[0])
+        }
+    }
+
+    //Returns the values of the arguments, where an array gives the values of its elements
+    fn flatten_inputs(
+        &mut self,
+        args: &[NodeId],
+        ctx: &SsaContext,
+        evaluator: &mut Evaluator,
+    ) -> Vec<Expression> {
+        let mut inputs = Vec::new();
+        for arg in args {
+            if let Some(a) = Memory::deref(ctx, *arg) {
+                let array = &ctx.mem[a];
+                self.close_trace(array, true, evaluator);
+                for i in 0..array.len {
+                    let address = array.adr + i;
+                    if let Some(value) = self.memory_map.get(&address) {
+                        inputs.push(value.expression.clone());
+                    } else {
+                        inputs.push(array.values[i as usize].expression.clone());
+                    }
+                }
+            } else {
+                inputs.push(self.substitute(*arg, evaluator, ctx).expression);
+            }
+        }
+        inputs
+    }
+
     pub fn prepare_outputs(
         &mut self,
         pointer: NodeId,
@@ -583,18 +938,358 @@ impl Acir {
 
         let l_obj = ctx.try_get_node(pointer).unwrap();
         if let node::ObjectType::Pointer(a) = l_obj.get_type() {
-            self.map_array(a, &outputs, ctx);
+            self.map_array(a, &outputs, ctx, evaluator);
         }
         outputs
     }
 }
 
+//Returns the result of a checked addition, subtraction or multiplication and constrains the exact result
+//to fit in the type, so that solving fails on overflow.
+//The result of a signed operation is offset by 2^bit_size in order to be positive; it is truncated later like
+//the result of an unchecked operation
+fn evaluate_checked(
+    operator: &BinaryOp,
+    lhs: &InternalVar,
+    rhs: &InternalVar,
+    res_type: ObjectType,
+    location: Location,
+    evaluator: &mut Evaluator,
+) -> InternalVar {
+    let bit_size = res_type.bits();
+    let signed = matches!(res_type, ObjectType::Signed(_));
+    //the values of signed integers are lhs - 2^bit_size*sign
+    let signed_value = |x: &InternalVar, evaluator: &mut Evaluator| {
+        let sign = evaluate_sign_bit(x, bit_size, evaluator);
+        let offset = FieldElement::from(2_i128).pow(&FieldElement::from(bit_size as i128));
+        InternalVar::from(add(&x.expression, -offset, &sign))
+    };
+    let (l_value, r_value) = if signed {
+        (signed_value(lhs, evaluator), signed_value(rhs, evaluator))
+    } else {
+        (lhs.clone(), rhs.clone())
+    };
+    let (mut exact, action) = match operator {
+        BinaryOp::SafeAdd { .. } => {
+            (add(&l_value.expression, FieldElement::one(), &r_value.expression), "add")
+        }
+        BinaryOp::SafeSub { .. } => {
+            (subtract(&l_value.expression, FieldElement::one(), &r_value.expression), "subtract")
+        }
+        BinaryOp::SafeMul { .. } => (evaluate_mul(&l_value, &r_value, evaluator), "multiply"),
+        _ => unreachable!("{:?} is not a checked operation", operator),
+    };
+    //signed results must be in [-2^(bit_size-1), 2^(bit_size-1)), so we check exact+2^(bit_size-1) instead
+    let half = if signed {
+        FieldElement::from(2_i128).pow(&FieldElement::from(bit_size as i128 - 1))
+    } else {
+        FieldElement::zero()
+    };
+    exact.q_c += half;
+    let (_, result) = evaluator.create_intermediate_variable(exact);
+    let first_gate = evaluator.gates.len();
+    range_constraint(result, bit_size, evaluator).unwrap_or_else(|err| {
+        dbg!(err);
+    });
+    evaluator.add_assertion(first_gate, format!("attempt to {} with overflow", action), location);
+    if signed {
+        let mut output = from_witness(result);
+        output.q_c += half;
+        InternalVar::from(output)
+    } else {
+        InternalVar::from(result)
+    }
+}
+
+//Returns the sign bit of lhs, a bit_size-bits signed integer in two's complement representation
+pub fn evaluate_sign_bit(
+    lhs: &InternalVar,
+    bit_size: u32,
+    evaluator: &mut Evaluator,
+) -> Expression {
+    if let Some(a_c) = lhs.to_const() {
+        let a_big = BigUint::from_bytes_be(&a_c.to_bytes());
+        let sign = (a_big >> (bit_size - 1)) % BigUint::from(2_u32);
+        return Expression::from_field(FieldElement::from_be_bytes_reduce(&sign.to_bytes_be()));
+    }
+    //a = b+2^(bit_size-1)*s, where s is the sign bit
+    let a_witness = generate_witness(lhs, evaluator);
+    let b_witness = evaluator.add_witness_to_cs();
+    let s_witness = evaluator.add_witness_to_cs();
+    evaluator.gates.push(Gate::Directive(Directive::Truncate {
+        a: a_witness,
+        b: b_witness,
+        c: s_witness,
+        bit_size: bit_size - 1,
+    }));
+    range_constraint(b_witness, bit_size - 1, evaluator).unwrap_or_else(|err| {
+        dbg!(err);
+    });
+    range_constraint(s_witness, 1, evaluator).unwrap_or_else(|err| {
+        dbg!(err);
+    });
+    let f = FieldElement::from(1_u128 << (bit_size - 1));
+    let res = add(&from_witness(b_witness), f, &from_witness(s_witness));
+    evaluator.gates.push(Gate::Arithmetic(subtract(
+        &res,
+        FieldElement::one(),
+        &from_witness(a_witness),
+    )));
+    from_witness(s_witness)
+}
+
+//Returns x if sign is 0, and 2^bit_size-x if sign is 1, i.e the absolute value of x when sign is the sign bit of x
+//n.b. 2^bit_size is returned instead of 0 when x is null and sign is 1
+fn conditional_negate(
+    x: &Expression,
+    sign: &Expression,
+    bit_size: u32,
+    evaluator: &mut Evaluator,
+) -> Expression {
+    let mut neg = x * &FieldElement::from(-2_i128);
+    neg.q_c += FieldElement::from(1_u128 << bit_size);
+    let diff =
+        if is_const(sign) { &neg * &sign.q_c } else { mul_with_witness(evaluator, sign, &neg) };
+    add(x, FieldElement::one(), &diff)
+}
+
+//Returns the quotient and the remainder of the signed division of lhs by rhs, the quotient is rounded toward zero
+//and the remainder has the sign of lhs. The inputs are bit_size-bits integers in two's complement representation.
 pub fn evaluate_sdiv(
-    _lhs: &InternalVar,
-    _rhs: &InternalVar,
-    _evaluator: &mut Evaluator,
+    lhs: &InternalVar,
+    rhs: &InternalVar,
+    bit_size: u32,
+    evaluator: &mut Evaluator,
 ) -> (Expression, Expression) {
-    todo!();
+    //we divide the absolute values and then fix the signs of the results
+    let l_sign = evaluate_sign_bit(lhs, bit_size, evaluator);
+    let r_sign = evaluate_sign_bit(rhs, bit_size, evaluator);
+    let l_abs = conditional_negate(&lhs.expression, &l_sign, bit_size, evaluator);
+    let r_abs = conditional_negate(&rhs.expression, &r_sign, bit_size, evaluator);
+    //the divisor is multiplied by the quotient so it must be linear
+    let r_abs = if is_const(&r_abs) {
+        InternalVar::from(r_abs)
+    } else {
+        InternalVar::from(generate_witness(&InternalVar::from(r_abs), evaluator))
+    };
+    let (q_witness, r_witness) =
+        evaluate_udiv(&InternalVar::from(l_abs), &r_abs, bit_size, evaluator);
+
+    //the quotient is negative iff the signs are different: q_sign = l_sign xor r_sign
+    let l_sign_r_sign = evaluate_mul(&l_sign.clone().into(), &r_sign.clone().into(), evaluator);
+    let mut q_sign = add(&l_sign, FieldElement::one(), &r_sign);
+    q_sign = add(&q_sign, FieldElement::from(-2_i128), &l_sign_r_sign);
+    let q = conditional_negate(&from_witness(q_witness), &q_sign, bit_size, evaluator);
+    let r = conditional_negate(&from_witness(r_witness), &l_sign, bit_size, evaluator);
+    (q, r)
+}
+
+//Returns 2^min(s, limit), where s is a s_bit_size-bits integer
+//2^s is computed from the bits of s as the product of the 2^(2^i) for which the i-th bit of s is set
+fn evaluate_pow2(
+    s: &InternalVar,
+    s_bit_size: u32,
+    limit: u32,
+    evaluator: &mut Evaluator,
+) -> Expression {
+    let two = FieldElement::from(2_i128);
+    let p_limit = two.pow(&FieldElement::from(limit as i128));
+    if let Some(s_c) = s.to_const() {
+        if s_c >= FieldElement::from(limit as i128) {
+            return Expression::from_field(p_limit);
+        }
+        return Expression::from_field(two.pow(&s_c));
+    }
+    //only the bits required to represent limit-1 are used, the other ones are null when s < limit
+    let used_bits = 32 - (limit.max(1) - 1).leading_zeros();
+    let s_bits = split(s, s_bit_size, evaluator);
+    let mut pow = Expression::one();
+    let mut table = two;
+    for bit in s_bits.iter().take(used_bits as usize) {
+        let factor = add(&Expression::one(), table - FieldElement::one(), &from_witness(*bit));
+        pow = mul_with_witness(evaluator, &pow, &factor);
+        table = table * table;
+    }
+    //the result is pow if s < limit, and 2^limit else: in_range*(pow-2^limit)+2^limit
+    let limit_var = InternalVar::from(FieldElement::from(limit as i128));
+    let cmp_size = u32::max(s_bit_size, 32 - limit.leading_zeros());
+    let in_range = evaluate_cmp(s, &limit_var, cmp_size, false, evaluator);
+    pow.q_c -= p_limit;
+    let mut result = mul_with_witness(evaluator, &from_witness(in_range), &pow);
+    result.q_c += p_limit;
+    result
+}
+
+//Returns lhs << rhs, the result is 0 when rhs is greater or equal to bit_size
+pub fn evaluate_shl(
+    lhs: &InternalVar,
+    rhs: &InternalVar,
+    bit_size: u32,
+    r_bit_size: u32,
+    evaluator: &mut Evaluator,
+) -> Expression {
+    //lhs*2^rhs mod 2^bit_size, with lhs*2^rhs < 2^(2*bit_size)
+    let pow = evaluate_pow2(rhs, r_bit_size, bit_size, evaluator);
+    let product = InternalVar::from(evaluate_mul(lhs, &InternalVar::from(pow), evaluator));
+    evaluate_truncate(product, bit_size, 2 * bit_size, evaluator).expression
+}
+
+//Returns lhs >> rhs. Right shifts of signed integers are arithmetic shifts; the sign bit is propagated
+//so shifting by the bit size or more gives 0 or -1, while it gives 0 for unsigned integers.
+pub fn evaluate_shr(
+    lhs: &InternalVar,
+    rhs: &InternalVar,
+    res_type: ObjectType,
+    r_bit_size: u32,
+    evaluator: &mut Evaluator,
+) -> Expression {
+    let bit_size = res_type.bits();
+    let signed = matches!(res_type, ObjectType::Signed(_));
+    //an arithmetic shift by bit_size-1 already gives 0 or -1
+    let limit = if signed { bit_size - 1 } else { bit_size };
+    let pow = evaluate_pow2(rhs, r_bit_size, limit, evaluator);
+    //the divisor must be linear
+    let pow = if is_const(&pow) {
+        InternalVar::from(pow)
+    } else {
+        InternalVar::from(generate_witness(&InternalVar::from(pow), evaluator))
+    };
+    let (q_witness, _) = evaluate_udiv(lhs, &pow, bit_size + 1, evaluator);
+    if !signed {
+        return from_witness(q_witness);
+    }
+    //the unsigned shift is completed with 1s if lhs is negative: (lhs udiv 2^rhs) + sign*(2^bit_size-2^bit_size/2^rhs)
+    let sign = evaluate_sign_bit(lhs, bit_size, evaluator);
+    let p_bit_size = FieldElement::from(2_i128).pow(&FieldElement::from(bit_size as i128));
+    let mut ones = match pow.to_const() {
+        Some(pow) => Expression::from_field(p_bit_size / pow),
+        None => {
+            let p_var = InternalVar::from(p_bit_size);
+            from_witness(evaluate_udiv(&p_var, &pow, bit_size + 1, evaluator).0)
+        }
+    };
+    ones = &ones * &(-FieldElement::one());
+    ones.q_c += p_bit_size;
+    add(
+        &from_witness(q_witness),
+        FieldElement::one(),
+        &evaluate_mul(&sign.into(), &ones.into(), evaluator),
+    )
+}
+
+//Sign extension of the bit_size-bits signed integer x into the type res_type
+pub fn sign_extend(
+    x: &InternalVar,
+    bit_size: u32,
+    res_type: ObjectType,
+    evaluator: &mut Evaluator,
+) -> Expression {
+    let sign = evaluate_sign_bit(x, bit_size, evaluator);
+    //x - 2^bit_size*sign is the value of x, which we represent in res_type
+    let mut k = -FieldElement::from(1_u128 << bit_size);
+    if res_type != ObjectType::NativeField {
+        k += FieldElement::from(1_u128 << res_type.bits());
+    }
+    add(&x.expression, k, &sign)
+}
+
+//Returns the selectors (index==i) for i<len, and constrains the index to match one of them
+fn index_selectors(index: &InternalVar, len: u32, evaluator: &mut Evaluator) -> Vec<Expression> {
+    let mut selectors = Vec::with_capacity(len as usize);
+    let mut sum = Expression::default();
+    for i in 0..len {
+        let mut diff = index.expression.clone();
+        diff.q_c -= FieldElement::from(i as i128);
+        let diff_witness = generate_witness(&diff.into(), evaluator);
+        let is_different = evaluate_zero_equality(&diff_witness.into(), evaluator);
+        let selector =
+            subtract(&Expression::one(), FieldElement::one(), &from_witness(is_different));
+        sum = add(&sum, FieldElement::one(), &selector);
+        selectors.push(selector);
+    }
+    evaluator.gates.push(Gate::Arithmetic(subtract(&sum, FieldElement::one(), &Expression::one())));
+    selectors
+}
+
+//Constrains the index of an array access to be less than the array length
+fn bound_check_index(index: &Expression, len: u32, evaluator: &mut Evaluator) {
+    if is_const(index) {
+        if index.q_c >= FieldElement::from(len as i128) {
+            unreachable!("Could not find value at index {}", index.q_c);
+        }
+        return;
+    }
+    //The comparison with len only holds for indices of at most bits bits, which field indices may exceed
+    let bits = 32 - len.leading_zeros();
+    let index = InternalVar::from(index.clone());
+    let index_witness = generate_witness(&index, evaluator);
+    range_constraint(index_witness, bits, evaluator).unwrap_or_else(|err| {
+        dbg!(err);
+    });
+    let len = InternalVar::from(FieldElement::from(len as i128));
+    bound_check(&index, &len, true, bits, evaluator);
+}
+
+//A switch of the permutation network, which exchanges the rows a and b when the bit is set
+fn switch(
+    a: &[Expression],
+    b: &[Expression],
+    bit: Witness,
+    evaluator: &mut Evaluator,
+) -> (Vec<Expression>, Vec<Expression>) {
+    evaluator.gates.push(Gate::Arithmetic(boolean(bit)));
+    let mut out_a = Vec::with_capacity(a.len());
+    let mut out_b = Vec::with_capacity(b.len());
+    for (x, y) in a.iter().zip(b) {
+        let diff = subtract(y, FieldElement::one(), x);
+        if is_const(&diff) && diff.q_c.is_zero() {
+            out_a.push(x.clone());
+            out_b.push(y.clone());
+            continue;
+        }
+        //bit*(y-x) moves from y to x
+        let swap = mul(&from_witness(bit), &diff);
+        let (_, w_a) = evaluator.create_intermediate_variable(add(x, FieldElement::one(), &swap));
+        let (_, w_b) =
+            evaluator.create_intermediate_variable(subtract(y, FieldElement::one(), &swap));
+        out_a.push(from_witness(w_a));
+        out_b.push(from_witness(w_b));
+    }
+    (out_a, out_b)
+}
+
+//Routes the rows through the Benes network controlled by the bits, with the layout of acvm::pwg::sorting::route
+fn permutation_network(
+    rows: Vec<Vec<Expression>>,
+    bits: &[Witness],
+    evaluator: &mut Evaluator,
+) -> Vec<Vec<Expression>> {
+    let n = rows.len();
+    if n <= 1 {
+        return rows;
+    }
+    if n == 2 {
+        let (a, b) = switch(&rows[0], &rows[1], bits[0], evaluator);
+        return vec![a, b];
+    }
+    let half = n / 2;
+    let sub_size = network_size(half);
+    let mut upper = Vec::with_capacity(half);
+    let mut lower = Vec::with_capacity(half);
+    for p in 0..half {
+        let (a, b) = switch(&rows[2 * p], &rows[2 * p + 1], bits[p], evaluator);
+        upper.push(a);
+        lower.push(b);
+    }
+    let upper = permutation_network(upper, &bits[half..half + sub_size], evaluator);
+    let lower = permutation_network(lower, &bits[half + sub_size..half + 2 * sub_size], evaluator);
+    let mut result = Vec::with_capacity(n);
+    for q in 0..half {
+        let (a, b) = switch(&upper[q], &lower[q], bits[half + 2 * sub_size + q], evaluator);
+        result.push(a);
+        result.push(b);
+    }
+    result
 }
 
 //Returns 1 if lhs < rhs
@@ -605,16 +1300,134 @@ pub fn evaluate_cmp(
     signed: bool,
     evaluator: &mut Evaluator,
 ) -> Witness {
+    if signed {
+        //x+2^(bit_size-1)-2^bit_size*sign maps signed integers to unsigned ones, preserving the order
+        let to_unsigned = |x: &InternalVar, evaluator: &mut Evaluator| {
+            let sign = evaluate_sign_bit(x, bit_size, evaluator);
+            let mut x_u = add(&x.expression, -FieldElement::from(1_u128 << bit_size), &sign);
+            x_u.q_c += FieldElement::from(1_u128 << (bit_size - 1));
+            InternalVar::from(x_u)
+        };
+        let lhs = to_unsigned(lhs, evaluator);
+        let rhs = to_unsigned(rhs, evaluator);
+        return evaluate_cmp(&lhs, &rhs, bit_size, false, evaluator);
+    }
     //TODO use quad_decomposition gate for barretenberg
     let mut sub_expr = subtract(&lhs.expression, FieldElement::one(), &rhs.expression);
     let two_pow = BigUint::one() << (bit_size + 1);
     sub_expr.q_c += FieldElement::from_be_bytes_reduce(&two_pow.to_bytes_be());
     let bits = split(&sub_expr.into(), bit_size + 2, evaluator);
-    if signed {
-        bits[(bit_size - 1) as usize]
-    } else {
-        bits[(bit_size) as usize]
+    bits[(bit_size) as usize]
+}
+
+//Number of bits of the low limb in the canonical decomposition of a field element
+const FIELD_LOW_BITS: u32 = 128;
+
+//Returns a bit size that bounds both lhs and rhs, when it is known at compile time
+//This is the case for constants and for unsigned integers that have been cast into a field element
+fn field_cmp_bound(ctx: &SsaContext, lhs: NodeId, rhs: NodeId) -> Option<u32> {
+    let bound = |id: NodeId| {
+        if let Some(c) = ctx.get_as_constant(id) {
+            return Some(c.num_bits());
+        }
+        if let Some(Operation::Cast(value)) = ctx.try_get_instruction(id).map(|ins| &ins.operation)
+        {
+            let value_type = ctx.get_object_type(*value);
+            if matches!(value_type, ObjectType::Unsigned(_) | ObjectType::Boolean) {
+                return Some(value_type.bits());
+            }
+        }
+        None
+    };
+    Some(u32::max(bound(lhs)?, bound(rhs)?))
+}
+
+//Returns 1 if lhs < rhs, where lhs and rhs are field elements compared as integers in [0, p)
+//Callers can provide max_bits when both lhs and rhs are known to be less than 2^max_bits, in which case the cheaper
+//integer comparison is used. Otherwise the field elements are canonically decomposed before being compared.
+pub fn evaluate_field_cmp(
+    lhs: &InternalVar,
+    rhs: &InternalVar,
+    max_bits: Option<u32>,
+    evaluator: &mut Evaluator,
+) -> Witness {
+    if let Some(bits) = max_bits {
+        if bits + 2 < FieldElement::max_num_bits() {
+            return evaluate_cmp(lhs, rhs, u32::max(bits, 1), false, evaluator);
+        }
+    }
+    let (l_low, l_high) = canonical_decomposition(lhs, evaluator);
+    let (r_low, r_high) = canonical_decomposition(rhs, evaluator);
+    //lhs < rhs iff l_high < r_high + (l_low < r_low)
+    let borrow = evaluate_cmp(&l_low, &r_low, FIELD_LOW_BITS, false, evaluator);
+    let r_high = add(&r_high.expression, FieldElement::one(), &from_witness(borrow));
+    let high_bits = FieldElement::max_num_bits() - FIELD_LOW_BITS;
+    evaluate_cmp(&l_high, &r_high.into(), high_bits + 1, false, evaluator)
+}
+
+//Decomposes x into low + 2^FIELD_LOW_BITS*high, and constrains the decomposition to be the canonical one,
+//i.e low + 2^FIELD_LOW_BITS*high < p when computed over the integers.
+pub fn canonical_decomposition(
+    x: &InternalVar,
+    evaluator: &mut Evaluator,
+) -> (InternalVar, InternalVar) {
+    let to_limbs = |f: FieldElement| {
+        let f_big = BigUint::from_bytes_be(&f.to_bytes());
+        let low = &f_big % (BigUint::one() << FIELD_LOW_BITS);
+        let high = f_big >> FIELD_LOW_BITS;
+        (
+            FieldElement::from_be_bytes_reduce(&low.to_bytes_be()),
+            FieldElement::from_be_bytes_reduce(&high.to_bytes_be()),
+        )
+    };
+    if let Some(c) = x.to_const() {
+        let (low, high) = to_limbs(c);
+        return (InternalVar::from(low), InternalVar::from(high));
     }
+    let high_bits = FieldElement::max_num_bits() - FIELD_LOW_BITS;
+    let x_witness = generate_witness(x, evaluator);
+    let low_witness = evaluator.add_witness_to_cs();
+    let high_witness = evaluator.add_witness_to_cs();
+    evaluator.gates.push(Gate::Directive(Directive::Truncate {
+        a: x_witness,
+        b: low_witness,
+        c: high_witness,
+        bit_size: FIELD_LOW_BITS,
+    }));
+    range_constraint(low_witness, FIELD_LOW_BITS, evaluator).unwrap_or_else(|err| {
+        dbg!(err);
+    });
+    range_constraint(high_witness, high_bits, evaluator).unwrap_or_else(|err| {
+        dbg!(err);
+    });
+    //x = low + 2^FIELD_LOW_BITS*high
+    let f = FieldElement::from(2_i128).pow(&FieldElement::from(FIELD_LOW_BITS as i128));
+    let res = add(&from_witness(low_witness), f, &from_witness(high_witness));
+    evaluator.gates.push(Gate::Arithmetic(subtract(
+        &res,
+        FieldElement::one(),
+        &from_witness(x_witness),
+    )));
+
+    //low + 2^FIELD_LOW_BITS*high <= p-1: we subtract the limbs from the limbs of p-1, the borrow is 1 iff low > (p-1)_low
+    let (m_low, m_high) = to_limbs(-FieldElement::one());
+    let borrow = evaluate_cmp(
+        &InternalVar::from(m_low),
+        &low_witness.into(),
+        FIELD_LOW_BITS,
+        false,
+        evaluator,
+    );
+    //(p-1)_high - high - borrow must not be negative
+    let mut diff =
+        subtract(&Expression::from_field(m_high), FieldElement::one(), &from_witness(high_witness));
+    diff = subtract(&diff, FieldElement::one(), &from_witness(borrow));
+    let (_, diff_witness) = evaluator.create_intermediate_variable(diff);
+    range_constraint(diff_witness, high_bits, evaluator).unwrap_or_else(|err| {
+        dbg!(err);
+    });
+
+    (InternalVar::from(low_witness), InternalVar::from(high_witness))
 }
 
 //Performs bit decomposition
@@ -898,6 +1711,7 @@ pub fn evaluate_mul(lhs: &InternalVar, rhs: &InternalVar, evaluator: &mut Evalua
 pub fn evaluate_udiv(
     lhs: &InternalVar,
     rhs: &InternalVar,
+    bit_size: u32,
     evaluator: &mut Evaluator,
 ) -> (Witness, Witness) {
     let q_witness = evaluator.add_witness_to_cs();
@@ -912,12 +1726,11 @@ pub fn evaluate_udiv(
     //r<b
     let r_expr = Expression::from(Linear::from_witness(r_witness));
     let r_var = InternalVar { expression: r_expr, witness: Some(r_witness), id: None };
-    bound_check(&r_var, rhs, true, 32, evaluator); //TODO bit size! should be max(a.bit, b.bit)
-                                                   //range check q<=a
-    range_constraint(q_witness, 32, evaluator).unwrap_or_else(|err| {
+    bound_check(&r_var, rhs, true, bit_size, evaluator);
+    //range check q<=a
+    range_constraint(q_witness, bit_size, evaluator).unwrap_or_else(|err| {
         dbg!(err);
     });
-    //todo bit size should be a.bits
     // a-b*q-r = 0
     let mut d = mul(&rhs.expression, &Expression::from(&q_witness));
     d = add(&d, FieldElement::one(), &Expression::from(&r_witness));
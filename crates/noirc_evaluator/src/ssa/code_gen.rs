@@ -1,18 +1,18 @@
 use super::context::SsaContext;
 use super::function::FuncIndex;
-use super::mem::ArrayId;
+use super::mem::{ArrayId, Memory};
 use super::node::{Binary, BinaryOp, NodeId, ObjectType, Operation, Variable};
 use super::{block, node, ssa_form};
 use std::collections::HashMap;
-use std::convert::TryInto;
 
 use super::super::environment::Environment;
-use super::super::errors::RuntimeError;
+use super::super::errors::{RuntimeError, RuntimeErrorKind};
 
 use crate::ssa::block::BlockType;
 use crate::ssa::function;
 use acvm::acir::OPCODE;
 use acvm::FieldElement;
+use noirc_errors::Location;
 use noirc_frontend::monomorphisation::ast::*;
 use noirc_frontend::util::vecmap;
 use noirc_frontend::{BinaryOpKind, UnaryOp};
@@ -27,6 +27,10 @@ pub struct IRGenerator {
     /// into multiple variables/values
     variable_values: HashMap<DefinitionId, Value>,
 
+    /// Integer additions, subtractions and multiplications fail on overflow when set,
+    /// except for the wrapping_* builtins
+    checked_arithmetic: bool,
+
     pub program: Program,
 }
 
@@ -99,16 +103,24 @@ impl Value {
 }
 
 impl IRGenerator {
-    pub fn new(program: Program) -> IRGenerator {
+    pub fn new(program: Program, checked_arithmetic: bool) -> IRGenerator {
         IRGenerator {
             context: SsaContext::new(),
             variable_values: HashMap::new(),
             function_context: None,
+            checked_arithmetic,
             program,
         }
     }
 
     pub fn codegen_main(&mut self, env: &mut Environment) -> Result<(), RuntimeError> {
+        //Unconstrained functions are compiled before any function body is taken by the code generation
+        for function in &self.program.functions {
+            if function.unconstrained {
+                let hint = super::hint_gen::compile_hint(&self.program, function.id)?;
+                self.context.hints.insert(function.id, hint);
+            }
+        }
         let main_body = self.program.take_main_body();
         self.codegen_expression(env, &main_body)?;
         Ok(())
@@ -194,34 +206,77 @@ impl IRGenerator {
         }
     }
 
+    //Arrays of tuples are compared field by field
+    fn codegen_infix_values(
+        &mut self,
+        lhs: Value,
+        rhs: Value,
+        op: BinaryOpKind,
+        location: Location,
+        checked: bool,
+    ) -> Result<NodeId, RuntimeError> {
+        match (lhs, rhs) {
+            (Value::Single(lhs), Value::Single(rhs)) => {
+                self.codegen_infix_expression(lhs, rhs, op, location, checked)
+            }
+            (Value::Tuple(lhs), Value::Tuple(rhs)) => {
+                let combine = match op {
+                    BinaryOpKind::Equal => BinaryOpKind::And,
+                    BinaryOpKind::NotEqual => BinaryOpKind::Or,
+                    _ => unreachable!("Uncaught type error, {:?} is not defined on tuples", op),
+                };
+                let mut result = None;
+                for (lhs, rhs) in lhs.into_iter().zip(rhs) {
+                    let field = self.codegen_infix_values(lhs, rhs, op, location, false)?;
+                    result = Some(match result {
+                        Some(previous) => self
+                            .codegen_infix_expression(previous, field, combine, location, false)?,
+                        None => field,
+                    });
+                }
+                Ok(result.unwrap())
+            }
+            _ => unreachable!("Uncaught type error, cannot compare a tuple with a single value"),
+        }
+    }
+
+    /// Rebuilds a value of the given type from the ids of its flattened values
+    fn value_from_ids(typ: &Type, ids: &mut impl Iterator<Item = NodeId>) -> Value {
+        match Memory::fields(typ) {
+            Some(fields) => Value::Tuple(vecmap(fields, |field| Self::value_from_ids(&field, ids))),
+            None => Value::Single(ids.next().unwrap()),
+        }
+    }
+
     fn codegen_infix_expression(
         &mut self,
         lhs: NodeId,
         rhs: NodeId,
         op: BinaryOpKind,
+        location: Location,
+        checked: bool,
     ) -> Result<NodeId, RuntimeError> {
         let ltype = self.context.get_object_type(lhs);
+        // The exact product of a checked multiplication is range checked, which is only sound if it
+        // cannot wrap around the field
+        let integer = matches!(ltype, ObjectType::Unsigned(_) | ObjectType::Signed(_));
+        if checked
+            && integer
+            && op == BinaryOpKind::Multiply
+            && 2 * ltype.bits() >= FieldElement::max_num_bits()
+        {
+            let message = format!(
+                "checked multiplication is not supported for {}-bit integers, use std::wrapping_mul instead",
+                ltype.bits()
+            );
+            return Err(RuntimeErrorKind::UnstructuredError { message }.add_location(location));
+        }
         // Get the opcode from the infix operator
-        let opcode = Operation::Binary(Binary::from_ast(op, ltype, lhs, rhs));
+        let opcode = Operation::Binary(Binary::from_ast(op, ltype, lhs, rhs, location, checked));
         let optype = self.context.get_result_type(&opcode, ltype);
         self.context.new_instruction(opcode, optype)
     }
 
-    fn codegen_indexed_value(
-        &mut self,
-        array: &LValue,
-        index: &Expression,
-        env: &mut Environment,
-    ) -> Result<(ArrayId, NodeId), RuntimeError> {
-        let ident_def = Self::lvalue_ident_def(array);
-        let val = self.find_variable(ident_def).unwrap();
-        let lhs = val.unwrap_id();
-
-        let a_id = self.context.get_object_type(lhs).type_to_pointer();
-        let index = self.codegen_expression(env, index)?.unwrap_id();
-        Ok((a_id, index))
-    }
-
     fn lvalue_ident_def(lvalue: &LValue) -> DefinitionId {
         match lvalue {
             LValue::Ident(ident) => ident.id,
@@ -269,19 +324,18 @@ impl IRGenerator {
         base_name: &str,
         def: Option<DefinitionId>,
     ) -> Value {
+        if let Some(fields) = Memory::fields(typ) {
+            let values = vecmap(fields.iter().enumerate(), |(i, field)| {
+                let name = format!("{}.{}", base_name, i);
+                self.create_new_value(field, &name, None)
+            });
+            return self.insert_new_struct(def, values);
+        }
         match typ {
-            Type::Tuple(fields) => {
-                let values = vecmap(fields.iter().enumerate(), |(i, field)| {
-                    let name = format!("{}.{}", base_name, i);
-                    self.create_new_value(field, &name, None)
-                });
-                self.insert_new_struct(def, values)
-            }
-            Type::Array(len, _) => {
-                //TODO support array of structs
+            Type::Array(..) => {
                 let obj_type = node::ObjectType::from(typ);
-                let len = *len;
-                let (v_id, _) = self.new_array(base_name, obj_type, len.try_into().unwrap(), def);
+                let len = Memory::flat_len(typ);
+                let (v_id, _) = self.new_array(base_name, obj_type, len, def);
                 Value::Single(v_id)
             }
             _ => {
@@ -313,9 +367,10 @@ impl IRGenerator {
         env: &mut Environment,
         expr: &Expression,
         location: noirc_errors::Location,
+        message: Option<String>,
     ) -> Result<Value, RuntimeError> {
         let cond = self.codegen_expression(env, expr)?.unwrap_id();
-        let operation = Operation::Constrain(cond, location);
+        let operation = Operation::Constrain(cond, location, message);
         self.context.new_instruction(operation, ObjectType::NotAnObject)?;
         Ok(Value::dummy())
     }
@@ -396,30 +451,208 @@ impl IRGenerator {
         let ident_def = Self::lvalue_ident_def(lvalue);
         let rhs = self.codegen_expression(env, expression)?;
 
+        let (lhs, _, indices) = self.codegen_lvalue(env, lvalue)?;
+        if !indices.is_empty() {
+            self.store_path(&lhs, &indices, rhs)?;
+        } else if let LValue::Ident(_) = lvalue {
+            let result = self.assign_pattern(&lhs, rhs)?;
+            self.variable_values.insert(ident_def, result);
+        } else {
+            self.assign_pattern(&lhs, rhs)?;
+        }
+        Ok(Value::dummy())
+    }
+
+    /// Returns the value of the variable which is assigned by the lvalue, together with the type of the
+    /// assigned element and the indices (with the type of the indexed elements) at which it is stored.
+    fn codegen_lvalue(
+        &mut self,
+        env: &mut Environment,
+        lvalue: &LValue,
+    ) -> Result<(Value, Type, Vec<(NodeId, Type)>), RuntimeError> {
         match lvalue {
-            LValue::Ident(_) => {
-                let lhs = self.find_variable(ident_def).unwrap();
+            LValue::Ident(ident) => {
                 // We may be able to avoid cloning here if we change find_variable
                 // and assign_pattern to use only fields of self instead of `self` itself.
-                let lhs = lhs.clone();
-                let result = self.assign_pattern(&lhs, rhs)?;
-                self.variable_values.insert(ident_def, result);
+                let value = self.find_variable(ident.id).unwrap().clone();
+                Ok((value, ident.typ.clone(), Vec::new()))
             }
             LValue::Index { array, index } => {
-                let (_, array_idx) = self.codegen_indexed_value(array.as_ref(), index, env)?;
-                let val = self.find_variable(ident_def).unwrap();
-                let rhs_id = rhs.unwrap_id();
-                let lhs_id = val.unwrap_id();
-                self.context.handle_assign(lhs_id, Some(array_idx), rhs_id)?;
+                let (value, typ, mut indices) = self.codegen_lvalue(env, array)?;
+                let element_type = match typ {
+                    Type::Array(_, element_type) => *element_type,
+                    other => {
+                        unreachable!("Index expression must be for an array, found {:?}", other)
+                    }
+                };
+                let index = self.codegen_expression(env, index)?.unwrap_id();
+                indices.push((index, element_type.clone()));
+                Ok((value, element_type, indices))
             }
-            LValue::MemberAccess { object: _, field_index } => {
-                // TODO: This is incorrect for nested structs
-                let val = self.find_variable(ident_def).unwrap();
-                let value = val.get_field_member(*field_index).clone();
-                self.assign_pattern(&value, rhs)?;
+            LValue::MemberAccess { object, field_index } => {
+                let (value, typ, indices) = self.codegen_lvalue(env, object)?;
+                let field_type = match typ {
+                    Type::Tuple(mut fields) => fields.swap_remove(*field_index),
+                    other => unreachable!("Member access must be for a struct, found {:?}", other),
+                };
+                let indices = Self::project_indices(&indices, *field_index);
+                Ok((value.into_field_member(*field_index), field_type, indices))
             }
         }
-        Ok(Value::dummy())
+    }
+
+    /// Arrays of tuples are stored as one array per field, so indices into an array of tuples
+    /// become indices into the array of the given field.
+    fn project_indices(indices: &[(NodeId, Type)], field_index: usize) -> Vec<(NodeId, Type)> {
+        vecmap(indices, |(index, element_type)| {
+            let mut fields = Memory::fields(element_type).unwrap();
+            (*index, fields.swap_remove(field_index))
+        })
+    }
+
+    /// Computes the position inside a flat array of the element at the given indices:
+    /// sum(index_i * flat_len(element_type_i)).
+    /// The position is computed in the field so that it cannot wrap around, out of bounds indices
+    /// stay out of bounds.
+    fn flat_offset(&mut self, indices: &[(NodeId, Type)]) -> Result<NodeId, RuntimeError> {
+        let (index, element_type) = &indices[0];
+        if indices.len() == 1 && Memory::flat_len(element_type) == 1 {
+            return Ok(*index);
+        }
+
+        let mut offset = self.context.zero_with_type(ObjectType::NativeField);
+        for (index, element_type) in indices {
+            let index = if self.context.get_object_type(*index) == ObjectType::NativeField {
+                *index
+            } else {
+                self.context.new_instruction(Operation::Cast(*index), ObjectType::NativeField)?
+            };
+            let stride = FieldElement::from(Memory::flat_len(element_type) as i128);
+            let stride = self.context.get_or_create_const(stride, ObjectType::NativeField);
+            let term = self.field_operation(BinaryOp::Mul, index, stride)?;
+            offset = self.field_operation(BinaryOp::Add, offset, term)?;
+        }
+        Ok(offset)
+    }
+
+    //Generates lhs op rhs on field elements, or its value if both are constants
+    fn field_operation(
+        &mut self,
+        operator: BinaryOp,
+        lhs: NodeId,
+        rhs: NodeId,
+    ) -> Result<NodeId, RuntimeError> {
+        if let (Some(l), Some(r)) =
+            (self.context.get_as_constant(lhs), self.context.get_as_constant(rhs))
+        {
+            let value = match operator {
+                BinaryOp::Add => l + r,
+                BinaryOp::Mul => l * r,
+                _ => unreachable!("unexpected operation on an array offset"),
+            };
+            return Ok(self.context.get_or_create_const(value, ObjectType::NativeField));
+        }
+        let operation = Operation::Binary(node::Binary { lhs, rhs, operator });
+        self.context.new_instruction(operation, ObjectType::NativeField)
+    }
+
+    /// Loads the element at the given indices of the array.
+    /// When the element is itself an array, it is copied into a new array.
+    fn load_path(
+        &mut self,
+        array: &Value,
+        indices: &[(NodeId, Type)],
+    ) -> Result<Value, RuntimeError> {
+        match array {
+            Value::Tuple(fields) => {
+                let values = fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, field)| self.load_path(field, &Self::project_indices(indices, i)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Value::Tuple(values))
+            }
+            Value::Single(pointer) => {
+                let array_id = self.context.get_object_type(*pointer).type_to_pointer();
+                let e_type = self.context.mem[array_id].element_type;
+                let offset = self.flat_offset(indices)?;
+                let element_type = &indices.last().unwrap().1;
+                if let Type::Array(..) = element_type {
+                    let len = Memory::flat_len(element_type);
+                    let (new_var, new_array) = self.context.new_array("", e_type, len, None);
+                    self.copy_elements(array_id, offset, new_array, None, len)?;
+                    Ok(Value::Single(new_var))
+                } else {
+                    let load = Operation::Load { array_id, index: offset };
+                    Ok(Value::Single(self.context.new_instruction(load, e_type)?))
+                }
+            }
+        }
+    }
+
+    /// Stores the value at the given indices of the array
+    fn store_path(
+        &mut self,
+        array: &Value,
+        indices: &[(NodeId, Type)],
+        value: Value,
+    ) -> Result<(), RuntimeError> {
+        match (array, value) {
+            (Value::Tuple(fields), Value::Tuple(values)) => {
+                for (i, (field, value)) in fields.iter().zip(values).enumerate() {
+                    self.store_path(field, &Self::project_indices(indices, i), value)?;
+                }
+                Ok(())
+            }
+            (Value::Single(pointer), Value::Single(value)) => {
+                let offset = self.flat_offset(indices)?;
+                let element_type = &indices.last().unwrap().1;
+                if let Type::Array(..) = element_type {
+                    let len = Memory::flat_len(element_type);
+                    let array_id = self.context.get_object_type(*pointer).type_to_pointer();
+                    // The value is first assigned to a new array, so that it can be the result of a call
+                    let e_type = self.context.mem[array_id].element_type;
+                    let (temp, value_id) = self.context.new_array("", e_type, len, None);
+                    self.context.handle_assign(temp, None, value)?;
+                    let zero = self.context.zero_with_type(ObjectType::NativeField);
+                    self.copy_elements(value_id, zero, array_id, Some(offset), len)
+                } else {
+                    self.context.handle_assign(*pointer, Some(offset), value)?;
+                    Ok(())
+                }
+            }
+            _ => unreachable!(
+                "Uncaught type error, the stored value does not match the array elements"
+            ),
+        }
+    }
+
+    //Copies len elements of the array src, starting at src_offset, into dest starting at dest_offset (or 0)
+    fn copy_elements(
+        &mut self,
+        src: ArrayId,
+        src_offset: NodeId,
+        dest: ArrayId,
+        dest_offset: Option<NodeId>,
+        len: u32,
+    ) -> Result<(), RuntimeError> {
+        let e_type = self.context.mem[dest].element_type;
+        for k in 0..len {
+            let k = self
+                .context
+                .get_or_create_const(FieldElement::from(k as i128), ObjectType::NativeField);
+            let src_index = self.field_operation(BinaryOp::Add, src_offset, k)?;
+            let dest_index = match dest_offset {
+                Some(offset) => self.field_operation(BinaryOp::Add, offset, k)?,
+                None => k,
+            };
+            let load = self
+                .context
+                .new_instruction(Operation::Load { array_id: src, index: src_index }, e_type)?;
+            let store = Operation::Store { array_id: dest, index: dest_index, value: load };
+            self.context.new_instruction(store, e_type)?;
+        }
+        Ok(())
     }
 
     /// Similar to bind_pattern but recursively creates Assignment instructions for
@@ -463,33 +696,36 @@ impl IRGenerator {
                 Ok(Value::Single(self.context.get_or_create_const(*x, typ.into())))
             }
             Expression::Literal(Literal::Array(arr_lit)) => {
-                let element_type = ObjectType::from(&arr_lit.element_type);
+                let element_type = &arr_lit.element_type;
+                let array_type = Type::Array(arr_lit.length as u64, Box::new(element_type.clone()));
+                let new_value = self.create_new_value(&array_type, "", None);
 
-                let (new_var, array_id) =
-                    self.context.new_array("", element_type, arr_lit.length as u32, None);
-
-                let elements = self.codegen_expression_list(env, &arr_lit.contents);
-                for (pos, object) in elements.into_iter().enumerate() {
+                for (pos, content) in arr_lit.contents.iter().enumerate() {
+                    let object = self.codegen_expression(env, content)?;
                     let lhs_adr = self.context.get_or_create_const(
                         FieldElement::from((pos as u32) as u128),
                         ObjectType::NativeField,
                     );
-                    let store = Operation::Store { array_id, index: lhs_adr, value: object };
-                    self.context.new_instruction(store, element_type)?;
+                    self.store_path(&new_value, &[(lhs_adr, element_type.clone())], object)?;
                 }
-                Ok(Value::Single(new_var))
+                Ok(new_value)
             }
             Expression::Ident(ident) => {
                 Ok(self.codegen_identifier(ident))
                 //n.b this creates a new variable if it does not exist, may be we should delegate this to explicit statements (let) - TODO
             }
             Expression::Binary(binary) => {
-                // Note: using .into_id() here disallows structs/tuples in infix expressions.
-                // The type checker currently disallows this as well but we may want to allow
-                // for e.g. struct == struct in the future
-                let lhs = self.codegen_expression(env, &binary.lhs)?.unwrap_id();
-                let rhs = self.codegen_expression(env, &binary.rhs)?.unwrap_id();
-                Ok(Value::Single(self.codegen_infix_expression(lhs, rhs, binary.operator)?))
+                // Arrays of structs are made of one array per field, and are compared field by field
+                let lhs = self.codegen_expression(env, &binary.lhs)?;
+                let rhs = self.codegen_expression(env, &binary.rhs)?;
+                let result = self.codegen_infix_values(
+                    lhs,
+                    rhs,
+                    binary.operator,
+                    binary.location,
+                    self.checked_arithmetic,
+                )?;
+                Ok(Value::Single(result))
             }
             Expression::Cast(cast_expr) => {
                 let lhs = self.codegen_expression(env, &cast_expr.lhs)?.unwrap_id();
@@ -498,18 +734,23 @@ impl IRGenerator {
                 Ok(Value::Single(self.context.new_instruction(Operation::Cast(lhs), rtype)?))
             }
             Expression::Index(indexed_expr) => {
+                // Successive indices, as in a[i][j], are merged into a single access
+                let mut path = vec![indexed_expr];
+                while let Expression::Index(inner) = path.last().unwrap().collection.as_ref() {
+                    path.push(inner);
+                }
                 // Evaluate the 'array' expression
-                let expr_node = self.codegen_expression(env, &indexed_expr.collection)?.unwrap_id();
-                let array = match self.context.get_object_type(expr_node) {
-                    ObjectType::Pointer(array_id) => &self.context.mem[array_id],
-                    other => unreachable!("Expected Pointer type, found {:?}", other),
-                };
-                let array_id = array.id;
-                let e_type = array.element_type;
-                // Evaluate the index expression
-                let index_as_obj = self.codegen_expression(env, &indexed_expr.index)?.unwrap_id();
-                let load = Operation::Load { array_id, index: index_as_obj };
-                Ok(Value::Single(self.context.new_instruction(load, e_type)?))
+                let array = self.codegen_expression(env, &path.last().unwrap().collection)?;
+                // Evaluate the index expressions
+                let mut indices = Vec::with_capacity(path.len());
+                for index in path.iter().rev() {
+                    let index_as_obj = self.codegen_expression(env, &index.index)?.unwrap_id();
+                    indices.push((index_as_obj, index.element_type.clone()));
+                }
+                self.load_path(&array, &indices)
+            }
+            Expression::Call(call_expr) if self.program[call_expr.func_id].unconstrained => {
+                Ok(Value::Single(self.call_hint(call_expr, env)?))
             }
             Expression::Call(call_expr) => {
                 if self.context.get_ssafunc(call_expr.func_id).is_none() {
@@ -526,21 +767,13 @@ impl IRGenerator {
                 let results = self.call(call_expr, env)?;
 
                 let function = &self.program[call_expr.func_id];
-                Ok(match &function.return_type {
-                    Type::Tuple(_) => Value::Tuple(vecmap(results, Value::Single)),
-                    _ => {
-                        assert_eq!(results.len(), 1);
-                        Value::Single(results[0])
-                    }
-                })
+                let mut results = results.into_iter();
+                let value = Self::value_from_ids(&function.return_type, &mut results);
+                assert!(results.next().is_none());
+                Ok(value)
             }
             Expression::CallLowLevel(call) => Ok(Value::Single(self.codegen_lowlevel(env, call)?)),
-            Expression::CallBuiltin(_call) => {
-                todo!()
-                // let attribute = func_meta.attributes.expect("all builtin functions must contain an attribute which contains the function name which it links to");
-                // let builtin_name = attribute.builtin().expect("ice: function marked as a builtin, but attribute kind does not match this");
-                // builtin::call_builtin(self, env, builtin_name, (call_expr,span))
-            }
+            Expression::CallBuiltin(call) => self.codegen_builtin(env, call),
             Expression::For(for_expr) => self.codegen_for(env, for_expr),
             Expression::Tuple(fields) => self.codegen_tuple(env, fields),
             Expression::If(if_expr) => self.handle_if_expr(env, if_expr),
@@ -555,8 +788,8 @@ impl IRGenerator {
                 Ok(tuple.into_field_member(*field))
             }
             Expression::Let(let_expr) => self.codegen_let(env, let_expr),
-            Expression::Constrain(expr, location) => {
-                self.codegen_constrain(env, expr.as_ref(), *location)
+            Expression::Constrain(expr, location, message) => {
+                self.codegen_constrain(env, expr.as_ref(), *location, message.clone())
             }
             Expression::Assign(assign) => {
                 self.codegen_assign(&assign.lvalue, assign.expression.as_ref(), env)
@@ -584,6 +817,124 @@ impl IRGenerator {
         }
     }
 
+    fn codegen_builtin(
+        &mut self,
+        env: &mut Environment,
+        call: &CallBuiltin,
+    ) -> Result<Value, RuntimeError> {
+        if call.opcode == "println" {
+            return self.codegen_println(env, call);
+        }
+        if call.opcode == "lt_bounded" {
+            return self.codegen_lt_bounded(env, call);
+        }
+        // The wrapping operations are never checked for overflow
+        let op = match call.opcode.as_str() {
+            "wrapping_add" => BinaryOpKind::Add,
+            "wrapping_sub" => BinaryOpKind::Subtract,
+            "wrapping_mul" => BinaryOpKind::Multiply,
+            _ => {
+                let message =
+                    format!("builtin function {} is not supported in the IR", call.opcode);
+                return Err(RuntimeErrorKind::Unimplemented(message).add_location(call.location));
+            }
+        };
+        let lhs = self.codegen_expression(env, &call.arguments[0])?.unwrap_id();
+        let rhs = self.codegen_expression(env, &call.arguments[1])?.unwrap_id();
+        self.codegen_infix_expression(lhs, rhs, op, call.location, false).map(Value::Single)
+    }
+
+    // The comparison of field elements which the caller asserts to be less than 2^bit_size, so that
+    // the cheaper integer comparison can be used once they are range checked
+    fn codegen_lt_bounded(
+        &mut self,
+        env: &mut Environment,
+        call: &CallBuiltin,
+    ) -> Result<Value, RuntimeError> {
+        let lhs = self.codegen_expression(env, &call.arguments[0])?.unwrap_id();
+        let rhs = self.codegen_expression(env, &call.arguments[1])?.unwrap_id();
+        let bit_size = self.codegen_expression(env, &call.arguments[2])?.unwrap_id();
+        let max_bits = FieldElement::max_num_bits() - 3;
+        let bit_size = match self.context.get_as_constant(bit_size).map(|c| c.to_u128()) {
+            Some(bit_size) if (1..=max_bits as u128).contains(&bit_size) => bit_size as u32,
+            _ => {
+                let message = format!(
+                    "the bit size of lt_bounded must be a constant between 1 and {}",
+                    max_bits
+                );
+                return Err(
+                    RuntimeErrorKind::UnstructuredError { message }.add_location(call.location)
+                );
+            }
+        };
+        let operator = BinaryOp::BoundedLt { bit_size, location: call.location };
+        let operation = Operation::Binary(node::Binary { lhs, rhs, operator });
+        self.context.new_instruction(operation, ObjectType::Boolean).map(Value::Single)
+    }
+
+    // The value is printed by a log instruction, whose inputs are its arguments, an array argument
+    // giving one input per element
+    fn codegen_println(
+        &mut self,
+        env: &mut Environment,
+        call: &CallBuiltin,
+    ) -> Result<Value, RuntimeError> {
+        let value = self.codegen_expression(env, &call.arguments[0])?;
+        let mut arguments = Vec::new();
+        let mut first_inputs = HashMap::new();
+        let mut input_count = 0;
+        for id in value.to_node_ids() {
+            if id == NodeId::dummy() || first_inputs.contains_key(&id) {
+                continue;
+            }
+            first_inputs.insert(id, input_count);
+            input_count += match self.context.get_object_type(id) {
+                ObjectType::Pointer(array_id) => self.context.mem[array_id].len,
+                _ => 1,
+            };
+            arguments.push(id);
+        }
+        let format = Self::log_format(&call.argument_types[0], &value, &first_inputs, &mut vec![]);
+        let operation = Operation::Log { format, arguments, predicate: None };
+        self.context.new_instruction(operation, ObjectType::NotAnObject)?;
+        Ok(Value::dummy())
+    }
+
+    // Returns the format string of a value of the given type, whose placeholders refer to the inputs
+    // of a log instruction. Arrays of tuples are held as one array per field, so the elements are
+    // located with their (index, length) in each of the enclosing arrays.
+    fn log_format(
+        typ: &Type,
+        value: &Value,
+        first_inputs: &HashMap<NodeId, u32>,
+        indices: &mut Vec<(u64, u64)>,
+    ) -> String {
+        match typ {
+            Type::Unit => "()".to_string(),
+            Type::Array(len, element) => {
+                let mut elements = Vec::new();
+                for i in 0..*len {
+                    indices.push((i, *len));
+                    elements.push(Self::log_format(element, value, first_inputs, indices));
+                    indices.pop();
+                }
+                format!("[{}]", elements.join(", "))
+            }
+            Type::Tuple(fields) => {
+                let mut elements = Vec::new();
+                for (i, field) in fields.iter().enumerate() {
+                    let field_value = value.get_field_member(i);
+                    elements.push(Self::log_format(field, field_value, first_inputs, indices));
+                }
+                format!("({})", elements.join(", "))
+            }
+            _ => {
+                let offset = indices.iter().fold(0, |offset, (index, len)| offset * len + index);
+                format!("{{{}}}", first_inputs[&value.unwrap_id()] + offset as u32)
+            }
+        }
+    }
+
     fn codegen_literal(&mut self, l: &Literal) -> NodeId {
         match l {
             Literal::Bool(b) => {
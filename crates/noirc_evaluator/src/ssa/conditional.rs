@@ -463,7 +463,7 @@ impl DecisionTree {
                 }
                 stack.push(ins_id);
             }
-            Operation::Intrinsic(_, _) => {
+            Operation::Intrinsic(_, _) | Operation::Hint(_, _) => {
                 stack.push(ins_id);
                 if ctx.under_assumption(ass_value) {
                     if let ObjectType::Pointer(a) = ins.res_type {
@@ -502,7 +502,7 @@ impl DecisionTree {
                 }
                 stack.push(ins_id);
             }
-            Operation::Constrain(expr, loc) => {
+            Operation::Constrain(expr, loc, message) => {
                 if ctx.under_assumption(ass_value) {
                     let operation = Operation::Cond {
                         condition: ass_value,
@@ -515,8 +515,20 @@ impl DecisionTree {
                         Some(stack.block),
                     ));
                     stack.push(cond);
+                    let message = message.clone();
                     let ins2 = ctx.get_mut_instruction(ins_id);
-                    ins2.operation = Operation::Constrain(cond, *loc);
+                    ins2.operation = Operation::Constrain(cond, *loc, message);
+                }
+                stack.push(ins_id);
+            }
+            Operation::Log { format, arguments, .. } => {
+                if ctx.under_assumption(ass_value) {
+                    let operation = Operation::Log {
+                        format: format.clone(),
+                        arguments: arguments.clone(),
+                        predicate: Some(ass_value),
+                    };
+                    ctx.get_mut_instruction(ins_id).operation = operation;
                 }
                 stack.push(ins_id);
             }
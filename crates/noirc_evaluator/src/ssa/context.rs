@@ -12,6 +12,7 @@ use crate::ssa::acir_gen::Acir;
 use crate::ssa::function;
 use crate::ssa::node::{Mark, Node};
 use crate::Evaluator;
+use acvm::acir::circuit::hint::HintProgram;
 use acvm::FieldElement;
 use noirc_frontend::monomorphisation::ast::{DefinitionId, FuncId};
 use noirc_frontend::util::vecmap;
@@ -33,6 +34,8 @@ pub struct SsaContext {
     pub functions: HashMap<FuncId, function::SSAFunction>,
     //Adjacency Matrix of the call graph; list of rows where each row indicates the functions called by the function whose FuncIndex is the row number
     pub call_graph: Vec<Vec<u8>>,
+    //Hint programs of the unconstrained functions, which are not part of the call graph since they are never inlined
+    pub hints: HashMap<FuncId, HintProgram>,
     dummy_store: HashMap<ArrayId, NodeId>,
     dummy_load: HashMap<ArrayId, NodeId>,
 }
@@ -49,6 +52,7 @@ impl SsaContext {
             mem: Memory::default(),
             functions: HashMap::new(),
             call_graph: Vec::new(),
+            hints: HashMap::new(),
             dummy_store: HashMap::new(),
             dummy_load: HashMap::new(),
         };
@@ -127,15 +131,15 @@ impl SsaContext {
         let rhs = self.node_to_string(binary.rhs);
         let op = match &binary.operator {
             BinaryOp::Add => "add",
-            BinaryOp::SafeAdd => "safe_add",
+            BinaryOp::SafeAdd { .. } => "safe_add",
             BinaryOp::Sub { .. } => "sub",
             BinaryOp::SafeSub { .. } => "safe_sub",
             BinaryOp::Mul => "mul",
-            BinaryOp::SafeMul => "safe_mul",
+            BinaryOp::SafeMul { .. } => "safe_mul",
             BinaryOp::Udiv => "udiv",
-            BinaryOp::Sdiv => "sdiv",
+            BinaryOp::Sdiv { .. } => "sdiv",
             BinaryOp::Urem => "urem",
-            BinaryOp::Srem => "srem",
+            BinaryOp::Srem { .. } => "srem",
             BinaryOp::Div => "div",
             BinaryOp::Eq => "eq",
             BinaryOp::Ne => "ne",
@@ -145,6 +149,7 @@ impl SsaContext {
             BinaryOp::Sle => "sle",
             BinaryOp::Lt => "lt",
             BinaryOp::Lte => "lte",
+            BinaryOp::BoundedLt { .. } => "bounded_lt",
             BinaryOp::And => "and",
             BinaryOp::Or => "or",
             BinaryOp::Xor => "xor",
@@ -204,6 +209,14 @@ impl SsaContext {
                 )
             }
             Operation::Intrinsic(opcode, args) => format!("intrinsic {}({})", opcode, join(args)),
+            Operation::Hint(func_id, args) => format!("hint {:?}({})", func_id, join(args)),
+            Operation::Log { format, arguments, predicate } => {
+                let log = format!("log {:?}({})", format, join(arguments));
+                match predicate {
+                    Some(predicate) => format!("{} if {}", log, self.node_to_string(*predicate)),
+                    None => log,
+                }
+            }
             Operation::Nop => "nop".into(),
             Operation::Call { func_id, arguments, returned_arrays, .. } => {
                 format!("call {:?}({}) _ {:?}", func_id, join(arguments), returned_arrays)
@@ -528,12 +541,14 @@ impl SsaContext {
             | Binary(node::Binary { operator: Slt, .. })
             | Binary(node::Binary { operator: Sle, .. })
             | Binary(node::Binary { operator: Lt, .. })
-            | Binary(node::Binary { operator: Lte, .. }) => ObjectType::Boolean,
+            | Binary(node::Binary { operator: Lte, .. })
+            | Binary(node::Binary { operator: BoundedLt { .. }, .. }) => ObjectType::Boolean,
             Operation::Jne(_, _)
             | Operation::Jeq(_, _)
             | Operation::Jmp(_)
             | Operation::Nop
             | Operation::Constrain(..)
+            | Operation::Log { .. }
             | Operation::Store { .. } => ObjectType::NotAnObject,
             Operation::Load { array_id, .. } => self.mem[*array_id].element_type,
             Operation::Cast(_) | Operation::Truncate { .. } => {
@@ -669,6 +684,7 @@ impl SsaContext {
             //TODO we should rather follow the jumps
             fb = block.left.map(|block_id| &self[block_id]);
         }
+        acir.close_traces(self, evaluator);
     }
 
     pub fn generate_empty_phi(&mut self, target_block: BlockId, phi_root: NodeId) -> NodeId {
@@ -769,7 +785,7 @@ impl SsaContext {
             }
         } else if matches!(lhs_type, ObjectType::Pointer(_)) {
             if let Some(Instruction {
-                operation: Operation::Intrinsic(_, _),
+                operation: Operation::Intrinsic(_, _) | Operation::Hint(_, _),
                 res_type: rtype,
                 ..
             }) = self.try_get_mut_instruction(rhs)
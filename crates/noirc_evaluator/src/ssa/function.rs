@@ -12,6 +12,7 @@ use super::{
     block::BlockId,
     code_gen::IRGenerator,
     context::SsaContext,
+    mem::Memory,
     node::{self, NodeId, ObjectType},
     ssa_form,
 };
@@ -112,6 +113,7 @@ pub fn get_result_type(op: OPCODE) -> (u32, ObjectType) {
         OPCODE::AES => (0, ObjectType::NotAnObject), //Not implemented
         OPCODE::SHA256 => (32, ObjectType::Unsigned(8)),
         OPCODE::Blake2s => (32, ObjectType::Unsigned(8)),
+        OPCODE::Keccak256 => (32, ObjectType::Unsigned(8)),
         OPCODE::HashToField => (1, ObjectType::NativeField),
         OPCODE::MerkleMembership => (1, ObjectType::NativeField), //or bool?
         OPCODE::SchnorrVerify => (1, ObjectType::NativeField),    //or bool?
@@ -144,7 +146,7 @@ impl IRGenerator {
 
         // ensure return types are defined in case of recursion call cycle
         let function = &mut self.program[func_id];
-        let return_types = function.return_type.flatten();
+        let return_types = Memory::flatten_type(&function.return_type);
         for typ in return_types {
             func.result_types.push(match typ {
                 Type::Unit => ObjectType::NotAnObject,
@@ -222,6 +224,26 @@ impl IRGenerator {
         Ok(result)
     }
 
+    //generates an instruction for executing an unconstrained function as a hint
+    pub fn call_hint(
+        &mut self,
+        call: &Call,
+        env: &mut Environment,
+    ) -> Result<NodeId, RuntimeError> {
+        let arguments = self.codegen_expression_list(env, &call.arguments);
+        let function = &self.program[call.func_id];
+        let name = format!("{}_result", function.name);
+        let result_type = match function.return_type.clone() {
+            typ @ Type::Array(..) => {
+                let element_type = ObjectType::from(&typ);
+                let len = Memory::flat_len(&typ);
+                ObjectType::Pointer(self.new_array(&name, element_type, len, None).1)
+            }
+            typ => ObjectType::from(typ),
+        };
+        self.context.new_instruction(node::Operation::Hint(call.func_id, arguments), result_type)
+    }
+
     //Lowlevel functions with no more than 2 arguments
     pub fn call_low_level(
         &mut self,
@@ -0,0 +1,490 @@
+use std::collections::HashMap;
+
+use acvm::acir::circuit::hint::{HintBinaryOp, HintNumeric, HintOp, HintProgram, Register};
+use acvm::FieldElement;
+use noirc_errors::{Location, Span};
+use noirc_frontend::monomorphisation::ast::*;
+use noirc_frontend::{BinaryOpKind, Signedness, UnaryOp};
+
+use crate::errors::{RuntimeError, RuntimeErrorKind};
+
+//Compiles an unconstrained function into a hint program which is executed by the ACVM during witness generation.
+//The function is not turned into constraints, so the caller is responsible for constraining the results.
+pub fn compile_hint(program: &Program, func_id: FuncId) -> Result<HintProgram, RuntimeError> {
+    let mut generator = HintGenerator::new(program);
+    generator.call_stack.push(func_id);
+    let function = &program[func_id];
+
+    let mut inputs = Vec::new();
+    for (id, _, _, typ) in &function.parameters {
+        let value = generator.allocate(typ)?;
+        inputs.extend(value.registers());
+        generator.variables.insert(*id, value);
+    }
+
+    match &function.return_type {
+        Type::Field | Type::Bool | Type::Integer(..) => (),
+        Type::Array(_, element) if is_scalar(element) => (),
+        other => {
+            let message = format!(
+                "unconstrained function {} returns {}, but only scalars and arrays of scalars are supported",
+                function.name, other
+            );
+            return Err(generator.error(message));
+        }
+    }
+
+    let result = generator.expression(&function.body)?;
+    Ok(HintProgram {
+        num_registers: generator.num_registers,
+        inputs,
+        outputs: result.registers(),
+        instructions: generator.instructions,
+    })
+}
+
+#[derive(Debug, Clone)]
+enum HintValue {
+    Single(Register, Type),
+    // An array of scalars, laid out in consecutive registers starting at the base register
+    Array(Register, u64, Type),
+    Tuple(Vec<HintValue>),
+    Unit,
+}
+
+impl HintValue {
+    fn registers(&self) -> Vec<Register> {
+        match self {
+            HintValue::Single(register, _) => vec![*register],
+            HintValue::Array(base, len, _) => (0..*len).map(|i| offset(*base, i)).collect(),
+            HintValue::Tuple(fields) => fields.iter().flat_map(HintValue::registers).collect(),
+            HintValue::Unit => Vec::new(),
+        }
+    }
+}
+
+fn offset(base: Register, index: u64) -> Register {
+    Register(base.0 + index as u32)
+}
+
+fn is_scalar(typ: &Type) -> bool {
+    matches!(typ, Type::Field | Type::Bool | Type::Integer(..))
+}
+
+fn numeric(typ: &Type) -> HintNumeric {
+    match typ {
+        Type::Integer(Signedness::Unsigned, bit_size) => HintNumeric::Unsigned(*bit_size),
+        Type::Integer(Signedness::Signed, bit_size) => HintNumeric::Signed(*bit_size),
+        Type::Bool => HintNumeric::Unsigned(1),
+        _ => HintNumeric::Field,
+    }
+}
+
+struct HintGenerator<'a> {
+    program: &'a Program,
+    instructions: Vec<HintOp>,
+    num_registers: u32,
+    variables: HashMap<DefinitionId, HintValue>,
+    //Functions called from an unconstrained function are inlined, so we keep track of them to detect recursion
+    call_stack: Vec<FuncId>,
+    location: Option<Location>,
+}
+
+impl<'a> HintGenerator<'a> {
+    fn new(program: &'a Program) -> HintGenerator<'a> {
+        HintGenerator {
+            program,
+            instructions: Vec::new(),
+            num_registers: 0,
+            variables: HashMap::new(),
+            call_stack: Vec::new(),
+            location: None,
+        }
+    }
+
+    fn error(&self, message: String) -> RuntimeError {
+        //Monomorphised expressions do not all carry a location, so we report the last one we have seen
+        let location = self
+            .location
+            .unwrap_or_else(|| Location::new(Span::single_char(0), Default::default()));
+        RuntimeErrorKind::Unimplemented(message).add_location(location)
+    }
+
+    fn new_registers(&mut self, count: u32) -> Register {
+        let base = Register(self.num_registers);
+        self.num_registers += count;
+        base
+    }
+
+    fn push(&mut self, op: HintOp) -> usize {
+        self.instructions.push(op);
+        self.instructions.len() - 1
+    }
+
+    //Sets the target of the jump at position 'jump' to the next instruction
+    fn patch_jump(&mut self, jump: usize) {
+        let next = self.instructions.len();
+        match &mut self.instructions[jump] {
+            HintOp::Jump { target } | HintOp::JumpIfNot { target, .. } => *target = next,
+            _ => unreachable!("expected a jump instruction"),
+        }
+    }
+
+    fn allocate(&mut self, typ: &Type) -> Result<HintValue, RuntimeError> {
+        Ok(match typ {
+            Type::Field | Type::Bool | Type::Integer(..) => {
+                HintValue::Single(self.new_registers(1), typ.clone())
+            }
+            Type::Array(len, element) if is_scalar(element) => {
+                HintValue::Array(self.new_registers(*len as u32), *len, element.as_ref().clone())
+            }
+            Type::Tuple(fields) => {
+                HintValue::Tuple(fields.iter().map(|f| self.allocate(f)).collect::<Result<_, _>>()?)
+            }
+            Type::Unit => HintValue::Unit,
+            Type::Array(..) => {
+                return Err(
+                    self.error("nested arrays are not supported in unconstrained functions".into())
+                )
+            }
+        })
+    }
+
+    fn value_type(value: &HintValue) -> Type {
+        match value {
+            HintValue::Single(_, typ) => typ.clone(),
+            HintValue::Array(_, len, typ) => Type::Array(*len, Box::new(typ.clone())),
+            HintValue::Tuple(fields) => Type::Tuple(fields.iter().map(Self::value_type).collect()),
+            HintValue::Unit => Type::Unit,
+        }
+    }
+
+    //Copies the registers of 'source' into the registers of 'destination', which must have the same shape
+    fn copy_into(&mut self, destination: &HintValue, source: &HintValue) {
+        for (destination, source) in destination.registers().into_iter().zip(source.registers()) {
+            if destination != source {
+                self.push(HintOp::Mov { destination, source });
+            }
+        }
+    }
+
+    //Values are copied when they are bound to a variable because arrays and tuples are value types
+    fn copy(&mut self, value: &HintValue) -> Result<HintValue, RuntimeError> {
+        let copy = self.allocate(&Self::value_type(value))?;
+        self.copy_into(&copy, value);
+        Ok(copy)
+    }
+
+    fn constant(&mut self, value: FieldElement) -> Register {
+        let destination = self.new_registers(1);
+        self.push(HintOp::Const { destination, value });
+        destination
+    }
+
+    fn single(&mut self, expr: &Expression) -> Result<(Register, Type), RuntimeError> {
+        match self.expression(expr)? {
+            HintValue::Single(register, typ) => Ok((register, typ)),
+            _ => unreachable!("expected a single value in expression {}", expr),
+        }
+    }
+
+    fn array(&mut self, expr: &Expression) -> Result<(Register, u64, Type), RuntimeError> {
+        match self.expression(expr)? {
+            HintValue::Array(base, len, typ) => Ok((base, len, typ)),
+            _ => unreachable!("expected an array in expression {}", expr),
+        }
+    }
+
+    fn binary(
+        &mut self,
+        op: HintBinaryOp,
+        numeric: HintNumeric,
+        lhs: Register,
+        rhs: Register,
+    ) -> Register {
+        let destination = self.new_registers(1);
+        self.push(HintOp::Binary { op, numeric, lhs, rhs, destination });
+        destination
+    }
+
+    //Traps unless the condition is true
+    fn assert(&mut self, condition: Register) {
+        let destination = self.new_registers(1);
+        self.push(HintOp::Not {
+            numeric: HintNumeric::Unsigned(1),
+            source: condition,
+            destination,
+        });
+        let jump = self.push(HintOp::JumpIfNot { condition: destination, target: 0 });
+        self.push(HintOp::Trap);
+        self.patch_jump(jump);
+    }
+
+    fn bounds_check(&mut self, index: Register, len: u64) {
+        let len = self.constant(FieldElement::from(len as u128));
+        let in_bounds = self.binary(HintBinaryOp::Lt, HintNumeric::Field, index, len);
+        self.assert(in_bounds);
+    }
+
+    fn expression(&mut self, expr: &Expression) -> Result<HintValue, RuntimeError> {
+        match expr {
+            Expression::Ident(ident) => {
+                if ident.location.is_some() {
+                    self.location = ident.location;
+                }
+                Ok(self.variables[&ident.id].clone())
+            }
+            Expression::Literal(Literal::Integer(value, typ)) => {
+                Ok(HintValue::Single(self.constant(*value), typ.clone()))
+            }
+            Expression::Literal(Literal::Bool(value)) => {
+                Ok(HintValue::Single(self.constant(FieldElement::from(*value as u128)), Type::Bool))
+            }
+            Expression::Literal(Literal::Array(array)) => {
+                let value = self.allocate(&Type::Array(
+                    array.length as u64,
+                    Box::new(array.element_type.clone()),
+                ))?;
+                let base = value.registers();
+                for (destination, element) in base.into_iter().zip(&array.contents) {
+                    let (source, _) = self.single(element)?;
+                    self.push(HintOp::Mov { destination, source });
+                }
+                Ok(value)
+            }
+            Expression::Literal(Literal::Str(_)) => {
+                Err(self.error("strings are not supported in unconstrained functions".into()))
+            }
+            Expression::Block(statements) => {
+                let mut result = HintValue::Unit;
+                for statement in statements {
+                    result = self.expression(statement)?;
+                }
+                Ok(result)
+            }
+            Expression::Unary(unary) => {
+                let (rhs, typ) = self.single(&unary.rhs)?;
+                let destination = match unary.operator {
+                    UnaryOp::Minus => {
+                        let zero = self.constant(FieldElement::zero());
+                        self.binary(HintBinaryOp::Sub, numeric(&typ), zero, rhs)
+                    }
+                    UnaryOp::Not => {
+                        let destination = self.new_registers(1);
+                        self.push(HintOp::Not { numeric: numeric(&typ), source: rhs, destination });
+                        destination
+                    }
+                };
+                Ok(HintValue::Single(destination, typ))
+            }
+            Expression::Binary(binary) => {
+                let (lhs, typ) = self.single(&binary.lhs)?;
+                let (rhs, _) = self.single(&binary.rhs)?;
+                let numeric = numeric(&typ);
+                let (op, lhs, rhs) = match binary.operator {
+                    BinaryOpKind::Add => (HintBinaryOp::Add, lhs, rhs),
+                    BinaryOpKind::Subtract => (HintBinaryOp::Sub, lhs, rhs),
+                    BinaryOpKind::Multiply => (HintBinaryOp::Mul, lhs, rhs),
+                    BinaryOpKind::Divide => (HintBinaryOp::Div, lhs, rhs),
+                    BinaryOpKind::Modulo => (HintBinaryOp::Rem, lhs, rhs),
+                    BinaryOpKind::Equal | BinaryOpKind::NotEqual => (HintBinaryOp::Eq, lhs, rhs),
+                    BinaryOpKind::Less => (HintBinaryOp::Lt, lhs, rhs),
+                    BinaryOpKind::LessEqual => (HintBinaryOp::Le, lhs, rhs),
+                    BinaryOpKind::Greater => (HintBinaryOp::Lt, rhs, lhs),
+                    BinaryOpKind::GreaterEqual => (HintBinaryOp::Le, rhs, lhs),
+                    BinaryOpKind::And => (HintBinaryOp::And, lhs, rhs),
+                    BinaryOpKind::Or => (HintBinaryOp::Or, lhs, rhs),
+                    BinaryOpKind::Xor => (HintBinaryOp::Xor, lhs, rhs),
+                    BinaryOpKind::ShiftLeft => (HintBinaryOp::Shl, lhs, rhs),
+                    BinaryOpKind::ShiftRight => (HintBinaryOp::Shr, lhs, rhs),
+                };
+                let mut destination = self.binary(op, numeric, lhs, rhs);
+                if binary.operator == BinaryOpKind::NotEqual {
+                    let source = destination;
+                    destination = self.new_registers(1);
+                    self.push(HintOp::Not {
+                        numeric: HintNumeric::Unsigned(1),
+                        source,
+                        destination,
+                    });
+                }
+                let typ = if binary.operator.is_comparator() { Type::Bool } else { typ };
+                Ok(HintValue::Single(destination, typ))
+            }
+            Expression::Index(index) => {
+                let (base, len, typ) = self.array(&index.collection)?;
+                let (index, _) = self.single(&index.index)?;
+                self.bounds_check(index, len);
+                let destination = self.new_registers(1);
+                self.push(HintOp::Load { destination, base, index });
+                Ok(HintValue::Single(destination, typ))
+            }
+            Expression::Cast(cast) => {
+                let (source, _) = self.single(&cast.lhs)?;
+                let destination = self.new_registers(1);
+                match &cast.r#type {
+                    Type::Integer(_, bit_size) => {
+                        self.push(HintOp::Truncate { source, destination, bit_size: *bit_size })
+                    }
+                    Type::Bool => self.push(HintOp::Truncate { source, destination, bit_size: 1 }),
+                    _ => self.push(HintOp::Mov { destination, source }),
+                };
+                Ok(HintValue::Single(destination, cast.r#type.clone()))
+            }
+            Expression::For(for_expr) => {
+                let (start, _) = self.single(&for_expr.start_range)?;
+                let (end, _) = self.single(&for_expr.end_range)?;
+                let index = self.new_registers(1);
+                self.push(HintOp::Mov { destination: index, source: start });
+                self.variables.insert(
+                    for_expr.index_variable,
+                    HintValue::Single(index, for_expr.index_type.clone()),
+                );
+                let one = self.constant(FieldElement::one());
+
+                let loop_start = self.instructions.len();
+                let condition =
+                    self.binary(HintBinaryOp::Lt, numeric(&for_expr.index_type), index, end);
+                let exit = self.push(HintOp::JumpIfNot { condition, target: 0 });
+                self.expression(&for_expr.block)?;
+                self.push(HintOp::Binary {
+                    op: HintBinaryOp::Add,
+                    numeric: numeric(&for_expr.index_type),
+                    lhs: index,
+                    rhs: one,
+                    destination: index,
+                });
+                self.push(HintOp::Jump { target: loop_start });
+                self.patch_jump(exit);
+                Ok(HintValue::Unit)
+            }
+            Expression::If(if_expr) => {
+                let (condition, _) = self.single(&if_expr.condition)?;
+                let jump_to_else = self.push(HintOp::JumpIfNot { condition, target: 0 });
+                let then_value = self.expression(&if_expr.consequence)?;
+                let result = self.copy(&then_value)?;
+                match &if_expr.alternative {
+                    Some(alternative) => {
+                        let jump_to_end = self.push(HintOp::Jump { target: 0 });
+                        self.patch_jump(jump_to_else);
+                        let else_value = self.expression(alternative)?;
+                        self.copy_into(&result, &else_value);
+                        self.patch_jump(jump_to_end);
+                    }
+                    None => self.patch_jump(jump_to_else),
+                }
+                Ok(result)
+            }
+            Expression::Tuple(fields) => Ok(HintValue::Tuple(
+                fields.iter().map(|field| self.expression(field)).collect::<Result<_, _>>()?,
+            )),
+            Expression::ExtractTupleField(tuple, field) => match self.expression(tuple)? {
+                HintValue::Tuple(mut fields) => Ok(fields.swap_remove(*field)),
+                _ => unreachable!("expected a tuple in expression {}", tuple),
+            },
+            Expression::Call(call) => self.call(call),
+            Expression::CallBuiltin(call) => {
+                // Hint arithmetic always wraps around
+                let op = match call.opcode.as_str() {
+                    "wrapping_add" => HintBinaryOp::Add,
+                    "wrapping_sub" => HintBinaryOp::Sub,
+                    "wrapping_mul" => HintBinaryOp::Mul,
+                    _ => {
+                        return Err(self.error(format!(
+                            "builtin function {} cannot be called from an unconstrained function",
+                            call.opcode
+                        )))
+                    }
+                };
+                let (lhs, typ) = self.single(&call.arguments[0])?;
+                let (rhs, _) = self.single(&call.arguments[1])?;
+                let destination = self.binary(op, numeric(&typ), lhs, rhs);
+                Ok(HintValue::Single(destination, typ))
+            }
+            Expression::CallLowLevel(call) => Err(self.error(format!(
+                "low level function {} cannot be called from an unconstrained function",
+                call.opcode
+            ))),
+            Expression::Let(let_expr) => {
+                let value = self.expression(&let_expr.expression)?;
+                let value = self.copy(&value)?;
+                self.variables.insert(let_expr.id, value);
+                Ok(HintValue::Unit)
+            }
+            Expression::Constrain(condition, location, _) => {
+                self.location = Some(*location);
+                let (condition, _) = self.single(condition)?;
+                self.assert(condition);
+                Ok(HintValue::Unit)
+            }
+            Expression::Assign(assign) => {
+                let value = self.expression(&assign.expression)?;
+                match &assign.lvalue {
+                    LValue::Index { array, index } => {
+                        let (base, len) = match self.lvalue(array) {
+                            HintValue::Array(base, len, _) => (base, len),
+                            _ => unreachable!("expected an array in lvalue {:?}", array),
+                        };
+                        let (index, _) = self.single(index)?;
+                        self.bounds_check(index, len);
+                        let source = value.registers()[0];
+                        self.push(HintOp::Store { base, index, source });
+                    }
+                    lvalue => {
+                        let destination = self.lvalue(lvalue);
+                        self.copy_into(&destination, &value);
+                    }
+                }
+                Ok(HintValue::Unit)
+            }
+            Expression::Semi(expr) => {
+                self.expression(expr)?;
+                Ok(HintValue::Unit)
+            }
+        }
+    }
+
+    fn lvalue(&mut self, lvalue: &LValue) -> HintValue {
+        match lvalue {
+            LValue::Ident(ident) => self.variables[&ident.id].clone(),
+            LValue::MemberAccess { object, field_index } => match self.lvalue(object) {
+                HintValue::Tuple(mut fields) => fields.swap_remove(*field_index),
+                _ => unreachable!("expected a tuple in lvalue {:?}", object),
+            },
+            LValue::Index { .. } => {
+                unreachable!("nested arrays are not supported in unconstrained functions")
+            }
+        }
+    }
+
+    //Calls are inlined into the hint program
+    fn call(&mut self, call: &Call) -> Result<HintValue, RuntimeError> {
+        let program = self.program;
+        let function = &program[call.func_id];
+        if self.call_stack.contains(&call.func_id) {
+            return Err(self.error(format!(
+                "recursive call to {} is not supported in unconstrained functions",
+                function.name
+            )));
+        }
+
+        let mut arguments = Vec::new();
+        for argument in &call.arguments {
+            arguments.extend(self.expression(argument)?.registers());
+        }
+        let mut parameters = Vec::new();
+        for (id, _, _, typ) in &function.parameters {
+            let value = self.allocate(typ)?;
+            parameters.extend(value.registers());
+            self.variables.insert(*id, value);
+        }
+        for (destination, source) in parameters.into_iter().zip(arguments) {
+            self.push(HintOp::Mov { destination, source });
+        }
+
+        self.call_stack.push(call.func_id);
+        let result = self.expression(&function.body)?;
+        self.call_stack.pop();
+        Ok(result)
+    }
+}
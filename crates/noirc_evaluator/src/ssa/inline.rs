@@ -375,7 +375,7 @@ impl node::Operation {
     ) {
         match self {
             //default way to handle arrays during inlining; we map arrays using the stack_frame
-            Operation::Binary(_) | Operation::Constrain(..) | Operation::Intrinsic(_,_)
+            Operation::Binary(_) | Operation::Constrain(..) | Operation::Intrinsic(_,_) | Operation::Hint(_,_) | Operation::Log { .. }
             => {
                 self.map_id_mut(|id| {
                     if let Some(a) = Memory::deref(ctx, id) {
@@ -64,6 +64,33 @@ fn get_instruction_max_operand(
             }
             get_max_value(ins, max_map)
         }
+        Operation::Binary(node::Binary { operator: BinaryOp::Shl, lhs, rhs }) => {
+            match ctx.get_as_constant(*rhs) {
+                Some(r_const) if is_oversized_shift(r_const, ins.res_type) => BigUint::zero(),
+                Some(r_const) => &max_map[lhs] << r_const.to_u128() as usize,
+                None => get_max_value(ins, max_map),
+            }
+        }
+        Operation::Binary(node::Binary { operator: BinaryOp::Shr, lhs, rhs })
+            if matches!(ins.res_type, ObjectType::Unsigned(_)) =>
+        {
+            match ctx.get_as_constant(*rhs) {
+                Some(r_const) if is_oversized_shift(r_const, ins.res_type) => BigUint::zero(),
+                Some(r_const) => &max_map[lhs] >> r_const.to_u128() as usize,
+                None => get_max_value(ins, max_map),
+            }
+        }
+        Operation::Cast(value_id) => {
+            let value_type = ctx.get_object_type(*value_id);
+            if matches!(value_type, ObjectType::Signed(_))
+                && ins.res_type.bits() > value_type.bits()
+            {
+                //sign extension may set the higher bits
+                ins.res_type.max_size()
+            } else {
+                get_max_value(ins, max_map)
+            }
+        }
         // Operation::Constrain(_) => {
         //ContrainOp::Eq :
         //TODO... we should update the max_map AFTER the truncate is processed (else it breaks it)
@@ -299,23 +326,34 @@ fn block_overflow(
             Operation::Binary(node::Binary { operator: BinaryOp::Shl, lhs, rhs }) => {
                 if let Some(r_const) = ctx.get_as_constant(rhs) {
                     let r_type = ctx[rhs].get_type();
-                    let rhs =
-                        ctx.get_or_create_const(FieldElement::from(2_i128).pow(&r_const), r_type);
+                    let rhs = if is_oversized_shift(r_const, ins.res_type) {
+                        ctx.zero_with_type(ins.res_type)
+                    } else {
+                        ctx.get_or_create_const(FieldElement::from(2_i128).pow(&r_const), r_type)
+                    };
                     ins.operation =
                         Operation::Binary(node::Binary { lhs, rhs, operator: BinaryOp::Mul });
                 }
             }
             Operation::Binary(node::Binary { operator: BinaryOp::Shr, lhs, rhs }) => {
-                if !matches!(ins.res_type, node::ObjectType::Unsigned(_)) {
-                    todo!("Right shift is only implemented for unsigned integers");
-                }
-                if let Some(r_const) = ctx.get_as_constant(rhs) {
-                    let r_type = ctx[rhs].get_type();
-                    let rhs =
-                        ctx.get_or_create_const(FieldElement::from(2_i128).pow(&r_const), r_type);
-                    //todo checks that 2^rhs does not overflow
-                    ins.operation =
-                        Operation::Binary(node::Binary { lhs, rhs, operator: BinaryOp::Udiv });
+                //signed right shifts are arithmetic shifts, they are kept and handled by the acir generation
+                if let (Some(r_const), ObjectType::Unsigned(_)) =
+                    (ctx.get_as_constant(rhs), ins.res_type)
+                {
+                    if is_oversized_shift(r_const, ins.res_type) {
+                        let zero = ctx.zero_with_type(ins.res_type);
+                        ins.operation = Operation::Binary(node::Binary {
+                            lhs,
+                            rhs: zero,
+                            operator: BinaryOp::Mul,
+                        });
+                    } else {
+                        let r_type = ctx[rhs].get_type();
+                        let rhs = ctx
+                            .get_or_create_const(FieldElement::from(2_i128).pow(&r_const), r_type);
+                        ins.operation =
+                            Operation::Binary(node::Binary { lhs, rhs, operator: BinaryOp::Udiv });
+                    }
                 }
             }
             Operation::Cast(value_id) => {
@@ -437,13 +475,18 @@ fn get_load_max(
     ctx.mem[array].max.clone() //return array max
 }
 
+//Shifting by the bit size of the type or more results in 0
+fn is_oversized_shift(shift: FieldElement, res_type: ObjectType) -> bool {
+    shift >= FieldElement::from(res_type.bits() as i128)
+}
+
 //Returns the max value of an operation from an upper bound of left and right hand sides
 //Function is used to check for overflows over the field size, this is why we use BigUint.
 fn get_max_value(ins: &Instruction, max_map: &mut HashMap<NodeId, BigUint>) -> BigUint {
     let max_value = match &ins.operation {
         Operation::Binary(binary) => get_binary_max_value(binary, ins.res_type, max_map),
         Operation::Not(_) => ins.res_type.max_size(),
-        Operation::Constrain(..) => BigUint::zero(),
+        Operation::Constrain(..) | Operation::Log { .. } => BigUint::zero(),
         //'a cast a' means we cast a into res_type of the instruction
         Operation::Cast(value_id) => {
             let type_max = ins.res_type.max_size();
@@ -477,6 +520,7 @@ fn get_max_value(ins: &Instruction, max_map: &mut HashMap<NodeId, BigUint>) -> B
             match opcode {
                 OPCODE::SHA256
                 | OPCODE::Blake2s
+                | OPCODE::Keccak256
                 | OPCODE::Pedersen
                 | OPCODE::FixedBaseScalarMul
                 | OPCODE::ToBits => BigUint::zero(), //pointers do not overflow
@@ -486,6 +530,14 @@ fn get_max_value(ins: &Instruction, max_map: &mut HashMap<NodeId, BigUint>) -> B
                 _ => todo!(),
             }
         }
+        Operation::Hint(..) => {
+            //the outputs of a hint are not constrained, so they can be anything
+            if let ObjectType::Pointer(_) = ins.res_type {
+                BigUint::zero()
+            } else {
+                ins.res_type.max_size()
+            }
+        }
     };
 
     if ins.res_type == ObjectType::NativeField {
@@ -508,8 +560,15 @@ fn get_binary_max_value(
     let rhs_max = &max_map[&binary.rhs];
 
     match &binary.operator {
+        //checked operations fail instead of overflowing, but the result of the signed ones is offset by 2^bits
+        //so that it is positive (see acir_gen::evaluate_checked)
+        BinaryOp::SafeAdd { .. } | BinaryOp::SafeSub { .. } | BinaryOp::SafeMul { .. } => {
+            match res_type {
+                ObjectType::Signed(bits) => (BigUint::one() << (bits + 1)) - BigUint::one(),
+                _ => res_type.max_size(),
+            }
+        }
         BinaryOp::Add => lhs_max + rhs_max,
-        BinaryOp::SafeAdd => todo!(),
         BinaryOp::Sub { .. } => {
             let r_mod = BigUint::one() << res_type.bits();
             let mut k = rhs_max / &r_mod;
@@ -519,13 +578,12 @@ fn get_binary_max_value(
             assert!(&k * &r_mod >= *rhs_max);
             lhs_max + k * r_mod
         }
-        BinaryOp::SafeSub { .. } => todo!(),
         BinaryOp::Mul => lhs_max * rhs_max,
-        BinaryOp::SafeMul => todo!(),
         BinaryOp::Udiv => lhs_max.clone(),
-        BinaryOp::Sdiv => todo!(),
+        //the result of a signed division is obtained by negating the unsigned division of the absolute values
+        //and 2^bits is the representation of -0
+        BinaryOp::Sdiv { .. } | BinaryOp::Srem { .. } => res_type.max_size() + BigUint::one(),
         BinaryOp::Urem => rhs_max - BigUint::one(),
-        BinaryOp::Srem => todo!(),
         BinaryOp::Div => todo!(),
         BinaryOp::Eq => BigUint::one(),
         BinaryOp::Ne => BigUint::one(),
@@ -535,6 +593,7 @@ fn get_binary_max_value(
         BinaryOp::Sle => BigUint::one(),
         BinaryOp::Lt => BigUint::one(),
         BinaryOp::Lte => BigUint::one(),
+        BinaryOp::BoundedLt { .. } => BigUint::one(),
         BinaryOp::And => {
             BigUint::from(2_u32).pow(u64::min(lhs_max.bits(), rhs_max.bits()) as u32)
                 - BigUint::one()
@@ -544,16 +603,10 @@ fn get_binary_max_value(
                 - BigUint::one()
         }
         BinaryOp::Assign => rhs_max.clone(),
-        BinaryOp::Shl => BigUint::min(
-            BigUint::from(2_u32).pow((lhs_max.bits() + 1) as u32) - BigUint::one(),
-            res_type.max_size(),
-        ),
-        BinaryOp::Shr => {
-            if lhs_max.bits() >= 1 {
-                BigUint::from(2_u32).pow((lhs_max.bits() - 1) as u32) - BigUint::one()
-            } else {
-                BigUint::zero()
-            }
-        }
+        //shifts by a constant amount are handled in get_instruction_max_operand
+        //a shift by a witness amount is reduced modulo 2^bits during acir generation
+        BinaryOp::Shl => res_type.max_size(),
+        BinaryOp::Shr if matches!(res_type, ObjectType::Signed(_)) => res_type.max_size(),
+        BinaryOp::Shr => lhs_max.clone(),
     }
 }
@@ -2,13 +2,17 @@ use super::acir_gen::InternalVar;
 use super::context::SsaContext;
 use super::node::{self, Node, NodeId};
 use acvm::FieldElement;
-use noirc_frontend::monomorphisation::ast::DefinitionId;
+use noirc_frontend::monomorphisation::ast::{DefinitionId, Type};
 use num_bigint::BigUint;
 use num_traits::ToPrimitive;
 
 use std::collections::HashMap;
 use std::convert::TryInto;
 
+/// Arrays of at least this many elements are accessed at witness indices through a permutation argument
+/// over their memory trace, instead of a multiplexer over all of their elements.
+pub const PERMUTATION_THRESHOLD: u32 = 128;
+
 #[derive(Default)]
 pub struct Memory {
     arrays: Vec<MemArray>,
@@ -116,6 +120,38 @@ impl Memory {
         let adr = self[array_id].absolute_adr(index);
         self.memory_map.get(&adr)
     }
+
+    /// Number of elements of the flat array which stores a value of the given type.
+    /// Nested arrays are laid out one after the other, e.g [[Field; 4]; 8] is stored in 32 elements
+    /// and the element [i][j] is at position 4*i+j.
+    pub fn flat_len(typ: &Type) -> u32 {
+        match typ {
+            Type::Array(len, element) => *len as u32 * Memory::flat_len(element),
+            _ => 1,
+        }
+    }
+
+    /// Values of tuple types are made of one value per field, and arrays of tuples are stored
+    /// as one array per field of the tuple.
+    /// Returns the types of these values, e.g [[Field; 2]; 3] and [u8; 3] for [([Field; 2], u8); 3],
+    /// or None if the type is neither a tuple nor an array of tuples.
+    pub fn fields(typ: &Type) -> Option<Vec<Type>> {
+        match typ {
+            Type::Tuple(fields) => Some(fields.clone()),
+            Type::Array(len, element) => Memory::fields(element).map(|fields| {
+                fields.into_iter().map(|field| Type::Array(*len, Box::new(field))).collect()
+            }),
+            _ => None,
+        }
+    }
+
+    /// The types of the values which represent a value of the given type, in order
+    pub fn flatten_type(typ: &Type) -> Vec<Type> {
+        match Memory::fields(typ) {
+            Some(fields) => fields.iter().flat_map(Memory::flatten_type).collect(),
+            None => vec![typ.clone()],
+        }
+    }
 }
 
 impl std::ops::Index<ArrayId> for Memory {
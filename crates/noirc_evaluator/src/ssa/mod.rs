@@ -6,6 +6,7 @@ pub mod conditional;
 pub mod context;
 pub mod flatten;
 pub mod function;
+pub mod hint_gen;
 pub mod inline;
 pub mod integer;
 pub mod mem;
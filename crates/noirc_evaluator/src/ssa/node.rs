@@ -9,10 +9,10 @@ use noirc_errors::Location;
 use noirc_frontend::monomorphisation::ast::{DefinitionId, FuncId, Type};
 use noirc_frontend::util::vecmap;
 use noirc_frontend::{BinaryOpKind, Signedness};
-use num_bigint::BigUint;
-use num_traits::{FromPrimitive, One};
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::{FromPrimitive, One, Zero};
 use std::ops::{Add, Mul, Sub};
-use std::ops::{BitAnd, BitOr, BitXor, Shl, Shr};
+use std::ops::{BitAnd, BitOr, BitXor};
 
 use super::block::BlockId;
 use super::conditional;
@@ -255,6 +255,64 @@ impl ObjectType {
         }
     }
 
+    //interprets the two's complement representation of a signed integer constant
+    pub fn to_signed(&self, f: FieldElement) -> i128 {
+        let bits = self.bits();
+        let x = self.field_to_type(f).to_u128();
+        if bits > 0 && x >> (bits - 1) == 1 {
+            x as i128 - (1_i128 << bits)
+        } else {
+            x as i128
+        }
+    }
+
+    //the two's complement representation of x, as a constant of this type
+    pub fn from_signed(&self, x: i128) -> FieldElement {
+        match self {
+            ObjectType::NativeField => FieldElement::from(x),
+            _ => {
+                assert!(self.bits() < 128);
+                FieldElement::from((x as u128) & ((1_u128 << self.bits()) - 1))
+            }
+        }
+    }
+
+    //the value of an integer constant, interpreting the two's complement representation of signed integers.
+    //Big integers are used so that constants of 128 bits types can be handled
+    fn integer_value(&self, f: FieldElement) -> BigInt {
+        let bits = self.bits();
+        let modulus = BigInt::one() << bits;
+        let x = if f.fits_in_u128() {
+            BigInt::from(f.to_u128())
+        } else {
+            //a negative field element
+            -BigInt::from_bytes_be(Sign::Plus, &(-f).to_bytes())
+        };
+        let x = ((x % &modulus) + &modulus) % &modulus;
+        if matches!(self, ObjectType::Signed(_)) && bits > 0 && x >= &modulus >> 1 {
+            x - modulus
+        } else {
+            x
+        }
+    }
+
+    //the representation of x as a constant of this type, or None if x does not fit in the type
+    fn integer_constant(&self, x: BigInt) -> Option<FieldElement> {
+        let bits = self.bits();
+        let (min, max) = match self {
+            ObjectType::Signed(_) if bits > 0 => {
+                let half = BigInt::one() << (bits - 1);
+                (-half.clone(), half)
+            }
+            _ => (BigInt::zero(), BigInt::one() << bits),
+        };
+        if x < min || x >= max {
+            return None;
+        }
+        let x = if x.sign() == Sign::Minus { x + (BigInt::one() << bits) } else { x };
+        Some(FieldElement::from_be_bytes_reduce(&x.to_bytes_be().1))
+    }
+
     pub fn type_to_pointer(&self) -> ArrayId {
         match self {
             ObjectType::Pointer(a) => *a,
@@ -268,7 +326,18 @@ impl ObjectType {
                 unreachable!()
             }
             ObjectType::NativeField => f,
-            ObjectType::Signed(_) => todo!(),
+            ObjectType::Signed(_) => {
+                assert!(self.bits() < 128);
+                let type_modulo = 1_u128 << self.bits();
+                match f.try_into_u128() {
+                    Some(x) => FieldElement::from(x % type_modulo),
+                    //a negative field element, -x is mapped to its two's complement 2^bits - x
+                    None => {
+                        let neg = (-f).to_u128() % type_modulo;
+                        FieldElement::from((type_modulo - neg) % type_modulo)
+                    }
+                }
+            }
             _ => {
                 assert!(self.bits() < 128);
                 FieldElement::from(f.to_u128() % (1_u128 << self.bits()))
@@ -388,6 +457,8 @@ impl Instruction {
             Operation::Load { .. } => false,
             Operation::Store { .. } => true,
             Operation::Intrinsic(_, _) => true, //TODO to check
+            Operation::Hint(_, _) => true, //hint programs work on the actual values of their inputs
+            Operation::Log { .. } => true,
             Operation::Call { .. } => false, //return values are in the return statment, should we truncate function arguments? probably but not lhs and rhs anyways.
             Operation::Return(_) => true,
             Operation::Result { .. } => false,
@@ -413,7 +484,12 @@ impl Instruction {
             }
             Operation::Cast(value) => {
                 if let Some(l_const) = eval_fn(ctx, *value)?.into_const_value() {
-                    if self.res_type == ObjectType::NativeField {
+                    let value_type = ctx.get_object_type(*value);
+                    if matches!(value_type, ObjectType::Signed(_)) {
+                        //sign extension
+                        let l_const = self.res_type.from_signed(value_type.to_signed(l_const));
+                        return Ok(NodeEval::Const(l_const, self.res_type));
+                    } else if self.res_type == ObjectType::NativeField {
                         return Ok(NodeEval::Const(l_const, self.res_type));
                     } else if let Some(l_const) = l_const.try_into_u128() {
                         return Ok(NodeEval::Const(
@@ -430,16 +506,19 @@ impl Instruction {
                     return Ok(NodeEval::Const(FieldElement::from((!l) & max), self.res_type));
                 }
             }
-            Operation::Constrain(value, location) => {
+            Operation::Constrain(value, location, message) => {
                 if let Some(obj) = eval_fn(ctx, *value)?.into_const_value() {
                     if obj.is_one() {
                         // Delete the constrain, it is always true
                         return Ok(NodeEval::VarOrInstruction(NodeId::dummy()));
                     } else if obj.is_zero() {
-                        return Err(RuntimeErrorKind::UnstructuredError {
-                            message: "Constraint is always false".into(),
-                        }
-                        .add_location(*location));
+                        let message = match message {
+                            Some(message) => format!("Constraint is always false: {}", message),
+                            None => "Constraint is always false".into(),
+                        };
+                        return Err(
+                            RuntimeErrorKind::UnstructuredError { message }.add_location(*location)
+                        );
                     }
                 }
             }
@@ -508,7 +587,7 @@ pub enum Operation {
     }, //truncate
 
     Not(NodeId), //(!) Bitwise Not
-    Constrain(NodeId, Location),
+    Constrain(NodeId, Location, /*message:*/ Option<String>),
 
     //control flow
     Jne(NodeId, BlockId), //jump on not equal
@@ -547,6 +626,12 @@ pub enum Operation {
     },
 
     Intrinsic(OPCODE, Vec<NodeId>), //Custom implementation of usefull primitives which are more performant with Aztec backend
+    Hint(FuncId, Vec<NodeId>), //Call to an unconstrained function, which is executed by the ACVM as a hint program
+    Log {
+        format: String,
+        arguments: Vec<NodeId>,
+        predicate: Option<NodeId>,
+    }, //Prints the arguments during witness generation, when the predicate holds
 
     Nop, // no op
 }
@@ -572,6 +657,7 @@ pub enum Opcode {
     Sle,
     Lt,
     Lte,
+    BoundedLt,
     And,
     Or,
     Xor,
@@ -598,6 +684,8 @@ pub enum Opcode {
     Load(ArrayId),
     Store(ArrayId),
     Intrinsic(OPCODE), //Custom implementation of usefull primitives which are more performant with Aztec backend
+    Hint(FuncId),      //Call to an unconstrained function
+    Log,               //Print values during witness generation
     Nop,               // no op
 }
 
@@ -611,24 +699,30 @@ pub struct Binary {
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub enum BinaryOp {
     Add, //(+)
-    #[allow(dead_code)]
-    SafeAdd, //(+) safe addition
+    SafeAdd {
+        location: Location,
+    }, //(+) safe addition, fails on overflow
     Sub {
         max_rhs_value: BigUint,
     }, //(-)
-    #[allow(dead_code)]
     SafeSub {
         max_rhs_value: BigUint,
-    }, //(-) safe subtraction
+        location: Location,
+    }, //(-) safe subtraction, fails on overflow
     Mul, //(*)
-    #[allow(dead_code)]
-    SafeMul, //(*) safe multiplication
+    SafeMul {
+        location: Location,
+    }, //(*) safe multiplication, fails on overflow
     Udiv, //(/) unsigned division
-    Sdiv, //(/) signed division
+    Sdiv {
+        location: Location,
+    }, //(/) signed division
     #[allow(dead_code)]
     Urem, //(%) modulo; remainder of unsigned division
     #[allow(dead_code)]
-    Srem, //(%) remainder of signed division
+    Srem {
+        location: Location,
+    }, //(%) remainder of signed division
     Div, //(/) field division
     Eq,  //(==) equal
     Ne,  //(!=) not equal
@@ -638,6 +732,10 @@ pub enum BinaryOp {
     Sle, //(<=) signed less or equal
     Lt,  //(<) field less
     Lte, //(<=) field less or equal
+    BoundedLt {
+        bit_size: u32,
+        location: Location,
+    }, //(<) field less, of operands which are constrained to be less than 2^bit_size
     And, //(&) Bitwise And
     Or,  //(|) Bitwise Or
     Xor, //(^) Bitwise Xor
@@ -652,11 +750,33 @@ impl Binary {
         Binary { operator, lhs, rhs }
     }
 
+    //Integer additions, subtractions and multiplications are checked for overflow when checked is set
     pub fn from_ast(
         op_kind: BinaryOpKind,
         op_type: ObjectType,
         lhs: NodeId,
         rhs: NodeId,
+        location: Location,
+        checked: bool,
+    ) -> Binary {
+        let checked = checked && matches!(op_type, ObjectType::Unsigned(_) | ObjectType::Signed(_));
+        let operator = match (op_kind, checked) {
+            (BinaryOpKind::Add, true) => BinaryOp::SafeAdd { location },
+            (BinaryOpKind::Subtract, true) => {
+                BinaryOp::SafeSub { max_rhs_value: BigUint::from_u8(0).unwrap(), location }
+            }
+            (BinaryOpKind::Multiply, true) => BinaryOp::SafeMul { location },
+            _ => return Binary::from_ast_unchecked(op_kind, op_type, lhs, rhs, location),
+        };
+        Binary::new(operator, lhs, rhs)
+    }
+
+    fn from_ast_unchecked(
+        op_kind: BinaryOpKind,
+        op_type: ObjectType,
+        lhs: NodeId,
+        rhs: NodeId,
+        location: Location,
     ) -> Binary {
         let operator = match op_kind {
             BinaryOpKind::Add => BinaryOp::Add,
@@ -670,11 +790,21 @@ impl Binary {
             BinaryOpKind::Divide => {
                 let num_type: NumericType = op_type.into();
                 match num_type {
-                    NumericType::Signed(_) => BinaryOp::Sdiv,
+                    NumericType::Signed(_) => BinaryOp::Sdiv { location },
                     NumericType::Unsigned(_) => BinaryOp::Udiv,
                     NumericType::NativeField => BinaryOp::Div,
                 }
             }
+            BinaryOpKind::Modulo => {
+                let num_type: NumericType = op_type.into();
+                match num_type {
+                    NumericType::Signed(_) => BinaryOp::Srem { location },
+                    NumericType::Unsigned(_) => BinaryOp::Urem,
+                    NumericType::NativeField => {
+                        unreachable!("Modulo operation is not defined for field elements")
+                    }
+                }
+            }
             BinaryOpKind::Less => {
                 let num_type: NumericType = op_type.into();
                 match num_type {
@@ -736,7 +866,10 @@ impl Binary {
         let r_is_zero = rhs.map_or(false, |x| x.is_zero());
 
         match &self.operator {
-            BinaryOp::Add | BinaryOp::SafeAdd => {
+            BinaryOp::SafeAdd { .. } | BinaryOp::SafeSub { .. } | BinaryOp::SafeMul { .. } => {
+                return Ok(self.evaluate_checked(l_eval, r_eval, id, res_type));
+            }
+            BinaryOp::Add => {
                 if l_is_zero {
                     return Ok(r_eval);
                 } else if r_is_zero {
@@ -750,7 +883,7 @@ impl Binary {
                 //so it is probably not worth it.
                 //same for x+x vs 2*x
             }
-            BinaryOp::Sub { .. } | BinaryOp::SafeSub { .. } => {
+            BinaryOp::Sub { .. } => {
                 if r_is_zero {
                     return Ok(l_eval);
                 }
@@ -762,7 +895,7 @@ impl Binary {
                     return Ok(wrapping(lhs, rhs, res_type, u128::wrapping_sub, Sub::sub));
                 }
             }
-            BinaryOp::Mul | BinaryOp::SafeMul => {
+            BinaryOp::Mul => {
                 let l_is_one = lhs.map_or(false, |x| x.is_one());
                 let r_is_one = rhs.map_or(false, |x| x.is_one());
                 assert_eq!(l_type, r_type);
@@ -801,26 +934,43 @@ impl Binary {
                     return Ok(NodeEval::Const(lhs / rhs, res_type));
                 }
             }
-            BinaryOp::Sdiv => {
+            BinaryOp::Sdiv { location } => {
                 if r_is_zero {
-                    todo!("Panic - division by zero");
+                    return Err(RuntimeErrorKind::DivisionByZero.add_location(*location));
                 } else if l_is_zero {
                     return Ok(l_eval); //TODO should we ensure rhs != 0 ???
                 }
-                //constant folding...TODO
-                else if lhs.is_some() && rhs.is_some() {
-                    todo!("Constant folding for division");
+                //constant folding, the quotient is rounded toward zero
+                else if let (Some(lhs), Some(rhs)) = (lhs, rhs) {
+                    let lhs = res_type.to_signed(lhs);
+                    let rhs = res_type.to_signed(rhs);
+                    return Ok(NodeEval::Const(res_type.from_signed(lhs / rhs), res_type));
                 }
             }
-            BinaryOp::Urem | BinaryOp::Srem => {
+            BinaryOp::Urem => {
                 if r_is_zero {
                     todo!("Panic - division by zero");
                 } else if l_is_zero {
                     return Ok(l_eval); //TODO what is the correct result?
                 }
-                //constant folding - TODO
-                else if lhs.is_some() && rhs.is_some() {
-                    todo!("divide lhs/rhs but take sign into account");
+                //constant folding
+                else if let (Some(lhs), Some(rhs)) = (lhs, rhs) {
+                    let lhs = res_type.field_to_type(lhs).to_u128();
+                    let rhs = res_type.field_to_type(rhs).to_u128();
+                    return Ok(NodeEval::Const(FieldElement::from(lhs % rhs), res_type));
+                }
+            }
+            BinaryOp::Srem { location } => {
+                if r_is_zero {
+                    return Err(RuntimeErrorKind::DivisionByZero.add_location(*location));
+                } else if l_is_zero {
+                    return Ok(l_eval); //TODO what is the correct result?
+                }
+                //constant folding, the remainder has the sign of lhs
+                else if let (Some(lhs), Some(rhs)) = (lhs, rhs) {
+                    let lhs = res_type.to_signed(lhs);
+                    let rhs = res_type.to_signed(rhs);
+                    return Ok(NodeEval::Const(res_type.from_signed(lhs % rhs), res_type));
                 }
             }
             BinaryOp::Ult => {
@@ -843,8 +993,26 @@ impl Binary {
                     return Ok(NodeEval::Const(res, ObjectType::Boolean));
                 }
             }
-            BinaryOp::Slt => (),
-            BinaryOp::Sle => (),
+            BinaryOp::Slt => {
+                if let (Some(lhs), Some(rhs)) = (lhs, rhs) {
+                    let res = if l_type.to_signed(lhs) < l_type.to_signed(rhs) {
+                        FieldElement::one()
+                    } else {
+                        FieldElement::zero()
+                    };
+                    return Ok(NodeEval::Const(res, ObjectType::Boolean));
+                }
+            }
+            BinaryOp::Sle => {
+                if let (Some(lhs), Some(rhs)) = (lhs, rhs) {
+                    let res = if l_type.to_signed(lhs) <= l_type.to_signed(rhs) {
+                        FieldElement::one()
+                    } else {
+                        FieldElement::zero()
+                    };
+                    return Ok(NodeEval::Const(res, ObjectType::Boolean));
+                }
+            }
             BinaryOp::Lt => {
                 if r_is_zero {
                     return Ok(NodeEval::Const(FieldElement::zero(), ObjectType::Boolean));
@@ -863,6 +1031,18 @@ impl Binary {
                     return Ok(NodeEval::Const(res, ObjectType::Boolean));
                 }
             }
+            BinaryOp::BoundedLt { bit_size, location } => {
+                if let Some(c) = lhs.into_iter().chain(rhs).find(|c| c.num_bits() > *bit_size) {
+                    let message = format!("the constant {} does not fit in {} bits", c, bit_size);
+                    return Err(
+                        RuntimeErrorKind::UnstructuredError { message }.add_location(*location)
+                    );
+                }
+                if let (Some(lhs), Some(rhs)) = (lhs, rhs) {
+                    let res = if lhs < rhs { FieldElement::one() } else { FieldElement::zero() };
+                    return Ok(NodeEval::Const(res, ObjectType::Boolean));
+                }
+            }
             BinaryOp::Eq => {
                 if self.lhs == self.rhs {
                     return Ok(NodeEval::Const(FieldElement::one(), ObjectType::Boolean));
@@ -927,7 +1107,8 @@ impl Binary {
                     return Ok(l_eval);
                 }
                 if let (Some(lhs), Some(rhs)) = (lhs, rhs) {
-                    return Ok(wrapping(lhs, rhs, res_type, u128::shl, field_op_not_allowed));
+                    let shl = |l: u128, r: u128| if r < 128 { l << r } else { 0 };
+                    return Ok(wrapping(lhs, rhs, res_type, shl, field_op_not_allowed));
                 }
             }
             BinaryOp::Shr => {
@@ -938,7 +1119,14 @@ impl Binary {
                     return Ok(l_eval);
                 }
                 if let (Some(lhs), Some(rhs)) = (lhs, rhs) {
-                    return Ok(wrapping(lhs, rhs, res_type, u128::shr, field_op_not_allowed));
+                    if let ObjectType::Signed(_) = res_type {
+                        //arithmetic shift, the sign bit is propagated
+                        let shift = rhs.to_u128().min(127) as u32;
+                        let res = res_type.to_signed(lhs) >> shift;
+                        return Ok(NodeEval::Const(res_type.from_signed(res), res_type));
+                    }
+                    let shr = |l: u128, r: u128| if r < 128 { l >> r } else { 0 };
+                    return Ok(wrapping(lhs, rhs, res_type, shr, field_op_not_allowed));
                 }
             }
             BinaryOp::Assign => (),
@@ -946,18 +1134,55 @@ impl Binary {
         Ok(NodeEval::VarOrInstruction(id))
     }
 
+    //Simplifies checked operations which cannot overflow. Operations on constants which overflow are kept
+    //so that they fail when the circuit is solved.
+    fn evaluate_checked(
+        &self,
+        l_eval: NodeEval,
+        r_eval: NodeEval,
+        id: NodeId,
+        res_type: ObjectType,
+    ) -> NodeEval {
+        let lhs = l_eval.into_const_value();
+        let rhs = r_eval.into_const_value();
+        let is_zero = |x: Option<FieldElement>| x.map_or(false, |x| x.is_zero());
+        let is_one = |x: Option<FieldElement>| x.map_or(false, |x| x.is_one());
+
+        match &self.operator {
+            BinaryOp::SafeAdd { .. } if is_zero(lhs) => return r_eval,
+            BinaryOp::SafeAdd { .. } | BinaryOp::SafeSub { .. } if is_zero(rhs) => return l_eval,
+            BinaryOp::SafeMul { .. } if is_zero(lhs) || is_one(rhs) => return l_eval,
+            BinaryOp::SafeMul { .. } if is_zero(rhs) || is_one(lhs) => return r_eval,
+            _ => (),
+        }
+
+        //constants are folded with big integers, so that the exact result is known for any bit size
+        if let (Some(lhs), Some(rhs)) = (lhs, rhs) {
+            let (l, r) = (res_type.integer_value(lhs), res_type.integer_value(rhs));
+            let exact = match &self.operator {
+                BinaryOp::SafeAdd { .. } => l + r,
+                BinaryOp::SafeSub { .. } => l - r,
+                _ => l * r,
+            };
+            if let Some(x) = res_type.integer_constant(exact) {
+                return NodeEval::Const(x, res_type);
+            }
+        }
+        NodeEval::VarOrInstruction(id)
+    }
+
     fn truncate_required(&self) -> bool {
         match &self.operator {
             BinaryOp::Add => false,
-            BinaryOp::SafeAdd => false,
+            BinaryOp::SafeAdd { .. } => true,
             BinaryOp::Sub { .. } => false,
-            BinaryOp::SafeSub { .. } => false,
+            BinaryOp::SafeSub { .. } => true,
             BinaryOp::Mul => false,
-            BinaryOp::SafeMul => false,
+            BinaryOp::SafeMul { .. } => true,
             BinaryOp::Udiv => true,
-            BinaryOp::Sdiv => true,
+            BinaryOp::Sdiv { .. } => true,
             BinaryOp::Urem => true,
-            BinaryOp::Srem => true,
+            BinaryOp::Srem { .. } => true,
             BinaryOp::Div => false,
             BinaryOp::Eq => true,
             BinaryOp::Ne => true,
@@ -967,6 +1192,7 @@ impl Binary {
             BinaryOp::Sle => true,
             BinaryOp::Lt => true,
             BinaryOp::Lte => true,
+            BinaryOp::BoundedLt { .. } => true,
             BinaryOp::And => true,
             BinaryOp::Or => true,
             BinaryOp::Xor => true,
@@ -979,15 +1205,15 @@ impl Binary {
     pub fn opcode(&self) -> Opcode {
         match &self.operator {
             BinaryOp::Add => Opcode::Add,
-            BinaryOp::SafeAdd => Opcode::SafeAdd,
+            BinaryOp::SafeAdd { .. } => Opcode::SafeAdd,
             BinaryOp::Sub { .. } => Opcode::Sub,
             BinaryOp::SafeSub { .. } => Opcode::SafeSub,
             BinaryOp::Mul => Opcode::Mul,
-            BinaryOp::SafeMul => Opcode::SafeMul,
+            BinaryOp::SafeMul { .. } => Opcode::SafeMul,
             BinaryOp::Udiv => Opcode::Udiv,
-            BinaryOp::Sdiv => Opcode::Sdiv,
+            BinaryOp::Sdiv { .. } => Opcode::Sdiv,
             BinaryOp::Urem => Opcode::Urem,
-            BinaryOp::Srem => Opcode::Srem,
+            BinaryOp::Srem { .. } => Opcode::Srem,
             BinaryOp::Div => Opcode::Div,
             BinaryOp::Eq => Opcode::Eq,
             BinaryOp::Ne => Opcode::Ne,
@@ -997,6 +1223,7 @@ impl Binary {
             BinaryOp::Sle => Opcode::Sle,
             BinaryOp::Lt => Opcode::Lt,
             BinaryOp::Lte => Opcode::Lte,
+            BinaryOp::BoundedLt { .. } => Opcode::BoundedLt,
             BinaryOp::And => Opcode::And,
             BinaryOp::Or => Opcode::Or,
             BinaryOp::Xor => Opcode::Xor,
@@ -1018,8 +1245,8 @@ fn wrapping(
 ) -> NodeEval {
     if res_type != ObjectType::NativeField {
         let type_modulo = 1_u128 << res_type.bits();
-        let lhs = lhs.to_u128() % type_modulo;
-        let rhs = rhs.to_u128() % type_modulo;
+        let lhs = res_type.field_to_type(lhs).to_u128();
+        let rhs = res_type.field_to_type(rhs).to_u128();
         let mut x = u128_op(lhs, rhs);
         x %= type_modulo;
         NodeEval::from_u128(x, res_type)
@@ -1057,7 +1284,7 @@ impl Operation {
                 Truncate { value: f(*value), bit_size: *bit_size, max_bit_size: *max_bit_size }
             }
             Not(id) => Not(f(*id)),
-            Constrain(id, loc) => Constrain(f(*id), *loc),
+            Constrain(id, loc, message) => Constrain(f(*id), *loc, message.clone()),
             Jne(id, block) => Jne(f(*id), *block),
             Jeq(id, block) => Jeq(f(*id), *block),
             Jmp(block) => Jmp(*block),
@@ -1073,6 +1300,12 @@ impl Operation {
                 Store { array_id: *array, index: f(*index), value: f(*value) }
             }
             Intrinsic(i, args) => Intrinsic(*i, vecmap(args.iter().copied(), f)),
+            Hint(func_id, args) => Hint(*func_id, vecmap(args.iter().copied(), f)),
+            Log { format, arguments, predicate } => Log {
+                format: format.clone(),
+                arguments: vecmap(arguments.iter().copied(), &mut f),
+                predicate: predicate.map(f),
+            },
             Nop => Nop,
             Call { func_id, arguments, returned_arrays, predicate } => Call {
                 func_id: *func_id,
@@ -1118,11 +1351,19 @@ impl Operation {
                 *index = f(*index);
                 *value = f(*value);
             }
-            Intrinsic(_, args) => {
+            Intrinsic(_, args) | Hint(_, args) => {
                 for arg in args {
                     *arg = f(*arg);
                 }
             }
+            Log { arguments, predicate, .. } => {
+                for arg in arguments {
+                    *arg = f(*arg);
+                }
+                if let Some(predicate) = predicate {
+                    *predicate = f(*predicate);
+                }
+            }
             Nop => (),
             Call { arguments, .. } => {
                 for arg in arguments {
@@ -1171,7 +1412,11 @@ impl Operation {
                 f(*index);
                 f(*value);
             }
-            Intrinsic(_, args) => args.iter().copied().for_each(f),
+            Intrinsic(_, args) | Hint(_, args) => args.iter().copied().for_each(f),
+            Log { arguments, predicate, .. } => {
+                arguments.iter().copied().for_each(&mut f);
+                predicate.iter().copied().for_each(f);
+            }
             Nop => (),
             Call { arguments, .. } => arguments.iter().copied().for_each(f),
             Return(values) => values.iter().copied().for_each(f),
@@ -1199,6 +1444,8 @@ impl Operation {
             Operation::Load { array_id, .. } => Opcode::Load(*array_id),
             Operation::Store { array_id, .. } => Opcode::Store(*array_id),
             Operation::Intrinsic(opcode, _) => Opcode::Intrinsic(*opcode),
+            Operation::Hint(func_id, _) => Opcode::Hint(*func_id),
+            Operation::Log { .. } => Opcode::Log,
             Operation::Nop => Opcode::Nop,
         }
     }
@@ -1209,9 +1456,9 @@ impl BinaryOp {
         matches!(
             self,
             BinaryOp::Add
-                | BinaryOp::SafeAdd
+                | BinaryOp::SafeAdd { .. }
                 | BinaryOp::Mul
-                | BinaryOp::SafeMul
+                | BinaryOp::SafeMul { .. }
                 | BinaryOp::And
                 | BinaryOp::Or
                 | BinaryOp::Xor
@@ -306,7 +306,19 @@ fn cse_block_with_anchor(
                     new_list.push(*ins_id);
                 }
                 Operation::Return(..) => new_list.push(*ins_id),
-                Operation::Intrinsic(_, args) => {
+                Operation::Log { arguments, .. } => {
+                    //Add dummy load for array arguments, but no CSE because every log is printed
+                    for arg in arguments {
+                        if let Some(obj) = ctx.try_get_node(*arg) {
+                            if let ObjectType::Pointer(a) = obj.get_type() {
+                                let id = ctx.get_dummy_load(a);
+                                anchor.push_mem_instruction(ctx, id);
+                            }
+                        }
+                    }
+                    new_list.push(*ins_id);
+                }
+                Operation::Intrinsic(_, args) | Operation::Hint(_, args) => {
                     //Add dunmmy load for function arguments and enable CSE only if no array in argument
                     let mut activate_cse = true;
                     for arg in args {
@@ -192,6 +192,7 @@ pub enum BinaryOpKind {
     Subtract,
     Multiply,
     Divide,
+    Modulo,
     Equal,
     NotEqual,
     Less,
@@ -227,6 +228,7 @@ impl BinaryOpKind {
             BinaryOpKind::Subtract => "-",
             BinaryOpKind::Multiply => "*",
             BinaryOpKind::Divide => "/",
+            BinaryOpKind::Modulo => "%",
             BinaryOpKind::Equal => "==",
             BinaryOpKind::NotEqual => "!=",
             BinaryOpKind::Less => "<",
@@ -247,6 +249,7 @@ impl BinaryOpKind {
             BinaryOpKind::Subtract => Token::Minus,
             BinaryOpKind::Multiply => Token::Star,
             BinaryOpKind::Divide => Token::Slash,
+            BinaryOpKind::Modulo => Token::Percent,
             BinaryOpKind::Equal => Token::Equal,
             BinaryOpKind::NotEqual => Token::NotEqual,
             BinaryOpKind::Less => Token::Less,
@@ -274,6 +277,7 @@ impl From<&Token> for Option<BinaryOpKind> {
             Token::Minus => BinaryOpKind::Subtract,
             Token::Star => BinaryOpKind::Multiply,
             Token::Slash => BinaryOpKind::Divide,
+            Token::Percent => BinaryOpKind::Modulo,
             Token::Equal => BinaryOpKind::Equal,
             Token::NotEqual => BinaryOpKind::NotEqual,
             Token::Less => BinaryOpKind::Less,
@@ -342,6 +346,7 @@ pub struct IfExpression {
 pub struct FunctionDefinition {
     pub name: Ident,
     pub attribute: Option<Attribute>, // XXX: Currently we only have one attribute defined. If more attributes are needed per function, we can make this a vector and make attribute definition more expressive
+    pub is_unconstrained: bool,
     pub generics: Vec<Ident>,
     pub parameters: Vec<(Pattern, UnresolvedType, noirc_abi::AbiFEType)>,
     pub body: BlockExpression,
@@ -536,6 +541,7 @@ impl Display for BinaryOpKind {
             BinaryOpKind::Subtract => write!(f, "-"),
             BinaryOpKind::Multiply => write!(f, "*"),
             BinaryOpKind::Divide => write!(f, "/"),
+            BinaryOpKind::Modulo => write!(f, "%"),
             BinaryOpKind::Equal => write!(f, "=="),
             BinaryOpKind::NotEqual => write!(f, "!="),
             BinaryOpKind::Less => write!(f, "<"),
@@ -581,6 +587,10 @@ impl Display for FunctionDefinition {
             format!("{}: {} {}", name, visibility, r#type)
         });
 
+        if self.is_unconstrained {
+            write!(f, "unconstrained ")?;
+        }
+
         write!(
             f,
             "fn {}({}) -> {} {}",
@@ -76,7 +76,8 @@ impl From<FunctionDefinition> for NoirFunction {
         let kind = match fd.attribute {
             Some(Attribute::Builtin(_)) => FunctionKind::Builtin,
             Some(Attribute::Foreign(_)) => FunctionKind::LowLevel,
-            None => FunctionKind::Normal,
+            // Rejected by the parser, path attributes are only allowed on module declarations
+            Some(Attribute::Path(_)) | None => FunctionKind::Normal,
         };
 
         NoirFunction { def: fd, kind }
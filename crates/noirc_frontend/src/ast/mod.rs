@@ -4,6 +4,7 @@ mod expression;
 mod function;
 mod statement;
 mod structure;
+mod type_alias;
 
 pub use expression::*;
 pub use function::*;
@@ -11,6 +12,7 @@ pub use function::*;
 use noirc_errors::Span;
 pub use statement::*;
 pub use structure::*;
+pub use type_alias::*;
 
 use crate::{token::IntType, util::vecmap, IsConst};
 
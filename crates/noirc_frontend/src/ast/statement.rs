@@ -312,7 +312,7 @@ pub enum LValue {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub struct ConstrainStatement(pub Expression);
+pub struct ConstrainStatement(pub Expression, /*message:*/ pub Option<String>);
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Pattern {
@@ -358,7 +358,11 @@ impl Display for LetStatement {
 
 impl Display for ConstrainStatement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "constrain {}", self.0)
+        write!(f, "constrain {}", self.0)?;
+        if let Some(message) = &self.1 {
+            write!(f, ", {:?}", message)?;
+        }
+        Ok(())
     }
 }
 
@@ -1,6 +1,7 @@
 use std::fmt::Display;
 
-use crate::{Ident, NoirFunction, Path, UnresolvedType};
+use crate::util::vecmap;
+use crate::{Ident, NoirFunction, UnresolvedType};
 use noirc_errors::Span;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -24,7 +25,11 @@ impl NoirStruct {
 
 #[derive(Clone, Debug)]
 pub struct NoirImpl {
-    pub type_path: Path,
+    /// The type the methods are implemented on. This is either a path to a struct
+    /// or one of the builtin types, e.g. `impl<T> [T]` or `impl Field`
+    pub object_type: UnresolvedType,
+    pub type_span: Span,
+    pub generics: Vec<Ident>,
     pub methods: Vec<NoirFunction>,
 }
 
@@ -42,7 +47,12 @@ impl Display for NoirStruct {
 
 impl Display for NoirImpl {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "impl {} {{", self.type_path)?;
+        if self.generics.is_empty() {
+            writeln!(f, "impl {} {{", self.object_type)?;
+        } else {
+            let generics = vecmap(&self.generics, ToString::to_string);
+            writeln!(f, "impl<{}> {} {{", generics.join(", "), self.object_type)?;
+        }
 
         for method in self.methods.iter() {
             let method = method.to_string();
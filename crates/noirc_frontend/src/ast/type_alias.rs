@@ -0,0 +1,35 @@
+use crate::util::vecmap;
+use crate::{Ident, UnresolvedType};
+use noirc_errors::Span;
+use std::fmt::Display;
+
+/// `type Name<Generics> = Type;`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NoirTypeAlias {
+    pub name: Ident,
+    pub generics: Vec<Ident>,
+    pub typ: UnresolvedType,
+    pub span: Span,
+}
+
+impl NoirTypeAlias {
+    pub fn new(
+        name: Ident,
+        generics: Vec<Ident>,
+        typ: UnresolvedType,
+        span: Span,
+    ) -> NoirTypeAlias {
+        NoirTypeAlias { name, generics, typ, span }
+    }
+}
+
+impl Display for NoirTypeAlias {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.generics.is_empty() {
+            write!(f, "type {} = {}", self.name, self.typ)
+        } else {
+            let generics = vecmap(&self.generics, ToString::to_string);
+            write!(f, "type {}<{}> = {}", self.name, generics.join(", "), self.typ)
+        }
+    }
+}
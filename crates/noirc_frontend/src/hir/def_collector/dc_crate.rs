@@ -8,18 +8,20 @@ use crate::hir::resolution::{
     import::{resolve_imports, ImportDirective},
     path_resolver::StandardPathResolver,
 };
+use crate::hir::type_check::check_statement_constants;
 use crate::hir::type_check::type_check;
 use crate::hir::type_check::type_check_func;
 use crate::hir::Context;
-use crate::node_interner::{FuncId, NodeInterner, StmtId, StructId};
+use crate::node_interner::{FuncId, NodeInterner, StmtId, StructId, TypeAliasId, TypeMethodKey};
 use crate::util::vecmap;
 use crate::{
-    Generics, Ident, LetStatement, NoirFunction, NoirStruct, ParsedModule, Path, Statement, Type,
+    Generics, Ident, LetStatement, NoirFunction, NoirStruct, NoirTypeAlias, ParsedModule, Path,
+    Statement, Type,
 };
 use fm::FileId;
 use noirc_errors::CollectedErrors;
 use noirc_errors::DiagnosableError;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// Stores all of the unresolved functions in a particular file/mod
 pub struct UnresolvedFunctions {
@@ -39,6 +41,12 @@ pub struct UnresolvedStruct {
     pub struct_def: NoirStruct,
 }
 
+pub struct UnresolvedTypeAlias {
+    pub file_id: FileId,
+    pub module_id: LocalModuleId,
+    pub type_alias_def: NoirTypeAlias,
+}
+
 #[derive(Clone)]
 pub struct UnresolvedGlobalConst {
     pub file_id: FileId,
@@ -53,10 +61,14 @@ pub struct DefCollector {
     pub(crate) collected_imports: Vec<ImportDirective>,
     pub(crate) collected_functions: Vec<UnresolvedFunctions>,
     pub(crate) collected_types: HashMap<StructId, UnresolvedStruct>,
+    pub(crate) collected_type_aliases: BTreeMap<TypeAliasId, UnresolvedTypeAlias>,
     pub(crate) collected_consts: Vec<UnresolvedGlobalConst>,
     /// collected impls maps the type name and the module id in which
     /// the impl is defined to the functions contained in that impl
     pub(crate) collected_impls: HashMap<(Path, LocalModuleId), Vec<UnresolvedFunctions>>,
+    /// impls on builtin types such as arrays and Field. Their methods are not
+    /// added to any module scope but can only be called with the method syntax
+    pub(crate) collected_primitive_impls: Vec<(TypeMethodKey, UnresolvedFunctions)>,
 }
 
 impl DefCollector {
@@ -66,7 +78,9 @@ impl DefCollector {
             collected_imports: vec![],
             collected_functions: vec![],
             collected_types: HashMap::new(),
+            collected_type_aliases: BTreeMap::new(),
             collected_impls: HashMap::new(),
+            collected_primitive_impls: vec![],
             collected_consts: vec![],
         }
     }
@@ -149,6 +163,15 @@ impl DefCollector {
             }
         }
 
+        // Type aliases and structs may refer to each other, so every struct must be
+        // known to the interner before aliases are resolved, and aliases must be
+        // resolved before the struct fields which use them.
+        for (type_id, typ) in &def_collector.collected_types {
+            context.def_interner.push_empty_struct(*type_id, typ);
+        }
+
+        resolve_type_aliases(context, def_collector.collected_type_aliases, crate_id, errors);
+
         resolve_structs(context, def_collector.collected_types, crate_id, errors);
 
         // We must first resolve and intern the global consts before we can resolve any stmts inside each function.
@@ -180,10 +203,19 @@ impl DefCollector {
             errors,
         );
 
+        let file_primitive_method_ids = resolve_primitive_impls(
+            &mut context.def_interner,
+            crate_id,
+            &context.def_maps,
+            def_collector.collected_primitive_impls,
+            errors,
+        );
+
         type_check_global_consts(&mut context.def_interner, file_const_ids, errors);
         // Type check all of the functions in the crate
         type_check_functions(&mut context.def_interner, file_func_ids, errors);
         type_check_functions(&mut context.def_interner, file_method_ids, errors);
+        type_check_functions(&mut context.def_interner, file_primitive_method_ids, errors);
     }
 }
 
@@ -283,6 +315,7 @@ fn type_check_global_consts(
     for (file_id, stmt_id) in global_const_ids {
         let mut type_check_errs = vec![];
         type_check(interner, &stmt_id, &mut type_check_errs);
+        check_statement_constants(interner, &stmt_id, &mut type_check_errs);
         let errors = vecmap(type_check_errs, |error| error.into_diagnostic());
 
         if !errors.is_empty() {
@@ -299,14 +332,10 @@ fn resolve_structs(
     crate_id: CrateId,
     errors: &mut Vec<CollectedErrors>,
 ) {
-    // We must first go through the struct list once to ensure all IDs are pushed to
-    // the def_interner map. This lets structs refer to each other regardless of declaration order
+    // All struct IDs have already been pushed to the def_interner map by this point.
+    // This lets structs refer to each other regardless of declaration order
     // without resolve_struct_fields nondeterministically unwrapping a value
     // that isn't in the HashMap.
-    for (type_id, typ) in &structs {
-        context.def_interner.push_empty_struct(*type_id, typ);
-    }
-
     for (type_id, typ) in structs {
         let (generics, fields) = resolve_struct_fields(context, crate_id, typ, errors);
         context.def_interner.update_struct(type_id, |struct_def| {
@@ -316,6 +345,104 @@ fn resolve_structs(
     }
 }
 
+/// Resolve the aliased type of each `type` declaration in the crate. Aliases are
+/// resolved after any aliases they refer to so that they can be expanded in place.
+fn resolve_type_aliases(
+    context: &mut Context,
+    mut type_aliases: BTreeMap<TypeAliasId, UnresolvedTypeAlias>,
+    crate_id: CrateId,
+    errors: &mut Vec<CollectedErrors>,
+) {
+    let mut references = HashMap::new();
+    for (alias_id, alias) in &type_aliases {
+        let path_resolver =
+            StandardPathResolver::new(ModuleId { local_id: alias.module_id, krate: crate_id });
+        let resolver = Resolver::new(
+            &mut context.def_interner,
+            &path_resolver,
+            &context.def_maps,
+            alias.file_id,
+        );
+        let def = &alias.type_alias_def;
+        references.insert(*alias_id, resolver.type_alias_references(&def.typ, &def.generics));
+    }
+
+    let mut order = Vec::with_capacity(type_aliases.len());
+    let mut visited = HashMap::new();
+    let mut cyclic = HashSet::new();
+    for alias_id in type_aliases.keys() {
+        sort_type_aliases(*alias_id, &references, &mut visited, &mut cyclic, &mut order);
+    }
+
+    for alias_id in order {
+        let alias = type_aliases.remove(&alias_id).unwrap();
+
+        // A cyclic alias is left with an Error type so that it does not cause further errors
+        if cyclic.contains(&alias_id) {
+            let name = alias.type_alias_def.name;
+            let err = ResolverError::CyclicTypeAlias { name }.into_diagnostic();
+            errors.push(CollectedErrors { file_id: alias.file_id, errors: vec![err] });
+            continue;
+        }
+
+        let path_resolver =
+            StandardPathResolver::new(ModuleId { local_id: alias.module_id, krate: crate_id });
+
+        let (generics, implicit_generics, typ, errs) = Resolver::new(
+            &mut context.def_interner,
+            &path_resolver,
+            &context.def_maps,
+            alias.file_id,
+        )
+        .resolve_type_alias(alias.type_alias_def);
+
+        if !errs.is_empty() {
+            errors.push(CollectedErrors {
+                file_id: alias.file_id,
+                errors: vecmap(errs, |err| err.into_diagnostic()),
+            })
+        }
+
+        context.def_interner.update_type_alias(alias_id, |alias| {
+            alias.generics = generics;
+            alias.implicit_generics = implicit_generics;
+            alias.typ = typ;
+        });
+    }
+}
+
+/// Depth-first post-order traversal of the aliases within this crate, so that each alias
+/// comes after the aliases it refers to. An alias reached again while its own references
+/// are still being traversed is part of a cycle.
+fn sort_type_aliases(
+    alias_id: TypeAliasId,
+    references: &HashMap<TypeAliasId, Vec<TypeAliasId>>,
+    visited: &mut HashMap<TypeAliasId, bool>,
+    cyclic: &mut HashSet<TypeAliasId>,
+    order: &mut Vec<TypeAliasId>,
+) {
+    // Aliases from other crates have no entry here, they are already resolved
+    let alias_references = match references.get(&alias_id) {
+        Some(alias_references) => alias_references,
+        None => return,
+    };
+
+    match visited.get(&alias_id) {
+        Some(false) => {
+            cyclic.insert(alias_id);
+        }
+        Some(true) => (),
+        None => {
+            visited.insert(alias_id, false);
+            for reference in alias_references {
+                sort_type_aliases(*reference, references, visited, cyclic, order);
+            }
+            visited.insert(alias_id, true);
+            order.push(alias_id);
+        }
+    }
+}
+
 fn resolve_struct_fields(
     context: &mut Context,
     krate: CrateId,
@@ -389,6 +516,43 @@ fn resolve_impls(
     file_method_ids
 }
 
+fn resolve_primitive_impls(
+    interner: &mut NodeInterner,
+    crate_id: CrateId,
+    def_maps: &HashMap<CrateId, CrateDefMap>,
+    collected_impls: Vec<(TypeMethodKey, UnresolvedFunctions)>,
+    errors: &mut Vec<CollectedErrors>,
+) -> Vec<(FileId, FuncId)> {
+    let mut file_method_ids = Vec::new();
+
+    for (key, methods) in collected_impls {
+        let mut ids = resolve_functions(interner, crate_id, def_maps, vec![methods], None, errors);
+
+        for (file_id, method_id) in &ids {
+            let method_name = interner.function_name(method_id).to_owned();
+
+            if let Some(first_fn) =
+                interner.add_method(key.clone(), method_name.clone(), *method_id)
+            {
+                let error = ResolverError::DuplicateDefinition {
+                    name: method_name,
+                    first_span: interner.function_ident(&first_fn).span(),
+                    second_span: interner.function_ident(method_id).span(),
+                };
+
+                errors.push(CollectedErrors {
+                    file_id: *file_id,
+                    errors: vec![error.into_diagnostic()],
+                });
+            }
+        }
+
+        file_method_ids.append(&mut ids);
+    }
+
+    file_method_ids
+}
+
 fn resolve_functions(
     interner: &mut NodeInterner,
     crate_id: CrateId,
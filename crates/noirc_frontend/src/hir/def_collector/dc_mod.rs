@@ -2,8 +2,12 @@ use fm::FileId;
 use noirc_errors::{CollectedErrors, CustomDiagnostic, DiagnosableError};
 
 use crate::{
-    graph::CrateId, hir::def_collector::dc_crate::UnresolvedStruct, node_interner::StructId,
-    parser::SubModule, Ident, LetStatement, NoirFunction, NoirImpl, NoirStruct, ParsedModule,
+    graph::CrateId,
+    hir::def_collector::dc_crate::{UnresolvedStruct, UnresolvedTypeAlias},
+    node_interner::{StructId, TypeMethodKey},
+    parser::{ModuleDeclaration, SubModule},
+    Ident, LetStatement, NoirFunction, NoirImpl, NoirStruct, NoirTypeAlias, ParsedModule,
+    UnresolvedType,
 };
 
 use super::{
@@ -35,7 +39,7 @@ pub fn collect_defs<'a>(
 
     // First resolve the module declarations
     for decl in ast.module_decls {
-        collector.parse_module_declaration(context, &decl, crate_id, errors)
+        collector.parse_module_declaration(context, decl, crate_id, errors)
     }
 
     collector.collect_submodules(context, crate_id, ast.submodules, file_id, errors);
@@ -53,9 +57,11 @@ pub fn collect_defs<'a>(
 
     collector.collect_structs(ast.types, crate_id, errors);
 
-    let errors_in_same_file = collector.collect_functions(context, ast.functions);
+    collector.collect_type_aliases(context, ast.type_aliases, errors);
 
-    collector.collect_impls(context, ast.impls);
+    let mut errors_in_same_file = collector.collect_functions(context, ast.functions);
+
+    errors_in_same_file.extend(collector.collect_impls(context, ast.impls));
 
     if !errors_in_same_file.is_empty() {
         errors.push(CollectedErrors { file_id: collector.file_id, errors: errors_in_same_file });
@@ -99,20 +105,68 @@ impl<'a> ModCollector<'a> {
         }
     }
 
-    fn collect_impls(&mut self, context: &mut Context, impls: Vec<NoirImpl>) {
+    fn collect_impls(
+        &mut self,
+        context: &mut Context,
+        impls: Vec<NoirImpl>,
+    ) -> Vec<CustomDiagnostic> {
+        let mut errors = vec![];
+
         for r#impl in impls {
             let mut unresolved_functions =
                 UnresolvedFunctions { file_id: self.file_id, functions: Vec::new() };
 
-            for method in r#impl.methods.iter() {
-                let func_id = context.def_interner.push_empty_fn();
-                unresolved_functions.push_fn(self.module_id, func_id, method.clone());
+            match &r#impl.object_type {
+                UnresolvedType::Named(path, args)
+                    if args.is_empty() && r#impl.generics.is_empty() =>
+                {
+                    for method in r#impl.methods.iter() {
+                        let func_id = context.def_interner.push_empty_fn();
+                        unresolved_functions.push_fn(self.module_id, func_id, method.clone());
+                    }
+
+                    let key = (path.clone(), self.module_id);
+                    let methods = self.def_collector.collected_impls.entry(key).or_default();
+                    methods.push(unresolved_functions);
+                }
+                object_type => {
+                    let key = match primitive_type_method_key(object_type, &r#impl.generics) {
+                        Some(key) => key,
+                        None => {
+                            let typ = object_type.clone();
+                            let err = DefCollectorErrorKind::UnsupportedImplType {
+                                typ,
+                                span: r#impl.type_span,
+                            };
+                            errors.push(err.to_diagnostic());
+                            continue;
+                        }
+                    };
+
+                    // Methods on builtin types are desugared into generic functions over the
+                    // impl's generics, with each occurrence of `Self` replaced by the object type
+                    for method in r#impl.methods.iter() {
+                        let mut method = method.clone();
+                        let mut generics = r#impl.generics.clone();
+                        generics.append(&mut method.def.generics);
+                        method.def.generics = generics;
+
+                        for (_, typ, _) in method.def.parameters.iter_mut() {
+                            *typ = substitute_self_type(typ.clone(), object_type);
+                        }
+                        method.def.return_type =
+                            substitute_self_type(method.def.return_type, object_type);
+
+                        let func_id = context.def_interner.push_empty_fn();
+                        unresolved_functions.push_fn(self.module_id, func_id, method);
+                    }
+
+                    self.def_collector.collected_primitive_impls.push((key, unresolved_functions));
+                }
             }
-
-            let key = (r#impl.type_path.clone(), self.module_id);
-            let methods = self.def_collector.collected_impls.entry(key).or_default();
-            methods.push(unresolved_functions);
         }
+
+        errors
     }
 
     fn collect_functions(
@@ -202,6 +256,39 @@ impl<'a> ModCollector<'a> {
         }
     }
 
+    fn collect_type_aliases(
+        &mut self,
+        context: &mut Context,
+        type_aliases: Vec<NoirTypeAlias>,
+        errors: &mut Vec<CollectedErrors>,
+    ) {
+        for type_alias in type_aliases {
+            let name = type_alias.name.clone();
+            let id = context.def_interner.push_type_alias(&type_alias);
+
+            // Aliases share the type namespace with structs and modules
+            let result = self.def_collector.def_map.modules[self.module_id.0]
+                .scope
+                .define_type_alias_def(name, id);
+
+            if let Err((first_def, second_def)) = result {
+                let err = DefCollectorErrorKind::DuplicateTypeAlias { first_def, second_def };
+
+                errors.push(CollectedErrors {
+                    file_id: self.file_id,
+                    errors: vec![err.to_diagnostic()],
+                });
+            }
+
+            let unresolved = UnresolvedTypeAlias {
+                file_id: self.file_id,
+                module_id: self.module_id,
+                type_alias_def: type_alias,
+            };
+            self.def_collector.collected_type_aliases.insert(id, unresolved);
+        }
+    }
+
     fn collect_submodules(
         &mut self,
         context: &mut Context,
@@ -232,24 +319,31 @@ impl<'a> ModCollector<'a> {
     fn parse_module_declaration(
         &mut self,
         context: &mut Context,
-        mod_name: &Ident,
+        mod_decl: ModuleDeclaration,
         crate_id: CrateId,
         errors: &mut Vec<CollectedErrors>,
     ) {
-        let child_file_id =
-            match context.file_manager.resolve_path(self.file_id, &mod_name.0.contents) {
-                Ok(child_file_id) => child_file_id,
-                Err(_) => {
-                    let err =
-                        DefCollectorErrorKind::UnresolvedModuleDecl { mod_name: mod_name.clone() };
-
-                    errors.push(CollectedErrors {
-                        file_id: self.file_id,
-                        errors: vec![err.to_diagnostic()],
-                    });
-                    return;
-                }
-            };
+        let mod_name = &mod_decl.name;
+        let resolution = match &mod_decl.path {
+            Some(path) => context.file_manager.resolve_path_attribute(self.file_id, path),
+            None => context.file_manager.resolve_path(self.file_id, &mod_name.0.contents),
+        };
+
+        let child_file_id = match resolution {
+            Ok(child_file_id) => child_file_id,
+            Err(error) => {
+                let err = DefCollectorErrorKind::UnresolvedModuleDecl {
+                    mod_name: mod_name.clone(),
+                    error,
+                };
+
+                errors.push(CollectedErrors {
+                    file_id: self.file_id,
+                    errors: vec![err.to_diagnostic()],
+                });
+                return;
+            }
+        };
 
         // Parse the AST for the module we just found and then recursively look for it's defs
         let ast = parse_file(&mut context.file_manager, child_file_id, errors);
@@ -319,3 +413,43 @@ impl<'a> ModCollector<'a> {
         Ok(LocalModuleId(module_id))
     }
 }
+
+// Returns the key of an impl on a builtin type. An array whose element type is one of the
+// impl's generics, as in `impl<T> [T]`, has the key of every array
+fn primitive_type_method_key(typ: &UnresolvedType, generics: &[Ident]) -> Option<TypeMethodKey> {
+    match typ {
+        UnresolvedType::FieldElement(_) => Some(TypeMethodKey::FieldElement),
+        UnresolvedType::Array(_, element) => match element.as_ref() {
+            UnresolvedType::Named(path, args)
+                if args.is_empty()
+                    && path.as_ident().map_or(false, |ident| generics.contains(ident)) =>
+            {
+                Some(TypeMethodKey::Array(None))
+            }
+            element => {
+                let element = primitive_type_method_key(element, generics)?;
+                Some(TypeMethodKey::Array(Some(Box::new(element))))
+            }
+        },
+        UnresolvedType::Integer(_, sign, bits) => Some(TypeMethodKey::Integer(*sign, *bits)),
+        UnresolvedType::Bool(_) => Some(TypeMethodKey::Bool),
+        _ => None,
+    }
+}
+
+fn substitute_self_type(typ: UnresolvedType, self_type: &UnresolvedType) -> UnresolvedType {
+    match typ {
+        UnresolvedType::Named(path, args)
+            if args.is_empty() && path.as_ident().map_or(false, |ident| ident == "Self") =>
+        {
+            self_type.clone()
+        }
+        UnresolvedType::Array(size, element) => {
+            UnresolvedType::Array(size, Box::new(substitute_self_type(*element, self_type)))
+        }
+        UnresolvedType::Tuple(fields) => UnresolvedType::Tuple(
+            fields.into_iter().map(|field| substitute_self_type(field, self_type)).collect(),
+        ),
+        other => other,
+    }
+}
@@ -1,7 +1,10 @@
-use crate::{hir::resolution::import::ImportDirective, Ident};
+use crate::{hir::resolution::import::ImportDirective, util::vecmap, Ident, UnresolvedType};
+
+use fm::ResolvePathError;
 
 use noirc_errors::CustomDiagnostic as Diagnostic;
 use noirc_errors::DiagnosableError;
+use noirc_errors::Span;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -14,10 +17,14 @@ pub enum DefCollectorErrorKind {
     DuplicateImport { first_def: Ident, second_def: Ident },
     #[error("duplicate global const found in namespace")]
     DuplicateGlobalConst { first_def: Ident, second_def: Ident },
+    #[error("duplicate type alias found in namespace")]
+    DuplicateTypeAlias { first_def: Ident, second_def: Ident },
     #[error("unresolved import")]
-    UnresolvedModuleDecl { mod_name: Ident },
+    UnresolvedModuleDecl { mod_name: Ident, error: ResolvePathError },
     #[error("unresolved import")]
     UnresolvedImport { import: ImportDirective },
+    #[error("unsupported impl type")]
+    UnsupportedImplType { typ: UnresolvedType, span: Span },
 }
 
 impl DiagnosableError for DefCollectorErrorKind {
@@ -75,13 +82,41 @@ impl DiagnosableError for DefCollectorErrorKind {
                 diag.add_secondary("second global declaration found here".to_string(), second_span);
                 diag
             }
-            DefCollectorErrorKind::UnresolvedModuleDecl { mod_name } => {
+            DefCollectorErrorKind::DuplicateTypeAlias { first_def, second_def } => {
+                let first_span = first_def.0.span();
+                let second_span = second_def.0.span();
+                let alias_name = &first_def.0.contents;
+
+                let mut diag = Diagnostic::simple_error(
+                    format!("the type name `{}` is defined multiple times", alias_name),
+                    "first type definition found here".to_string(),
+                    first_span,
+                );
+                diag.add_secondary("second type definition found here".to_string(), second_span);
+                diag
+            }
+            DefCollectorErrorKind::UnresolvedModuleDecl { mod_name, error } => {
                 let span = mod_name.0.span();
                 let mod_name = &mod_name.0.contents;
 
+                let secondary = match error {
+                    ResolvePathError::NotFound { candidates } => {
+                        let candidates = vecmap(candidates, |path| path.display().to_string());
+                        format!("no file found, tried: {}", candidates.join(", "))
+                    }
+                    ResolvePathError::Ambiguous { file, mod_file } => format!(
+                        "both {} and {} exist, remove one of them",
+                        file.display(),
+                        mod_file.display()
+                    ),
+                    ResolvePathError::InvalidExtension { path } => {
+                        format!("{} is not a .nr file", path.display())
+                    }
+                };
+
                 Diagnostic::simple_error(
                     format!("could not resolve module `{}` ", mod_name),
-                    String::new(),
+                    secondary,
                     span,
                 )
             }
@@ -97,6 +132,11 @@ impl DiagnosableError for DefCollectorErrorKind {
                     span,
                 )
             }
+            DefCollectorErrorKind::UnsupportedImplType { typ, span } => Diagnostic::simple_error(
+                format!("cannot define methods on type {}", typ),
+                "impls are only allowed on structs, arrays, Field, integers and bool".to_string(),
+                *span,
+            ),
         }
     }
 }
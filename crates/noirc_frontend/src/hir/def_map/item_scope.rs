@@ -1,6 +1,6 @@
 use super::{namespace::PerNs, ModuleDefId, ModuleId};
 use crate::{
-    node_interner::{FuncId, StmtId, StructId},
+    node_interner::{FuncId, StmtId, StructId, TypeAliasId},
     Ident,
 };
 use std::collections::{hash_map::Entry, HashMap};
@@ -51,6 +51,7 @@ impl ItemScope {
             ModuleDefId::ModuleId(_) => add_item(&mut self.types),
             ModuleDefId::FunctionId(_) => add_item(&mut self.values),
             ModuleDefId::TypeId(_) => add_item(&mut self.types),
+            ModuleDefId::TypeAliasId(_) => add_item(&mut self.types),
             ModuleDefId::ConstId(_) => add_item(&mut self.values),
         }
     }
@@ -75,6 +76,14 @@ impl ItemScope {
         self.add_definition(name, ModuleDefId::TypeId(local_id))
     }
 
+    pub fn define_type_alias_def(
+        &mut self,
+        name: Ident,
+        local_id: TypeAliasId,
+    ) -> Result<(), (Ident, Ident)> {
+        self.add_definition(name, ModuleDefId::TypeAliasId(local_id))
+    }
+
     pub fn define_global_const_def(
         &mut self,
         name: Ident,
@@ -1,4 +1,4 @@
-use crate::node_interner::{FuncId, StmtId, StructId};
+use crate::node_interner::{FuncId, StmtId, StructId, TypeAliasId};
 
 use super::ModuleId;
 
@@ -7,6 +7,7 @@ pub enum ModuleDefId {
     ModuleId(ModuleId),
     FunctionId(FuncId),
     TypeId(StructId),
+    TypeAliasId(TypeAliasId),
     ConstId(StmtId),
 }
 
@@ -25,6 +26,13 @@ impl ModuleDefId {
         }
     }
 
+    pub fn as_type_alias(&self) -> Option<TypeAliasId> {
+        match self {
+            ModuleDefId::TypeAliasId(type_alias_id) => Some(*type_alias_id),
+            _ => None,
+        }
+    }
+
     pub fn as_const(&self) -> Option<StmtId> {
         match self {
             ModuleDefId::ConstId(stmt_id) => Some(*stmt_id),
@@ -38,6 +46,7 @@ impl ModuleDefId {
         match self {
             ModuleDefId::FunctionId(_) => "function",
             ModuleDefId::TypeId(_) => "type",
+            ModuleDefId::TypeAliasId(_) => "type alias",
             ModuleDefId::ModuleId(_) => "module",
             ModuleDefId::ConstId(_) => "const",
         }
@@ -96,6 +105,20 @@ impl TryFromModuleDefId for StructId {
     }
 }
 
+impl TryFromModuleDefId for TypeAliasId {
+    fn try_from(id: ModuleDefId) -> Option<Self> {
+        id.as_type_alias()
+    }
+
+    fn dummy_id() -> Self {
+        TypeAliasId::dummy_id()
+    }
+
+    fn description() -> String {
+        "type alias".to_string()
+    }
+}
+
 impl TryFromModuleDefId for StmtId {
     fn try_from(id: ModuleDefId) -> Option<Self> {
         id.as_const()
@@ -34,6 +34,10 @@ pub enum ResolverError {
     ExpectedConstVariable { name: String, span: Span },
     #[error("Missing expression for declared constant")]
     MissingRhsExpr { name: String, span: Span },
+    #[error("Incorrect amount of arguments to generic type alias")]
+    IncorrectGenericCount { span: Span, name: String, expected: usize, found: usize },
+    #[error("Type alias is defined in terms of itself")]
+    CyclicTypeAlias { name: Ident },
 }
 
 impl ResolverError {
@@ -177,6 +181,26 @@ impl ResolverError {
                 "expected expression to be stored for let statement".to_string(),
                 span,
             ),
+            ResolverError::IncorrectGenericCount { span, name, expected, found } => {
+                let plural = if expected != 1 { "s" } else { "" };
+                Diagnostic::simple_error(
+                    format!(
+                        "type alias {} expects {} generic argument{}, but {} were given",
+                        name, expected, plural, found
+                    ),
+                    format!("expected {} generic argument{}", expected, plural),
+                    span,
+                )
+            }
+            ResolverError::CyclicTypeAlias { name } => {
+                let mut diag = Diagnostic::simple_error(
+                    format!("type alias {} is defined in terms of itself", name),
+                    "cycle detected when expanding this alias".to_string(),
+                    name.span(),
+                );
+                diag.add_note("Type aliases are expanded during name resolution so they may not refer to themselves, either directly or through other aliases. Consider using a struct instead".to_owned());
+                diag
+            }
         }
     }
 }
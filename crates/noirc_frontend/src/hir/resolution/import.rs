@@ -125,6 +125,8 @@ fn resolve_name_in_module(
             ModuleDefId::FunctionId(_) => panic!("functions cannot be in the type namespace"),
             // TODO: If impls are ever implemented, types can be used in a path
             ModuleDefId::TypeId(id) => id.0,
+            // Aliases have no namespace of their own to look the next segment up in
+            ModuleDefId::TypeAliasId(_) => return PathResolution::Unresolved(segment.clone()),
             ModuleDefId::ConstId(_) => panic!("global consts cannot be in the type namespace"),
         };
         current_mod = &def_maps[&new_module_id.krate].modules[new_module_id.local_id.0];
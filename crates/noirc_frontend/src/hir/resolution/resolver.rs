@@ -29,7 +29,9 @@ use std::rc::Rc;
 use crate::graph::CrateId;
 use crate::hir::def_map::{ModuleDefId, TryFromModuleDefId};
 use crate::hir_def::stmt::{HirAssignStatement, HirLValue, HirPattern};
-use crate::node_interner::{DefinitionId, ExprId, FuncId, NodeInterner, StmtId, StructId};
+use crate::node_interner::{
+    DefinitionId, ExprId, FuncId, NodeInterner, StmtId, StructId, TypeAliasId,
+};
 use crate::util::vecmap;
 use crate::{
     hir::{def_map::CrateDefMap, resolution::path_resolver::PathResolver},
@@ -37,8 +39,8 @@ use crate::{
     Statement, UnresolvedArraySize,
 };
 use crate::{
-    Generics, LValue, NoirStruct, Path, Pattern, Shared, StructType, Type, TypeBinding,
-    TypeVariable, UnresolvedType, ERROR_IDENT,
+    Generics, LValue, NoirStruct, NoirTypeAlias, Path, Pattern, Shared, StructType, Type,
+    TypeBinding, TypeVariable, UnresolvedType, ERROR_IDENT,
 };
 use fm::FileId;
 use noirc_errors::{Location, Span, Spanned};
@@ -148,8 +150,9 @@ impl<'a> Resolver<'a> {
     fn check_for_unused_variables_in_local_scope(decl_map: Scope, unused_vars: &mut Vec<HirIdent>) {
         let unused_variables = decl_map.filter(|(variable_name, metadata)| {
             let has_underscore_prefix = variable_name.starts_with('_'); // XXX: This is used for development mode, and will be removed
+            let is_self = variable_name == "self";
 
-            if metadata.num_times_used == 0 && !has_underscore_prefix {
+            if metadata.num_times_used == 0 && !has_underscore_prefix && !is_self {
                 return true;
             }
             false
@@ -317,6 +320,12 @@ impl<'a> Resolver<'a> {
                     }
                 }
 
+                let span = path.span();
+                if let Some(alias_id) = self.try_lookup_type_alias(&path) {
+                    let args = vecmap(args, |arg| self.resolve_type_inner(arg, new_variables));
+                    return self.expand_type_alias(alias_id, args, span, new_variables);
+                }
+
                 match self.lookup_struct(path) {
                     Some(definition) => {
                         let args = vecmap(args, |arg| self.resolve_type_inner(arg, new_variables));
@@ -410,6 +419,65 @@ impl<'a> Resolver<'a> {
         (generics, fields, self.errors)
     }
 
+    /// Resolve the aliased type of a `type` declaration, returning the alias' own generics,
+    /// the type variables of any unsized arrays within the type, and the type itself.
+    pub fn resolve_type_alias(
+        mut self,
+        unresolved: NoirTypeAlias,
+    ) -> (Generics, Generics, Type, Vec<ResolverError>) {
+        let generics = self.add_generics(unresolved.generics);
+        let mut implicit_generics = vec![];
+        let typ = self.resolve_type_inner(unresolved.typ, &mut implicit_generics);
+        (generics, implicit_generics, typ, self.errors)
+    }
+
+    /// Returns each type alias directly referred to within the given type of an alias
+    /// definition. Paths which fail to resolve are skipped, they are reported once the
+    /// alias itself is resolved.
+    pub fn type_alias_references(
+        &self,
+        typ: &UnresolvedType,
+        generics: &[Ident],
+    ) -> Vec<TypeAliasId> {
+        let mut aliases = vec![];
+        self.collect_type_alias_references(typ, generics, &mut aliases);
+        aliases
+    }
+
+    fn collect_type_alias_references(
+        &self,
+        typ: &UnresolvedType,
+        generics: &[Ident],
+        aliases: &mut Vec<TypeAliasId>,
+    ) {
+        match typ {
+            UnresolvedType::Array(_, elem) => {
+                self.collect_type_alias_references(elem, generics, aliases)
+            }
+            UnresolvedType::Tuple(fields) => {
+                for field in fields {
+                    self.collect_type_alias_references(field, generics, aliases);
+                }
+            }
+            UnresolvedType::Named(path, args) => {
+                let is_generic =
+                    path.segments.len() == 1 && generics.contains(&path.last_segment());
+                if !is_generic {
+                    aliases.extend(self.try_lookup_type_alias(path));
+                }
+                for arg in args {
+                    self.collect_type_alias_references(arg, generics, aliases);
+                }
+            }
+            UnresolvedType::FieldElement(_)
+            | UnresolvedType::Integer(..)
+            | UnresolvedType::Bool(_)
+            | UnresolvedType::Unit
+            | UnresolvedType::Unspecified
+            | UnresolvedType::Error => (),
+        }
+    }
+
     /// Extract metadata from a NoirFunction
     /// to be used in analysis and intern the function parameters
     fn extract_meta(&mut self, func: &NoirFunction) -> FuncMeta {
@@ -467,6 +535,7 @@ impl<'a> Resolver<'a> {
             name: name_ident,
             kind: func.kind,
             attributes,
+            is_unconstrained: func.def.is_unconstrained,
             location,
             typ,
             parameters: parameters.into(),
@@ -487,7 +556,7 @@ impl<'a> Resolver<'a> {
             }
             Statement::Constrain(constrain_stmt) => {
                 let expr_id = self.resolve_expression(constrain_stmt.0);
-                HirStatement::Constrain(HirConstrainStatement(expr_id, self.file))
+                HirStatement::Constrain(HirConstrainStatement(expr_id, self.file, constrain_stmt.1))
             }
             Statement::Expression(expr) => HirStatement::Expression(self.resolve_expression(expr)),
             Statement::Semi(expr) => HirStatement::Semi(self.resolve_expression(expr)),
@@ -809,6 +878,42 @@ impl<'a> Resolver<'a> {
         (id != StructId::dummy_id()).then(|| self.get_struct(id))
     }
 
+    /// Resolves the given path without reporting any errors, returning
+    /// the TypeAliasId it refers to if it is a type alias.
+    fn try_lookup_type_alias(&self, path: &Path) -> Option<TypeAliasId> {
+        match self.path_resolver.resolve(self.def_maps, path.clone()) {
+            Ok(Some(def_id)) => def_id.as_type_alias(),
+            _ => None,
+        }
+    }
+
+    fn expand_type_alias(
+        &mut self,
+        id: TypeAliasId,
+        args: Vec<Type>,
+        span: Span,
+        new_variables: &mut Generics,
+    ) -> Type {
+        let alias = self.interner.get_type_alias(id).clone();
+        let found = args.len();
+
+        match alias.instantiate(args, self.interner) {
+            Some((typ, implicit_generics)) => {
+                new_variables.extend(implicit_generics);
+                typ
+            }
+            None => {
+                self.push_err(ResolverError::IncorrectGenericCount {
+                    span,
+                    name: alias.name.0.contents,
+                    expected: alias.generics.len(),
+                    found,
+                });
+                Type::Error
+            }
+        }
+    }
+
     pub fn lookup_type_for_impl(mut self, path: Path) -> (StructId, Vec<ResolverError>) {
         (self.lookup_type(path), self.errors)
     }
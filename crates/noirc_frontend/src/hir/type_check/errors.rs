@@ -32,6 +32,12 @@ pub enum TypeCheckError {
         second_type: String,
         second_index: usize,
     },
+    #[error("Integer literal does not fit in its type")]
+    IntegerLiteralOverflow { value: String, typ: Type, span: Span },
+    #[error("Negative literal of an unsigned type")]
+    NegativeUnsignedLiteral { typ: Type, span: Span },
+    #[error("Constant expression overflows its type")]
+    ConstantOverflow { value: Option<i128>, typ: Type, span: Span },
 }
 
 impl TypeCheckError {
@@ -91,6 +97,26 @@ impl TypeCheckError {
                 format!("return type is {}", typ),
                 span,
             ),
+            TypeCheckError::IntegerLiteralOverflow { value, typ, span } => {
+                Diagnostic::simple_error(
+                    format!("The literal {} does not fit in type {}", value, typ),
+                    String::new(),
+                    span,
+                )
+            }
+            TypeCheckError::NegativeUnsignedLiteral { typ, span } => Diagnostic::simple_error(
+                format!("Negative literals cannot have the unsigned type {}", typ),
+                String::new(),
+                span,
+            ),
+            TypeCheckError::ConstantOverflow { value, typ, span } => {
+                let secondary = value.map(|value| format!("evaluates to {}", value));
+                Diagnostic::simple_error(
+                    format!("This constant expression overflows type {}", typ),
+                    secondary.unwrap_or_default(),
+                    span,
+                )
+            }
         }
     }
 
@@ -3,11 +3,12 @@ use noirc_errors::Span;
 use crate::{
     hir_def::{
         expr::{self, HirBinaryOp, HirExpression, HirLiteral},
+        stmt::{HirLValue, HirStatement},
         types::Type,
     },
-    node_interner::{ExprId, FuncId, NodeInterner},
+    node_interner::{ExprId, FuncId, NodeInterner, StmtId},
     util::vecmap,
-    IsConst, Shared, TypeBinding,
+    BinaryOpKind, IsConst, Shared, Signedness, TypeBinding,
 };
 
 use super::errors::TypeCheckError;
@@ -217,6 +218,21 @@ pub(crate) fn type_check_expression(
     typ
 }
 
+/// Array indices are either fields or unsigned integers. They do not need to be known at
+/// compile-time, out of bounds indices make the proof fail.
+pub(crate) fn check_index_type(index_type: &Type, span: Span, errors: &mut Vec<TypeCheckError>) {
+    if let Type::Integer(_, Signedness::Unsigned, _) = index_type {
+        return;
+    }
+    index_type.make_subtype_of(&Type::field(Some(span)), span, errors, || {
+        TypeCheckError::TypeMismatch {
+            expected_typ: "Field or an unsigned integer".to_owned(),
+            expr_typ: index_type.to_string(),
+            expr_span: span,
+        }
+    });
+}
+
 fn type_check_index_expression(
     interner: &mut NodeInterner,
     index_expr: expr::HirIndexExpression,
@@ -224,22 +240,7 @@ fn type_check_index_expression(
 ) -> Type {
     let index_type = type_check_expression(interner, &index_expr.index, errors);
     let span = interner.expr_span(&index_expr.index);
-
-    index_type.unify(&Type::constant(Some(span)), span, errors, || {
-        // Specialize the error in the case the user has a Field, just not a const one.
-        if matches!(index_type, Type::FieldElement(..)) {
-            TypeCheckError::Unstructured {
-                msg: format!("Array index must be const (known at compile-time), but here a non-const {} was used instead", index_type),
-                span,
-            }
-        } else {
-            TypeCheckError::TypeMismatch {
-                expected_typ: "const Field".to_owned(),
-                expr_typ: index_type.to_string(),
-                expr_span: span,
-            }
-        }
-    });
+    check_index_type(&index_type, span, errors);
 
     let lhs_type = type_check_expression(interner, &index_expr.collection, errors);
     match lhs_type {
@@ -340,15 +341,17 @@ fn lookup_method(
         // checking its arguments as we can't even resolve the name of the function
         Type::Error => None,
 
-        // In the future we could support methods for non-struct types if we have a context
-        // (in the interner?) essentially resembling HashMap<Type, Methods>
-        other => {
-            errors.push(TypeCheckError::Unstructured {
-                span: interner.expr_span(expr_id),
-                msg: format!("Type '{}' must be a struct type to call methods on it", other),
-            });
-            None
-        }
+        // Methods on builtin types are defined in impls such as `impl Field`
+        other => match interner.lookup_primitive_method(other, method_name) {
+            Some(method_id) => Some(method_id),
+            None => {
+                errors.push(TypeCheckError::Unstructured {
+                    span: interner.expr_span(expr_id),
+                    msg: format!("No method named '{}' found for type '{}'", method_name, other),
+                });
+                None
+            }
+        },
     }
 }
 
@@ -431,7 +434,8 @@ fn bind_function_type(
 pub fn prefix_operand_type_rules(op: &crate::UnaryOp, rhs_type: &Type) -> Result<Type, String> {
     match op {
         crate::UnaryOp::Minus => {
-            if !matches!(rhs_type, Type::Integer(..) | Type::Error) {
+            // Integer literals are polymorphic until they are unified with their type
+            if !matches!(rhs_type, Type::Integer(..) | Type::PolymorphicInteger(..) | Type::Error) {
                 return Err("Only Integers can be used in a Minus expression".to_string());
             }
         }
@@ -494,6 +498,10 @@ pub fn infix_operand_type_rules(
         (Error, _) | (_,Error) => Ok(Error),
         (Unit, _) | (_,Unit) => Ok(Unit),
 
+        (FieldElement(_), FieldElement(_)) if op.kind == crate::BinaryOpKind::Modulo => {
+            Err("Fields do not have a remainder operation, try converting them into integers".to_string())
+        }
+
         // The result of two Fields is always a witness
         (FieldElement(is_const_x), FieldElement(is_const_y)) => {
             let is_const = is_const_x.and(is_const_y, op.location.span);
@@ -693,3 +701,265 @@ pub fn comparator_operand_type_rules(
         (lhs, rhs) => Err(format!("Unsupported types for comparison: {} and {}", lhs, rhs)),
     }
 }
+
+/// Integer constants are only reduced to their type when the program is compiled, so a literal
+/// or a constant expression which does not fit in its integer type is reported here, once the
+/// types of the function have been inferred.
+pub(crate) fn check_integer_constants(
+    interner: &NodeInterner,
+    expr_id: &ExprId,
+    errors: &mut Vec<TypeCheckError>,
+) {
+    evaluate_constant(interner, expr_id, errors);
+}
+
+pub(crate) fn check_statement_constants(
+    interner: &NodeInterner,
+    stmt_id: &StmtId,
+    errors: &mut Vec<TypeCheckError>,
+) {
+    match interner.statement(stmt_id) {
+        HirStatement::Let(let_stmt) => {
+            evaluate_constant(interner, &let_stmt.expression, errors);
+        }
+        HirStatement::Constrain(constrain) => {
+            evaluate_constant(interner, &constrain.0, errors);
+        }
+        HirStatement::Assign(assign) => {
+            check_lvalue_constants(interner, &assign.lvalue, errors);
+            evaluate_constant(interner, &assign.expression, errors);
+        }
+        HirStatement::Expression(expr_id) | HirStatement::Semi(expr_id) => {
+            evaluate_constant(interner, &expr_id, errors);
+        }
+        HirStatement::Error => (),
+    }
+}
+
+fn check_lvalue_constants(
+    interner: &NodeInterner,
+    lvalue: &HirLValue,
+    errors: &mut Vec<TypeCheckError>,
+) {
+    match lvalue {
+        HirLValue::Ident(_) => (),
+        HirLValue::MemberAccess { object, .. } => check_lvalue_constants(interner, object, errors),
+        HirLValue::Index { array, index } => {
+            check_lvalue_constants(interner, array, errors);
+            evaluate_constant(interner, index, errors);
+        }
+    }
+}
+
+/// Returns the value of an expression if it is an integer constant, after checking that the
+/// constants within the expression fit in their type.
+/// Field constants are tracked as well since they can be cast into integers, but only while they fit in an i128.
+fn evaluate_constant(
+    interner: &NodeInterner,
+    expr_id: &ExprId,
+    errors: &mut Vec<TypeCheckError>,
+) -> Option<i128> {
+    let typ = interner.id_type(*expr_id).follow_bindings();
+    let span = interner.expr_span(expr_id);
+    match interner.expression(expr_id) {
+        HirExpression::Literal(HirLiteral::Integer(value)) => {
+            check_literal(value.to_string(), value.try_into_u128(), false, typ, span, errors)
+        }
+        HirExpression::Literal(HirLiteral::Bool(value)) => Some(i128::from(value)),
+        HirExpression::Literal(HirLiteral::Array(array)) => {
+            for element in &array.contents {
+                evaluate_constant(interner, element, errors);
+            }
+            None
+        }
+        HirExpression::Prefix(prefix) => {
+            // Negative literals are checked as a whole, since -128 is an i8 but 128 is not
+            if let (crate::UnaryOp::Minus, HirExpression::Literal(HirLiteral::Integer(value))) =
+                (prefix.operator, interner.expression(&prefix.rhs))
+            {
+                if matches!(typ, Type::Integer(_, Signedness::Unsigned, _)) && !value.is_zero() {
+                    errors.push(TypeCheckError::NegativeUnsignedLiteral { typ, span });
+                    return None;
+                }
+                let magnitude = value.try_into_u128();
+                return check_literal(format!("-{}", value), magnitude, true, typ, span, errors);
+            }
+
+            let rhs = evaluate_constant(interner, &prefix.rhs, errors)?;
+            let value = match (prefix.operator, &typ) {
+                (crate::UnaryOp::Minus, _) => rhs.checked_neg(),
+                (crate::UnaryOp::Not, Type::Bool(_)) => Some(1 - rhs),
+                (crate::UnaryOp::Not, Type::Integer(_, Signedness::Unsigned, bits))
+                    if *bits < 127 =>
+                {
+                    Some(((1 << bits) - 1) ^ rhs)
+                }
+                (crate::UnaryOp::Not, Type::Integer(_, Signedness::Signed, _)) => Some(!rhs),
+                (crate::UnaryOp::Not, _) => return None,
+            };
+            check_constant_overflow(value, typ, span, errors)
+        }
+        HirExpression::Infix(infix) => {
+            let lhs = evaluate_constant(interner, &infix.lhs, errors);
+            let rhs = evaluate_constant(interner, &infix.rhs, errors);
+            let value = evaluate_infix(lhs?, infix.operator.kind, rhs?, &typ)?;
+            check_constant_overflow(value, typ, span, errors)
+        }
+        HirExpression::Cast(cast) => {
+            let value = evaluate_constant(interner, &cast.lhs, errors)?;
+            check_constant_overflow(Some(value), typ, span, errors)
+        }
+        HirExpression::Block(block) => {
+            for statement in block.statements() {
+                check_statement_constants(interner, statement, errors);
+            }
+            None
+        }
+        HirExpression::Index(index) => {
+            evaluate_constant(interner, &index.collection, errors);
+            evaluate_constant(interner, &index.index, errors);
+            None
+        }
+        HirExpression::Constructor(constructor) => {
+            for (_, field) in &constructor.fields {
+                evaluate_constant(interner, field, errors);
+            }
+            None
+        }
+        HirExpression::MemberAccess(access) => {
+            evaluate_constant(interner, &access.lhs, errors);
+            None
+        }
+        HirExpression::Call(call) => {
+            for argument in &call.arguments {
+                evaluate_constant(interner, argument, errors);
+            }
+            None
+        }
+        HirExpression::MethodCall(method_call) => {
+            evaluate_constant(interner, &method_call.object, errors);
+            for argument in &method_call.arguments {
+                evaluate_constant(interner, argument, errors);
+            }
+            None
+        }
+        HirExpression::For(for_expr) => {
+            evaluate_constant(interner, &for_expr.start_range, errors);
+            evaluate_constant(interner, &for_expr.end_range, errors);
+            evaluate_constant(interner, &for_expr.block, errors);
+            None
+        }
+        HirExpression::If(if_expr) => {
+            evaluate_constant(interner, &if_expr.condition, errors);
+            evaluate_constant(interner, &if_expr.consequence, errors);
+            if let Some(alternative) = if_expr.alternative {
+                evaluate_constant(interner, &alternative, errors);
+            }
+            None
+        }
+        HirExpression::Tuple(elements) => {
+            for element in &elements {
+                evaluate_constant(interner, element, errors);
+            }
+            None
+        }
+        HirExpression::Ident(_)
+        | HirExpression::Literal(HirLiteral::Str(_))
+        | HirExpression::Error => None,
+    }
+}
+
+// Comparisons are not folded, and neither are Field divisions since they are not integer divisions
+fn evaluate_infix(
+    lhs: i128,
+    operator: BinaryOpKind,
+    rhs: i128,
+    typ: &Type,
+) -> Option<Option<i128>> {
+    let is_field = !matches!(typ, Type::Integer(..) | Type::Bool(_));
+    let value = match operator {
+        BinaryOpKind::Add => lhs.checked_add(rhs),
+        BinaryOpKind::Subtract => lhs.checked_sub(rhs),
+        BinaryOpKind::Multiply => lhs.checked_mul(rhs),
+        BinaryOpKind::Divide if !is_field => lhs.checked_div(rhs),
+        BinaryOpKind::Modulo if !is_field => lhs.checked_rem(rhs),
+        BinaryOpKind::And if !is_field => Some(lhs & rhs),
+        BinaryOpKind::Or if !is_field => Some(lhs | rhs),
+        BinaryOpKind::Xor if !is_field => Some(lhs ^ rhs),
+        BinaryOpKind::ShiftLeft if !is_field && (0..127).contains(&rhs) => {
+            lhs.checked_mul(1 << rhs)
+        }
+        BinaryOpKind::ShiftRight if !is_field && (0..128).contains(&rhs) => Some(lhs >> rhs),
+        _ => return None,
+    };
+    // A division by zero is left to the evaluator
+    if value.is_none() && matches!(operator, BinaryOpKind::Divide | BinaryOpKind::Modulo) {
+        return None;
+    }
+    Some(value)
+}
+
+// Reports a constant of an integer type whose value, if it could be computed, does not fit in the type
+fn check_constant_overflow(
+    value: Option<i128>,
+    typ: Type,
+    span: Span,
+    errors: &mut Vec<TypeCheckError>,
+) -> Option<i128> {
+    match value {
+        Some(value) if integer_fits(&typ, value) => Some(value),
+        _ if matches!(typ, Type::Integer(..)) => {
+            errors.push(TypeCheckError::ConstantOverflow { value, typ, span });
+            None
+        }
+        _ => value,
+    }
+}
+
+fn integer_fits(typ: &Type, value: i128) -> bool {
+    match *typ {
+        Type::Integer(_, Signedness::Unsigned, bits) => {
+            value >= 0 && (bits >= 127 || value >> bits == 0)
+        }
+        Type::Integer(_, Signedness::Signed, 0) => value == 0,
+        Type::Integer(_, Signedness::Signed, bits) => {
+            bits >= 128 || value >> (bits - 1) == 0 || value >> (bits - 1) == -1
+        }
+        _ => true,
+    }
+}
+
+/// Checks that the literal with the given magnitude and sign fits its type, and returns its value as a constant
+fn check_literal(
+    value: String,
+    magnitude: Option<u128>,
+    negative: bool,
+    typ: Type,
+    span: Span,
+    errors: &mut Vec<TypeCheckError>,
+) -> Option<i128> {
+    let constant = match magnitude {
+        Some(magnitude) if negative && magnitude == 1 << 127 => Some(i128::MIN),
+        Some(magnitude) => {
+            let constant = i128::try_from(magnitude).ok();
+            if negative {
+                constant.map(|constant| -constant)
+            } else {
+                constant
+            }
+        }
+        None => None,
+    };
+    // Unsigned literals above i128::MAX are not tracked as constants, but they may still fit their type
+    let fits = match (magnitude, &typ) {
+        (Some(magnitude), Type::Integer(_, Signedness::Unsigned, bits)) if !negative => {
+            *bits >= 128 || magnitude >> bits == 0
+        }
+        _ => constant.map_or(false, |constant| integer_fits(&typ, constant)),
+    };
+    if !fits && matches!(typ, Type::Integer(..)) {
+        errors.push(TypeCheckError::IntegerLiteralOverflow { value, typ, span });
+        return None;
+    }
+    constant
+}
@@ -7,10 +7,11 @@ mod stmt;
 // all foreseeable types, if it is needed then we would need to switch to Hindley-Milner type or maybe bidirectional
 
 pub use errors::TypeCheckError;
-use expr::type_check_expression;
+use expr::{check_integer_constants, type_check_expression};
 
 use crate::node_interner::{FuncId, NodeInterner};
 
+pub(crate) use self::expr::check_statement_constants;
 pub(crate) use self::stmt::{bind_pattern, type_check};
 
 /// Type checks a function and assigns the
@@ -46,6 +47,7 @@ pub fn type_check_func(interner: &mut NodeInterner, func_id: FuncId) -> Vec<Type
         });
     }
 
+    check_integer_constants(interner, func_as_expr, &mut errors);
     errors
 }
 
@@ -58,6 +60,7 @@ mod test {
     use fm::FileId;
     use noirc_errors::{Location, Span};
 
+    use super::TypeCheckError;
     use crate::hir_def::expr::HirIdent;
     use crate::hir_def::stmt::HirLetStatement;
     use crate::hir_def::stmt::HirPattern::Identifier;
@@ -139,6 +142,7 @@ mod test {
             name,
             kind: FunctionKind::Normal,
             attributes: None,
+            is_unconstrained: false,
             location,
             typ: Type::Function(vec![Type::field(None), Type::field(None)], Box::new(Type::Unit)),
             parameters: vec![
@@ -238,9 +242,53 @@ mod test {
         }
     }
 
+    #[test]
+    fn integer_constants_fit() {
+        let src = r#"
+            fn main(x : u8) {
+                let _a: u8 = 255;
+                let _b: i8 = -128;
+                let _c = 200 as u8 + 55;
+                let _d = x + 1;
+                let _e: u128 = 170141183460469231731687303715884105728;
+                let _f: u128 = 340282366920920938463463374607431768211455;
+                let _g: i128 = -170141183460469231731687303715884105728;
+            }
+        "#;
+
+        type_check_src_code(src, vec![String::from("main")]);
+    }
+
+    #[test]
+    fn integer_constants_overflow() {
+        let src = r#"
+            fn main(x : Field) {
+                let _a: u8 = 300;
+                let _b: u8 = -1;
+                let _c = 200 as u8 + 100;
+                let _d = 300 as u8;
+                let _e = x as u8 + 1;
+                let _f: u128 = 340282366920920938463463374607431768211456;
+            }
+        "#;
+
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 5);
+        assert!(matches!(errors[0], TypeCheckError::IntegerLiteralOverflow { .. }));
+        assert!(matches!(errors[1], TypeCheckError::NegativeUnsignedLiteral { .. }));
+        assert!(matches!(errors[2], TypeCheckError::ConstantOverflow { value: Some(300), .. }));
+        assert!(matches!(errors[3], TypeCheckError::ConstantOverflow { value: Some(300), .. }));
+        assert!(matches!(errors[4], TypeCheckError::IntegerLiteralOverflow { .. }));
+    }
+
     // This function assumes that there is only one function and this is the
     // func id that is returned
     fn type_check_src_code(src: &str, func_namespace: Vec<String>) {
+        let errors = type_check_src_code_errors(src, func_namespace);
+        assert_eq!(errors, vec![]);
+    }
+
+    fn type_check_src_code_errors(src: &str, func_namespace: Vec<String>) -> Vec<TypeCheckError> {
         let (program, errors) = parse_program(src);
         let mut interner = NodeInterner::default();
 
@@ -274,7 +322,6 @@ mod test {
         }
 
         // Type check section
-        let errors = super::type_check_func(&mut interner, func_ids.first().cloned().unwrap());
-        assert_eq!(errors, vec![]);
+        super::type_check_func(&mut interner, func_ids.first().cloned().unwrap())
     }
 }
@@ -7,7 +7,10 @@ use crate::hir_def::types::Type;
 use crate::node_interner::{DefinitionId, ExprId, NodeInterner, StmtId};
 use crate::IsConst;
 
-use super::{errors::TypeCheckError, expr::type_check_expression};
+use super::{
+    errors::TypeCheckError,
+    expr::{check_index_type, type_check_expression},
+};
 
 pub(crate) fn type_check(
     interner: &mut NodeInterner,
@@ -183,14 +186,7 @@ fn type_check_lvalue(
         HirLValue::Index { array, index } => {
             let index_type = type_check_expression(interner, &index, errors);
             let expr_span = interner.expr_span(&index);
-
-            index_type.unify(&Type::constant(Some(expr_span)), expr_span, errors, || {
-                TypeCheckError::TypeMismatch {
-                    expected_typ: "const Field".to_owned(),
-                    expr_typ: index_type.to_string(),
-                    expr_span,
-                }
-            });
+            check_index_type(&index_type, expr_span, errors);
 
             let (result, array) = type_check_lvalue(interner, *array, assign_span, errors);
             let array = Box::new(array);
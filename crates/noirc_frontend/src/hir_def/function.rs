@@ -118,6 +118,11 @@ pub struct FuncMeta {
     pub kind: FunctionKind,
 
     pub attributes: Option<Attribute>,
+
+    /// Unconstrained functions are compiled to hint programs executed by the ACVM
+    /// instead of being inlined into the circuit
+    pub is_unconstrained: bool,
+
     pub parameters: Parameters,
     pub return_visibility: AbiFEType,
 
@@ -27,7 +27,7 @@ pub struct HirAssignStatement {
 }
 
 #[derive(Debug, Clone)]
-pub struct HirConstrainStatement(pub ExprId, pub FileId);
+pub struct HirConstrainStatement(pub ExprId, pub FileId, /*message:*/ pub Option<String>);
 
 #[derive(Debug, Clone)]
 pub struct BinaryStatement {
@@ -9,7 +9,7 @@ use noirc_abi::{AbiFEType, AbiType};
 use noirc_errors::Span;
 
 use crate::{
-    node_interner::{FuncId, StructId},
+    node_interner::{FuncId, StructId, TypeAliasId},
     util::vecmap,
     Ident, Signedness,
 };
@@ -81,6 +81,54 @@ pub struct StructType {
 
 pub type Generics = Vec<(TypeVariableId, TypeVariable)>;
 
+/// A resolved `type Name<Generics> = Type;` declaration. Aliases are expanded
+/// during name resolution so they never appear within other Types.
+#[derive(Debug, Clone)]
+pub struct TypeAliasType {
+    pub id: TypeAliasId,
+    pub name: Ident,
+    pub generics: Generics,
+
+    /// Type variables for any `[T]` arrays of unspecified length within the aliased
+    /// type. Each use of the alias receives fresh variables for these.
+    pub implicit_generics: Generics,
+
+    pub typ: Type,
+    pub span: Span,
+}
+
+impl TypeAliasType {
+    /// Expand this alias with the given generic arguments, returning None if
+    /// the number of arguments does not match the alias' generics. Also returns
+    /// the fresh type variables created for the alias' implicit generics.
+    pub fn instantiate(
+        &self,
+        args: Vec<Type>,
+        interner: &mut NodeInterner,
+    ) -> Option<(Type, Generics)> {
+        if args.len() != self.generics.len() {
+            return None;
+        }
+
+        let mut bindings: TypeBindings = self
+            .generics
+            .iter()
+            .zip(args)
+            .map(|((id, var), arg)| (*id, (var.clone(), arg)))
+            .collect();
+
+        let implicit_generics = vecmap(&self.implicit_generics, |(id, var)| {
+            let new_id = interner.next_type_variable_id();
+            let new_var = Shared::new(TypeBinding::Unbound(new_id));
+            let typ = Type::NamedGeneric(new_var.clone(), Rc::new("".into()));
+            bindings.insert(*id, (var.clone(), typ));
+            (new_id, new_var)
+        });
+
+        Some((self.typ.substitute(&bindings), implicit_generics))
+    }
+}
+
 impl std::hash::Hash for StructType {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.id.hash(state)
@@ -281,10 +281,8 @@ impl<'a> Lexer<'a> {
         }
         self.next_char();
 
-        let (word, start, end) = self.eat_while(None, |ch| {
-            (ch.is_ascii_alphabetic() || ch.is_numeric() || ch == '_' || ch == '(' || ch == ')')
-                && (ch != ']')
-        });
+        // The contents are validated by lookup_attribute, `#[path = "..."]` attributes may contain any character
+        let (word, start, end) = self.eat_while(None, |ch| ch != ']' && ch != '\n');
 
         if !self.peek_char_is(']') {
             return Err(LexerErrorKind::UnexpectedCharacter {
@@ -286,6 +286,8 @@ impl IntType {
 pub enum Attribute {
     Foreign(String),
     Builtin(String),
+    /// `#[path = "..."]` overrides the file a module declaration is loaded from
+    Path(String),
 }
 
 impl fmt::Display for Attribute {
@@ -293,6 +295,7 @@ impl fmt::Display for Attribute {
         match *self {
             Attribute::Foreign(ref k) => write!(f, "#[foreign({})]", k),
             Attribute::Builtin(ref k) => write!(f, "#[builtin({})]", k),
+            Attribute::Path(ref k) => write!(f, "#[path = \"{}\"]", k),
         }
     }
 }
@@ -301,6 +304,23 @@ impl Attribute {
     /// If the string is a fixed attribute return that, else
     /// return the custom attribute
     pub(crate) fn lookup_attribute(word: &str, span: Span) -> Result<Token, LexerErrorKind> {
+        if let Some((attribute_type, value)) = word.split_once('=') {
+            let value = value.trim();
+            let is_string = value.len() >= 2 && value.starts_with('"') && value.ends_with('"');
+            if attribute_type.trim() != "path" || !is_string {
+                return Err(LexerErrorKind::MalformedFuncAttribute {
+                    span,
+                    found: word.to_owned(),
+                });
+            }
+            let path = value[1..value.len() - 1].to_string();
+            return Ok(Token::Attribute(Attribute::Path(path)));
+        }
+
+        if word.contains(char::is_whitespace) {
+            return Err(LexerErrorKind::MalformedFuncAttribute { span, found: word.to_owned() });
+        }
+
         let word_segments: Vec<&str> = word
             .split(|c| c == '(' || c == ')')
             .filter(|string_segment| !string_segment.is_empty())
@@ -325,15 +345,15 @@ impl Attribute {
 
     pub fn builtin(self) -> Option<String> {
         match self {
-            Attribute::Foreign(_) => None,
             Attribute::Builtin(name) => Some(name),
+            Attribute::Foreign(_) | Attribute::Path(_) => None,
         }
     }
 
     pub fn foreign(self) -> Option<String> {
         match self {
             Attribute::Foreign(name) => Some(name),
-            Attribute::Builtin(_) => None,
+            Attribute::Builtin(_) | Attribute::Path(_) => None,
         }
     }
 
@@ -350,6 +370,7 @@ impl AsRef<str> for Attribute {
         match self {
             Attribute::Foreign(string) => string,
             Attribute::Builtin(string) => string,
+            Attribute::Path(string) => string,
         }
     }
 }
@@ -361,6 +382,7 @@ pub enum Keyword {
     Crate,
     Fn,
     Struct,
+    Type,
     Impl,
     If,
     Mod,
@@ -372,6 +394,7 @@ pub enum Keyword {
     Use,
     Constrain,
     Mut,
+    Unconstrained,
     // Field types
     Pub,
     Const,
@@ -392,6 +415,7 @@ impl fmt::Display for Keyword {
             Keyword::Crate => write!(f, "crate"),
             Keyword::Fn => write!(f, "fn"),
             Keyword::Struct => write!(f, "struct"),
+            Keyword::Type => write!(f, "type"),
             Keyword::Impl => write!(f, "impl"),
             Keyword::If => write!(f, "if"),
             Keyword::Mod => write!(f, "mod"),
@@ -401,6 +425,7 @@ impl fmt::Display for Keyword {
             Keyword::While => write!(f, "while"),
             Keyword::Constrain => write!(f, "constrain"),
             Keyword::Mut => write!(f, "mut"),
+            Keyword::Unconstrained => write!(f, "unconstrained"),
             Keyword::Let => write!(f, "let"),
             Keyword::As => write!(f, "as"),
             Keyword::Use => write!(f, "use"),
@@ -421,6 +446,7 @@ impl Keyword {
         let keyword = match word {
             "fn" => Keyword::Fn,
             "struct" => Keyword::Struct,
+            "type" => Keyword::Type,
             "impl" => Keyword::Impl,
             "dep" => Keyword::Dep,
             "crate" => Keyword::Crate,
@@ -435,6 +461,7 @@ impl Keyword {
             "as" => Keyword::As,
             "use" => Keyword::Use,
             "mut" => Keyword::Mut,
+            "unconstrained" => Keyword::Unconstrained,
 
             "setpub" => Keyword::SetPub,
             "pub" => Keyword::Pub,
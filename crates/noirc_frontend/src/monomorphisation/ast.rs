@@ -22,7 +22,7 @@ pub enum Expression {
     CallLowLevel(CallLowLevel),
 
     Let(Let),
-    Constrain(Box<Expression>, Location),
+    Constrain(Box<Expression>, Location, /*message:*/ Option<String>),
     Assign(Assign),
     Semi(Box<Expression>),
 }
@@ -73,6 +73,7 @@ pub struct Binary {
     pub lhs: Box<Expression>,
     pub operator: BinaryOp,
     pub rhs: Box<Expression>,
+    pub location: Location,
 }
 
 #[derive(Debug, Clone)]
@@ -99,6 +100,7 @@ pub struct ArrayLiteral {
 pub struct Call {
     pub func_id: FuncId,
     pub arguments: Vec<Expression>,
+    pub location: Location,
 }
 
 #[derive(Debug, Clone)]
@@ -112,12 +114,16 @@ pub struct CallLowLevel {
 pub struct CallBuiltin {
     pub opcode: String,
     pub arguments: Vec<Expression>,
+    /// The monomorphised types of the arguments, for builtins which are generic over them
+    pub argument_types: Vec<Type>,
+    pub location: Location,
 }
 
 #[derive(Debug, Clone)]
 pub struct Index {
     pub collection: Box<Expression>,
     pub index: Box<Expression>,
+    pub element_type: Type,
 }
 
 #[derive(Debug, Clone)]
@@ -157,6 +163,9 @@ pub struct Function {
     pub body: Expression,
 
     pub return_type: Type,
+
+    /// Unconstrained functions are not inlined but executed as hints by the ACVM
+    pub unconstrained: bool,
 }
 
 /// A monomorphised Type has all type variables removed
@@ -0,0 +1,60 @@
+use noirc_errors::CustomDiagnostic as Diagnostic;
+use noirc_errors::Location;
+use thiserror::Error;
+
+/// A call from one function to another, which is part of a cycle of the call graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallSite {
+    pub caller: String,
+    pub callee: String,
+    pub location: Location,
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RecursionError {
+    #[error("Recursive call which is not bounded by a constant argument")]
+    Unbounded { cycle: Vec<CallSite> },
+    #[error("Recursion depth limit exceeded")]
+    LimitExceeded { cycle: Vec<CallSite>, limit: u32 },
+}
+
+impl RecursionError {
+    fn cycle(&self) -> &[CallSite] {
+        match self {
+            RecursionError::Unbounded { cycle } | RecursionError::LimitExceeded { cycle, .. } => {
+                cycle
+            }
+        }
+    }
+
+    /// The location of the first call of the cycle, where the error is reported
+    pub fn location(&self) -> Location {
+        self.cycle()[0].location
+    }
+
+    pub fn into_diagnostic(self) -> Diagnostic {
+        let function = &self.cycle()[0].caller;
+        let message = match &self {
+            RecursionError::Unbounded { .. } => format!(
+                "Function {} is recursive, which is only supported when the recursion is bounded by constant arguments",
+                function
+            ),
+            RecursionError::LimitExceeded { limit, .. } => format!(
+                "The recursion of function {} is deeper than the limit of {} calls",
+                function, limit
+            ),
+        };
+        let mut diagnostic = Diagnostic::from_message(&message);
+        let file = self.location().file;
+        for call in self.cycle() {
+            let label = format!("{} calls {}", call.caller, call.callee);
+            // Labels can only point into the file of the error
+            if call.location.file == file {
+                diagnostic.add_secondary(label, call.location.span);
+            } else {
+                diagnostic.add_note(format!("{} in another file", label));
+            }
+        }
+        diagnostic
+    }
+}
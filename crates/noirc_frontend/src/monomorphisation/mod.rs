@@ -1,5 +1,7 @@
 use std::collections::{BTreeMap, HashMap, VecDeque};
 
+use noirc_errors::Location;
+
 use crate::{
     hir_def::{
         expr::*,
@@ -14,7 +16,9 @@ use crate::{
 use self::ast::{DefinitionId, FuncId, Program};
 
 pub mod ast;
+pub mod errors;
 pub mod printer;
+pub mod recursion;
 
 struct Monomorphiser {
     // Store monomorphised globals and locals separately,
@@ -110,10 +114,10 @@ impl Monomorphiser {
                 Box::new(ast::Expression::Ident(ast::Ident { id, location: None, name, typ }));
             let rhs = Box::new(main.body);
             let operator = ast::BinaryOp::Equal;
-            let eq = ast::Expression::Binary(ast::Binary { operator, lhs, rhs });
-
             let location = self.interner.function_meta(&main_id).location;
-            main.body = ast::Expression::Constrain(Box::new(eq), location);
+            let eq = ast::Expression::Binary(ast::Binary { operator, lhs, rhs, location });
+
+            main.body = ast::Expression::Constrain(Box::new(eq), location, None);
         }
 
         let abi = main_meta.into_abi(&self.interner);
@@ -125,10 +129,11 @@ impl Monomorphiser {
         let name = self.interner.function_name(&f).to_owned();
 
         let return_type = Self::convert_type(meta.return_type());
+        let unconstrained = meta.is_unconstrained;
         let parameters = self.parameters(meta.parameters);
         let body = self.expr_infer(*self.interner.function(&f).as_expr());
 
-        ast::Function { id, name, parameters, body, return_type }
+        ast::Function { id, name, parameters, body, return_type, unconstrained }
     }
 
     /// Monomorphise each parameter, expanding tuple/struct patterns into multiple parameters
@@ -211,12 +216,14 @@ impl Monomorphiser {
                 let lhs = Box::new(self.expr_infer(infix.lhs));
                 let rhs = Box::new(self.expr_infer(infix.rhs));
                 let operator = infix.operator.kind;
-                ast::Expression::Binary(ast::Binary { lhs, rhs, operator })
+                let location = self.interner.expr_location(&expr);
+                ast::Expression::Binary(ast::Binary { lhs, rhs, operator, location })
             }
 
             HirExpression::Index(index) => ast::Expression::Index(ast::Index {
                 collection: Box::new(self.expr_infer(index.collection)),
                 index: Box::new(self.expr_infer(index.index)),
+                element_type: Self::convert_type(&self.interner.id_type(expr)),
             }),
 
             HirExpression::MemberAccess(access) => {
@@ -277,7 +284,7 @@ impl Monomorphiser {
             HirStatement::Constrain(constrain) => {
                 let expr = self.expr(constrain.0, &HirType::Bool(IsConst::No(None)));
                 let location = self.interner.expr_location(&constrain.0);
-                ast::Expression::Constrain(Box::new(expr), location)
+                ast::Expression::Constrain(Box::new(expr), location, constrain.2)
             }
             HirStatement::Assign(assign) => self.assign(assign),
             HirStatement::Expression(expr) => self.expr_infer(expr),
@@ -479,13 +486,17 @@ impl Monomorphiser {
                 );
                 ast::Expression::CallLowLevel(ast::CallLowLevel { opcode, arguments })
             }
-            FunctionKind::Builtin => self.call_builtin(meta, arguments, call.arguments),
+            FunctionKind::Builtin => {
+                let location = self.interner.expr_location(&expr_id);
+                self.call_builtin(meta, arguments, call.arguments, location)
+            }
             FunctionKind::Normal => {
                 let func_id = self
                     .lookup_global(func_id, &typ)
                     .unwrap_or_else(|| self.queue_function(func_id, expr_id, typ));
 
-                ast::Expression::Call(ast::Call { func_id, arguments })
+                let location = self.interner.expr_location(&expr_id);
+                ast::Expression::Call(ast::Call { func_id, arguments, location })
             }
         }
     }
@@ -495,6 +506,7 @@ impl Monomorphiser {
         meta: FuncMeta,
         arguments: Vec<ast::Expression>,
         arg_ids: Vec<node_interner::ExprId>,
+        location: Location,
     ) -> ast::Expression {
         let attribute = meta.attributes.expect("all builtin functions must contain an attribute which contains the function name which it links to");
         let opcode = attribute
@@ -506,7 +518,14 @@ impl Monomorphiser {
             let len = typ.array_length().unwrap();
             ast::Expression::Literal(ast::Literal::Integer((len as u128).into(), ast::Type::Field))
         } else {
-            ast::Expression::CallBuiltin(ast::CallBuiltin { opcode, arguments })
+            let argument_types =
+                vecmap(arg_ids, |id| Self::convert_type(&self.interner.id_type(id)));
+            ast::Expression::CallBuiltin(ast::CallBuiltin {
+                opcode,
+                arguments,
+                argument_types,
+                location,
+            })
         }
     }
 
@@ -16,6 +16,9 @@ impl AstPrinter {
         })
         .join(", ");
 
+        if function.unconstrained {
+            write!(f, "unconstrained ")?;
+        }
         write!(
             f,
             "fn {}${}({}) -> {} {{",
@@ -60,9 +63,13 @@ impl AstPrinter {
                 write!(f, "let {}${} = ", let_expr.name, let_expr.id.0)?;
                 self.print_expr(&let_expr.expression, f)
             }
-            Expression::Constrain(expr, _) => {
+            Expression::Constrain(expr, _, message) => {
                 write!(f, "constrain ")?;
-                self.print_expr(expr, f)
+                self.print_expr(expr, f)?;
+                match message {
+                    Some(message) => write!(f, ", {:?}", message),
+                    None => Ok(()),
+                }
             }
             Expression::Assign(assign) => {
                 self.print_lvalue(&assign.lvalue, f)?;
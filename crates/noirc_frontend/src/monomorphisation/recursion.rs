@@ -0,0 +1,552 @@
+//! Every call is inlined when the program is compiled into SSA, so a recursive function cannot be compiled as is.
+//! The call graph of the monomorphised program is checked for cycles, and a recursive call whose arguments
+//! are partly known at compile time is unrolled: the callee is specialised for the constant arguments,
+//! which folds its conditionals until the recursion ends.
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use acvm::FieldElement;
+use noirc_errors::Location;
+
+use super::ast::{
+    Call, DefinitionId, Expression, FuncId, Function, LValue, Let, Literal, Program, Type,
+};
+use super::errors::{CallSite, RecursionError};
+use crate::{util::vecmap, BinaryOpKind, Signedness, UnaryOp};
+
+/// The default number of nested recursive calls which can be unrolled
+pub const DEFAULT_RECURSION_LIMIT: u32 = 100;
+
+/// Returns an error if a function of the program is recursive, unless its recursive calls
+/// are bounded by constant arguments, in which case they are unrolled up to `limit` nested calls.
+pub fn unroll_recursion(program: &mut Program, limit: u32) -> Result<(), RecursionError> {
+    let mut unroller = Unroller::new(program, limit);
+    if unroller.recursive.is_empty() {
+        return Ok(());
+    }
+
+    for index in 0..program.functions.len() {
+        if !unroller.recursive.contains(&index) {
+            let id = FuncId(index as u32);
+            let mut body = program.take_function_body(id);
+            unroller.process(program, &mut body, id, 0, false)?;
+            program[id].body = body;
+        }
+    }
+    Ok(())
+}
+
+/// The calls of each function, indexed by function
+type CallGraph = Vec<Vec<(FuncId, Location)>>;
+
+struct Unroller {
+    graph: CallGraph,
+    recursive: HashSet<usize>,
+    limit: u32,
+
+    /// The specialisations of the recursive functions, by function and values of the constant arguments
+    specialisations: HashMap<(FuncId, Vec<Option<FieldElement>>), FuncId>,
+    /// The function which has been specialised into each specialisation
+    origins: HashMap<FuncId, FuncId>,
+    /// The specialisations whose body is being unrolled
+    in_progress: HashSet<FuncId>,
+
+    /// The known values of the variables which are never assigned to
+    constants: HashMap<DefinitionId, Literal>,
+    assigned: HashSet<DefinitionId>,
+    next_definition_id: u32,
+}
+
+impl Unroller {
+    fn new(program: &mut Program, limit: u32) -> Unroller {
+        let mut graph = Vec::new();
+        let mut assigned = HashSet::new();
+        let mut next_definition_id = 0;
+        for function in &mut program.functions {
+            let mut calls = Vec::new();
+            visit(&mut function.body, &mut |expr| match expr {
+                Expression::Call(call) => calls.push((call.func_id, call.location)),
+                Expression::Assign(assign) => {
+                    assigned.insert(lvalue_root(&assign.lvalue));
+                }
+                _ => (),
+            });
+            graph.push(calls);
+
+            for (id, ..) in &function.parameters {
+                next_definition_id = next_definition_id.max(id.0 + 1);
+            }
+            visit(&mut function.body, &mut |expr| {
+                let id = match expr {
+                    Expression::Ident(ident) => Some(ident.id),
+                    _ => defined_id(expr),
+                };
+                if let Some(id) = id {
+                    next_definition_id = next_definition_id.max(id.0 + 1);
+                }
+            });
+        }
+
+        let recursive =
+            (0..graph.len()).filter(|index| find_path(&graph, *index, *index).is_some()).collect();
+
+        Unroller {
+            graph,
+            recursive,
+            limit,
+            specialisations: HashMap::new(),
+            origins: HashMap::new(),
+            in_progress: HashSet::new(),
+            constants: HashMap::new(),
+            assigned,
+            next_definition_id,
+        }
+    }
+
+    /// Unrolls the recursive calls of an expression of the caller function. When `fold` is set,
+    /// the expression is also simplified with the constants, which removes the branches which are not taken.
+    fn process(
+        &mut self,
+        program: &mut Program,
+        expr: &mut Expression,
+        caller: FuncId,
+        depth: u32,
+        fold: bool,
+    ) -> Result<(), RecursionError> {
+        if fold {
+            if let Expression::If(if_expr) = expr {
+                self.process(program, &mut if_expr.condition, caller, depth, fold)?;
+                if let Expression::Literal(Literal::Bool(condition)) = *if_expr.condition {
+                    let branch = if condition {
+                        let empty = Box::new(Expression::Block(Vec::new()));
+                        Some(std::mem::replace(&mut if_expr.consequence, empty))
+                    } else {
+                        if_expr.alternative.take()
+                    };
+                    *expr = branch.map_or(Expression::Block(Vec::new()), |branch| *branch);
+                    return self.process(program, expr, caller, depth, fold);
+                }
+                self.process(program, &mut if_expr.consequence, caller, depth, fold)?;
+                if let Some(alternative) = &mut if_expr.alternative {
+                    self.process(program, alternative, caller, depth, fold)?;
+                }
+                return Ok(());
+            }
+        }
+
+        for child in children(expr) {
+            self.process(program, child, caller, depth, fold)?;
+        }
+
+        match expr {
+            Expression::Let(let_expr) if !self.assigned.contains(&let_expr.id) => {
+                if let Some(value) = self.evaluate(&let_expr.expression) {
+                    self.constants.insert(let_expr.id, value);
+                }
+            }
+            Expression::Call(call) if self.recursive.contains(&(call.func_id.0 as usize)) => {
+                self.unroll_call(program, call, caller, depth)?;
+            }
+            _ if fold => {
+                if let Some(value) = self.evaluate(expr) {
+                    *expr = Expression::Literal(value);
+                }
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
+    // Replaces a call to a recursive function by a call to its specialisation for the constant arguments
+    fn unroll_call(
+        &mut self,
+        program: &mut Program,
+        call: &mut Call,
+        caller: FuncId,
+        depth: u32,
+    ) -> Result<(), RecursionError> {
+        let callee = call.func_id;
+        let constants = vecmap(&call.arguments, |argument| self.evaluate(argument));
+        let key = (callee, vecmap(&constants, |constant| constant.as_ref().map(literal_value)));
+
+        if program[callee].unconstrained || constants.iter().all(Option::is_none) {
+            let cycle = self.cycle(program, caller, callee, call.location);
+            return Err(RecursionError::Unbounded { cycle });
+        }
+        let specialisation = match self.specialisations.get(&key) {
+            // The recursion does not progress towards a base case
+            Some(id) if self.in_progress.contains(id) => {
+                let cycle = self.cycle(program, caller, callee, call.location);
+                return Err(RecursionError::Unbounded { cycle });
+            }
+            Some(id) => *id,
+            None if depth >= self.limit => {
+                let cycle = self.cycle(program, caller, callee, call.location);
+                return Err(RecursionError::LimitExceeded { cycle, limit: self.limit });
+            }
+            None => self.specialise(program, callee, &constants, key, depth + 1)?,
+        };
+
+        call.func_id = specialisation;
+        let arguments = std::mem::take(&mut call.arguments);
+        call.arguments = arguments
+            .into_iter()
+            .zip(constants)
+            .filter(|(_, constant)| constant.is_none())
+            .map(|(argument, _)| argument)
+            .collect();
+        Ok(())
+    }
+
+    // Creates a copy of the function where the constant parameters are bound to their values
+    fn specialise(
+        &mut self,
+        program: &mut Program,
+        function_id: FuncId,
+        constants: &[Option<Literal>],
+        key: (FuncId, Vec<Option<FieldElement>>),
+        depth: u32,
+    ) -> Result<FuncId, RecursionError> {
+        let function = &program[function_id];
+        let mut ids = HashMap::new();
+        let mut parameters = Vec::new();
+        let mut statements = Vec::new();
+        for ((id, mutable, name, typ), constant) in function.parameters.iter().zip(constants) {
+            let new_id = self.next_definition_id();
+            ids.insert(*id, new_id);
+            match constant {
+                Some(value) => {
+                    let value = match value {
+                        Literal::Integer(value, _) => Literal::Integer(*value, typ.clone()),
+                        value => value.clone(),
+                    };
+                    let expression = Box::new(Expression::Literal(value));
+                    statements.push(Expression::Let(Let {
+                        id: new_id,
+                        name: name.clone(),
+                        expression,
+                    }));
+                }
+                None => parameters.push((new_id, *mutable, name.clone(), typ.clone())),
+            }
+        }
+
+        let mut body = function.body.clone();
+        self.rename(&mut body, &mut ids);
+        statements.push(body);
+        let mut body = Expression::Block(statements);
+        visit(&mut body, &mut |expr| {
+            if let Expression::Assign(assign) = expr {
+                self.assigned.insert(lvalue_root(&assign.lvalue));
+            }
+        });
+
+        let id = FuncId(program.functions.len() as u32);
+        let name = function.name.clone();
+        let return_type = function.return_type.clone();
+        program.push_function(Function {
+            id,
+            name,
+            parameters,
+            body: Expression::Block(Vec::new()),
+            return_type,
+            unconstrained: false,
+        });
+        self.specialisations.insert(key, id);
+        self.origins.insert(id, function_id);
+
+        self.in_progress.insert(id);
+        self.process(program, &mut body, id, depth, true)?;
+        self.in_progress.remove(&id);
+        program[id].body = body;
+        Ok(id)
+    }
+
+    // Gives fresh ids to the definitions of a copied function body
+    fn rename(&mut self, expr: &mut Expression, ids: &mut HashMap<DefinitionId, DefinitionId>) {
+        if let Some(id) = defined_id(expr) {
+            let new_id = self.next_definition_id();
+            ids.insert(id, new_id);
+        }
+        match expr {
+            Expression::Ident(ident) => rename_id(&mut ident.id, ids),
+            Expression::Let(let_expr) => rename_id(&mut let_expr.id, ids),
+            Expression::For(for_expr) => rename_id(&mut for_expr.index_variable, ids),
+            Expression::Assign(assign) => rename_lvalue(&mut assign.lvalue, ids),
+            _ => (),
+        }
+        for child in children(expr) {
+            self.rename(child, ids);
+        }
+    }
+
+    fn next_definition_id(&mut self) -> DefinitionId {
+        let id = self.next_definition_id;
+        self.next_definition_id += 1;
+        DefinitionId(id)
+    }
+
+    // Returns the calls of a cycle through the call from caller to callee
+    fn cycle(
+        &self,
+        program: &Program,
+        caller: FuncId,
+        callee: FuncId,
+        location: Location,
+    ) -> Vec<CallSite> {
+        let caller = self.origins.get(&caller).copied().unwrap_or(caller).0 as usize;
+        let callee = callee.0 as usize;
+        let calls = match find_path(&self.graph, callee, caller) {
+            Some(path) => std::iter::once((caller, callee, location)).chain(path).collect(),
+            None => find_path(&self.graph, callee, callee).unwrap_or_default(),
+        };
+        vecmap(calls, |(caller, callee, location)| CallSite {
+            caller: program.functions[caller].name.clone(),
+            callee: program.functions[callee].name.clone(),
+            location,
+        })
+    }
+
+    // Returns the value of an expression, if it is a constant integer or boolean
+    fn evaluate(&self, expr: &Expression) -> Option<Literal> {
+        match expr {
+            Expression::Literal(literal @ (Literal::Integer(..) | Literal::Bool(_))) => {
+                Some(literal.clone())
+            }
+            Expression::Ident(ident) => self.constants.get(&ident.id).cloned(),
+            Expression::Binary(binary) => {
+                let lhs = self.evaluate(&binary.lhs)?;
+                let rhs = self.evaluate(&binary.rhs)?;
+                evaluate_binary(lhs, binary.operator, rhs)
+            }
+            Expression::Unary(unary) => match (unary.operator, self.evaluate(&unary.rhs)?) {
+                (UnaryOp::Not, Literal::Bool(value)) => Some(Literal::Bool(!value)),
+                (UnaryOp::Minus, Literal::Integer(value, Type::Field)) => {
+                    Some(Literal::Integer(-value, Type::Field))
+                }
+                _ => None,
+            },
+            Expression::Cast(cast) => {
+                let value = match self.evaluate(&cast.lhs)? {
+                    Literal::Bool(value) => literal_value(&Literal::Bool(value)),
+                    Literal::Integer(
+                        value,
+                        Type::Field | Type::Integer(Signedness::Unsigned, _),
+                    ) => value,
+                    _ => return None,
+                };
+                match &cast.r#type {
+                    Type::Field => Some(Literal::Integer(value, Type::Field)),
+                    typ @ Type::Integer(Signedness::Unsigned, bits)
+                        if value.num_bits() <= *bits =>
+                    {
+                        Some(Literal::Integer(value, typ.clone()))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+// Values which do not fit in their type are not folded, so that they are handled by the evaluator
+fn evaluate_binary(lhs: Literal, operator: BinaryOpKind, rhs: Literal) -> Option<Literal> {
+    use BinaryOpKind::*;
+    let (lhs, rhs, typ) = match (lhs, rhs) {
+        (Literal::Bool(lhs), Literal::Bool(rhs)) => {
+            let value = match operator {
+                Equal => lhs == rhs,
+                NotEqual => lhs != rhs,
+                And => lhs & rhs,
+                Or => lhs | rhs,
+                Xor => lhs ^ rhs,
+                _ => return None,
+            };
+            return Some(Literal::Bool(value));
+        }
+        (Literal::Integer(lhs, typ), Literal::Integer(rhs, _)) => (lhs, rhs, typ),
+        _ => return None,
+    };
+
+    match operator {
+        Equal => return Some(Literal::Bool(lhs == rhs)),
+        NotEqual => return Some(Literal::Bool(lhs != rhs)),
+        _ => (),
+    }
+    match typ {
+        Type::Field => {
+            let value = match operator {
+                Add => lhs + rhs,
+                Subtract => lhs - rhs,
+                Multiply => lhs * rhs,
+                Divide if !rhs.is_zero() => lhs / rhs,
+                _ => return compare(lhs, operator, rhs),
+            };
+            Some(Literal::Integer(value, typ))
+        }
+        Type::Integer(Signedness::Unsigned, bits) if bits < 128 => {
+            let (l, r) = (lhs.try_into_u128()?, rhs.try_into_u128()?);
+            let value = match operator {
+                Add => l.checked_add(r)?,
+                Subtract => l.checked_sub(r)?,
+                Multiply => l.checked_mul(r)?,
+                Divide => l.checked_div(r)?,
+                Modulo => l.checked_rem(r)?,
+                And => l & r,
+                Or => l | r,
+                Xor => l ^ r,
+                _ => return compare(lhs, operator, rhs),
+            };
+            (value >> bits == 0).then(|| Literal::Integer(FieldElement::from(value), typ))
+        }
+        _ => None,
+    }
+}
+
+fn compare(lhs: FieldElement, operator: BinaryOpKind, rhs: FieldElement) -> Option<Literal> {
+    let (lhs, rhs) = (lhs.try_into_u128()?, rhs.try_into_u128()?);
+    let value = match operator {
+        BinaryOpKind::Less => lhs < rhs,
+        BinaryOpKind::LessEqual => lhs <= rhs,
+        BinaryOpKind::Greater => lhs > rhs,
+        BinaryOpKind::GreaterEqual => lhs >= rhs,
+        _ => return None,
+    };
+    Some(Literal::Bool(value))
+}
+
+fn literal_value(literal: &Literal) -> FieldElement {
+    match literal {
+        Literal::Integer(value, _) => *value,
+        Literal::Bool(value) => {
+            if *value {
+                FieldElement::one()
+            } else {
+                FieldElement::zero()
+            }
+        }
+        _ => unreachable!("only integers and booleans are constant arguments"),
+    }
+}
+
+// Returns the calls of a shortest path from start to target in the call graph, which is not empty
+fn find_path(
+    graph: &CallGraph,
+    start: usize,
+    target: usize,
+) -> Option<Vec<(usize, usize, Location)>> {
+    let mut parents = HashMap::new();
+    let mut queue = VecDeque::from(vec![start]);
+    while let Some(caller) = queue.pop_front() {
+        for (callee, location) in &graph[caller] {
+            let callee = callee.0 as usize;
+            if parents.contains_key(&callee) {
+                continue;
+            }
+            parents.insert(callee, (caller, *location));
+            if callee == target {
+                let mut path = Vec::new();
+                let mut function = target;
+                loop {
+                    let (caller, location) = parents[&function];
+                    path.push((caller, function, location));
+                    if caller == start {
+                        break;
+                    }
+                    function = caller;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            queue.push_back(callee);
+        }
+    }
+    None
+}
+
+fn defined_id(expr: &Expression) -> Option<DefinitionId> {
+    match expr {
+        Expression::Let(let_expr) => Some(let_expr.id),
+        Expression::For(for_expr) => Some(for_expr.index_variable),
+        _ => None,
+    }
+}
+
+fn rename_id(id: &mut DefinitionId, ids: &HashMap<DefinitionId, DefinitionId>) {
+    if let Some(new_id) = ids.get(id) {
+        *id = *new_id;
+    }
+}
+
+fn rename_lvalue(lvalue: &mut LValue, ids: &HashMap<DefinitionId, DefinitionId>) {
+    match lvalue {
+        LValue::Ident(ident) => rename_id(&mut ident.id, ids),
+        LValue::Index { array, .. } => rename_lvalue(array, ids),
+        LValue::MemberAccess { object, .. } => rename_lvalue(object, ids),
+    }
+}
+
+fn lvalue_root(lvalue: &LValue) -> DefinitionId {
+    match lvalue {
+        LValue::Ident(ident) => ident.id,
+        LValue::Index { array, .. } => lvalue_root(array),
+        LValue::MemberAccess { object, .. } => lvalue_root(object),
+    }
+}
+
+// Calls f on the expression and all its sub-expressions
+fn visit(expr: &mut Expression, f: &mut impl FnMut(&mut Expression)) {
+    f(expr);
+    for child in children(expr) {
+        visit(child, f);
+    }
+}
+
+fn children(expr: &mut Expression) -> Vec<&mut Expression> {
+    match expr {
+        Expression::Ident(_) => Vec::new(),
+        Expression::Literal(Literal::Array(array)) => array.contents.iter_mut().collect(),
+        Expression::Literal(_) => Vec::new(),
+        Expression::Block(exprs) | Expression::Tuple(exprs) => exprs.iter_mut().collect(),
+        Expression::Unary(unary) => vec![unary.rhs.as_mut()],
+        Expression::Binary(binary) => vec![binary.lhs.as_mut(), binary.rhs.as_mut()],
+        Expression::Index(index) => vec![index.collection.as_mut(), index.index.as_mut()],
+        Expression::Cast(cast) => vec![cast.lhs.as_mut()],
+        Expression::For(for_expr) => vec![
+            for_expr.start_range.as_mut(),
+            for_expr.end_range.as_mut(),
+            for_expr.block.as_mut(),
+        ],
+        Expression::If(if_expr) => {
+            let mut exprs = vec![if_expr.condition.as_mut(), if_expr.consequence.as_mut()];
+            if let Some(alternative) = &mut if_expr.alternative {
+                exprs.push(alternative.as_mut());
+            }
+            exprs
+        }
+        Expression::ExtractTupleField(expr, _)
+        | Expression::Constrain(expr, ..)
+        | Expression::Semi(expr) => vec![expr.as_mut()],
+        Expression::Call(call) => call.arguments.iter_mut().collect(),
+        Expression::CallBuiltin(call) => call.arguments.iter_mut().collect(),
+        Expression::CallLowLevel(call) => call.arguments.iter_mut().collect(),
+        Expression::Let(let_expr) => vec![let_expr.expression.as_mut()],
+        Expression::Assign(assign) => {
+            let mut exprs = lvalue_children(&mut assign.lvalue);
+            exprs.push(assign.expression.as_mut());
+            exprs
+        }
+    }
+}
+
+fn lvalue_children(lvalue: &mut LValue) -> Vec<&mut Expression> {
+    match lvalue {
+        LValue::Ident(_) => Vec::new(),
+        LValue::Index { array, index } => {
+            let mut exprs = lvalue_children(array);
+            exprs.push(index.as_mut());
+            exprs
+        }
+        LValue::MemberAccess { object, .. } => lvalue_children(object),
+    }
+}
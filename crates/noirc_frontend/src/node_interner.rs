@@ -4,19 +4,19 @@ use arena::{Arena, Index};
 use fm::FileId;
 use noirc_errors::{Location, Span, Spanned};
 
-use crate::ast::Ident;
+use crate::ast::{Ident, NoirTypeAlias};
 use crate::graph::CrateId;
 use crate::hir::def_collector::dc_crate::UnresolvedStruct;
 use crate::hir::def_map::{LocalModuleId, ModuleId};
 use crate::hir_def::stmt::HirLetStatement;
-use crate::hir_def::types::{StructType, Type};
+use crate::hir_def::types::{StructType, Type, TypeAliasType};
 use crate::hir_def::{
     expr::HirExpression,
     function::{FuncMeta, HirFunction},
     stmt::HirStatement,
 };
 use crate::util::vecmap;
-use crate::{Shared, TypeBinding, TypeBindings, TypeVariableId};
+use crate::{Shared, Signedness, TypeBinding, TypeBindings, TypeVariableId};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub struct DefinitionId(usize);
@@ -149,6 +149,13 @@ pub struct NodeInterner {
     // methods from impls to the type.
     structs: HashMap<StructId, Shared<StructType>>,
 
+    // Type alias map, indexed by TypeAliasId.
+    //
+    // Aliases are pushed during def collection with an Error type, then filled in
+    // once resolved. Since the interner is shared between crates, aliases imported
+    // from a dependency are already resolved by the time they are used.
+    type_aliases: Vec<TypeAliasType>,
+
     /// Map from ExprId (referring to a Function/Method call) to its corresponding TypeBindings,
     /// filled out during type checking from instantiated variables. Used during monomorphisation
     /// to map callsite types back onto function parameter types, and undo this binding as needed.
@@ -166,9 +173,41 @@ pub struct NodeInterner {
 
     global_constants: HashMap<StmtId, GlobalConstInfo>, // NOTE: currently only used for checking repeat global consts and restricting their scope to a module
 
+    /// Methods defined in impls on builtin types, e.g. `impl Field`. Unlike struct methods
+    /// which are stored on their StructType, these are visible from every crate.
+    primitive_methods: HashMap<(TypeMethodKey, String), FuncId>,
+
     next_type_variable_id: usize,
 }
 
+/// The builtin types which methods can be implemented on. The length of an array
+/// is not part of the key, but its element type is, unless the impl is generic over it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TypeMethodKey {
+    FieldElement,
+    /// `None` is the key of `impl<T> [T]`, whose methods apply to every array
+    Array(Option<Box<TypeMethodKey>>),
+    Integer(Signedness, u32),
+    Bool,
+}
+
+impl TypeMethodKey {
+    /// Returns the keys of the impls whose methods apply to this type, from the most specific one.
+    /// e.g. the methods of `[[u8]]` are looked up in `impl [[u8]]`, `impl<T> [[T]]`, then `impl<T> [T]`
+    fn candidates(self) -> Vec<TypeMethodKey> {
+        match self {
+            TypeMethodKey::Array(Some(element)) => {
+                let mut candidates = vecmap(element.candidates(), |element| {
+                    TypeMethodKey::Array(Some(Box::new(element)))
+                });
+                candidates.push(TypeMethodKey::Array(None));
+                candidates
+            }
+            key => vec![key],
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DefinitionInfo {
     pub name: String,
@@ -192,11 +231,13 @@ impl Default for NodeInterner {
             definitions: vec![],
             id_to_type: HashMap::new(),
             structs: HashMap::new(),
+            type_aliases: Vec::new(),
             instantiation_bindings: HashMap::new(),
             function_types: HashMap::new(),
             field_indices: HashMap::new(),
             next_type_variable_id: 0,
             global_constants: HashMap::new(),
+            primitive_methods: HashMap::new(),
         };
 
         // An empty block expression is used often, we add this into the `node` on startup
@@ -253,6 +294,27 @@ impl NodeInterner {
         );
     }
 
+    pub fn push_type_alias(&mut self, typ: &NoirTypeAlias) -> TypeAliasId {
+        let id = TypeAliasId(self.type_aliases.len());
+        self.type_aliases.push(TypeAliasType {
+            id,
+            name: typ.name.clone(),
+            generics: vec![],
+            implicit_generics: vec![],
+            typ: Type::Error,
+            span: typ.span,
+        });
+        id
+    }
+
+    pub fn update_type_alias(&mut self, id: TypeAliasId, f: impl FnOnce(&mut TypeAliasType)) {
+        f(&mut self.type_aliases[id.0])
+    }
+
+    pub fn get_type_alias(&self, id: TypeAliasId) -> &TypeAliasType {
+        &self.type_aliases[id.0]
+    }
+
     pub fn update_struct(&mut self, type_id: StructId, f: impl FnOnce(&mut StructType)) {
         let mut value = self.structs.get_mut(&type_id).unwrap().borrow_mut();
         f(&mut value)
@@ -504,4 +566,85 @@ impl NodeInterner {
     pub fn set_field_index(&mut self, expr_id: ExprId, index: usize) {
         self.field_indices.insert(expr_id, index);
     }
+
+    /// Adds a method to a builtin type, returning the previous method with the same name if any
+    pub fn add_method(&mut self, key: TypeMethodKey, name: String, id: FuncId) -> Option<FuncId> {
+        self.primitive_methods.insert((key, name), id)
+    }
+
+    /// Looks up a method defined in an impl on the given builtin type,
+    /// falling back to the impls which are generic over the element type of arrays
+    pub fn lookup_primitive_method(&self, typ: &Type, method_name: &str) -> Option<FuncId> {
+        let key = get_type_method_key(typ)?;
+        key.candidates()
+            .into_iter()
+            .find_map(|key| self.primitive_methods.get(&(key, method_name.to_owned())).copied())
+    }
+}
+
+fn get_type_method_key(typ: &Type) -> Option<TypeMethodKey> {
+    match typ {
+        Type::FieldElement(_) => Some(TypeMethodKey::FieldElement),
+        Type::Array(_, element) => {
+            Some(TypeMethodKey::Array(get_type_method_key(element).map(Box::new)))
+        }
+        Type::Integer(_, sign, bits) => Some(TypeMethodKey::Integer(*sign, *bits)),
+        Type::Bool(_) => Some(TypeMethodKey::Bool),
+        Type::TypeVariable(var) | Type::NamedGeneric(var, _) => match &*var.borrow() {
+            TypeBinding::Bound(typ) => get_type_method_key(typ),
+            TypeBinding::Unbound(_) => None,
+        },
+        // Integer literals default to a Field if they are not bound to an integer type
+        Type::PolymorphicInteger(_, var) => match &*var.borrow() {
+            TypeBinding::Bound(typ) => get_type_method_key(typ),
+            TypeBinding::Unbound(_) => Some(TypeMethodKey::FieldElement),
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{NodeInterner, TypeMethodKey};
+    use crate::{IsConst, Signedness, Type};
+
+    fn array_of(element: Type) -> Type {
+        Type::Array(Box::new(Type::ArrayLength(4)), Box::new(element))
+    }
+
+    #[test]
+    fn array_methods_depend_on_the_element_type() {
+        let mut interner = NodeInterner::default();
+        let field = Type::FieldElement(IsConst::No(None));
+        let byte = Type::Integer(IsConst::No(None), Signedness::Unsigned, 8);
+        let field_key = || TypeMethodKey::Array(Some(Box::new(TypeMethodKey::FieldElement)));
+
+        // impl [Field] { fn sum }
+        let field_sum = interner.push_empty_fn();
+        assert_eq!(interner.add_method(field_key(), "sum".into(), field_sum), None);
+        assert_eq!(
+            interner.lookup_primitive_method(&array_of(field.clone()), "sum"),
+            Some(field_sum)
+        );
+        assert_eq!(interner.lookup_primitive_method(&array_of(byte.clone()), "sum"), None);
+
+        // impl [u8] { fn sum } does not conflict with it
+        let byte_key =
+            TypeMethodKey::Array(Some(Box::new(TypeMethodKey::Integer(Signedness::Unsigned, 8))));
+        let byte_sum = interner.push_empty_fn();
+        assert_eq!(interner.add_method(byte_key, "sum".into(), byte_sum), None);
+        assert_eq!(
+            interner.lookup_primitive_method(&array_of(byte.clone()), "sum"),
+            Some(byte_sum)
+        );
+
+        // impl<T> [T] { fn len } applies to arrays of any element, including nested arrays
+        let len = interner.push_empty_fn();
+        assert_eq!(interner.add_method(TypeMethodKey::Array(None), "len".into(), len), None);
+        assert_eq!(interner.lookup_primitive_method(&array_of(byte.clone()), "len"), Some(len));
+        assert_eq!(interner.lookup_primitive_method(&array_of(array_of(field)), "len"), Some(len));
+        assert_eq!(interner.lookup_primitive_method(&byte, "len"), None);
+
+        assert_eq!(interner.add_method(field_key(), "sum".into(), len), Some(field_sum));
+    }
 }
@@ -2,8 +2,8 @@ mod errors;
 #[allow(clippy::module_inception)]
 mod parser;
 
-use crate::token::{Keyword, Token};
-use crate::{ast::ImportStatement, Expression, NoirStruct};
+use crate::token::{Attribute, Keyword, Token};
+use crate::{ast::ImportStatement, Expression, NoirStruct, NoirTypeAlias};
 use crate::{Ident, LetStatement, NoirFunction, NoirImpl, Recoverable, Statement};
 
 use chumsky::prelude::*;
@@ -15,10 +15,11 @@ pub use parser::parse_program;
 #[derive(Debug, Clone)]
 pub(crate) enum TopLevelStatement {
     Function(NoirFunction),
-    Module(Ident),
+    Module(ModuleDeclaration),
     Import(ImportStatement),
     Struct(NoirStruct),
     Impl(NoirImpl),
+    TypeAlias(NoirTypeAlias),
     SubModule(SubModule),
     GlobalConst(LetStatement),
     Error,
@@ -198,11 +199,20 @@ pub struct ParsedModule {
     pub functions: Vec<NoirFunction>,
     pub types: Vec<NoirStruct>,
     pub impls: Vec<NoirImpl>,
-    pub module_decls: Vec<Ident>,
+    pub type_aliases: Vec<NoirTypeAlias>,
+    pub module_decls: Vec<ModuleDeclaration>,
     pub submodules: Vec<SubModule>,
     pub global_constants: Vec<LetStatement>,
 }
 
+/// A `mod foo;` declaration whose contents live in another file
+#[derive(Clone, Debug)]
+pub struct ModuleDeclaration {
+    pub name: Ident,
+    /// Set by a `#[path = "..."]` attribute, relative to the directory of the declaring file
+    pub path: Option<String>,
+}
+
 #[derive(Clone, Debug)]
 pub struct SubModule {
     pub name: Ident,
@@ -222,12 +232,16 @@ impl ParsedModule {
         self.impls.push(r#impl);
     }
 
+    fn push_type_alias(&mut self, type_alias: NoirTypeAlias) {
+        self.type_aliases.push(type_alias);
+    }
+
     fn push_import(&mut self, import_stmt: ImportStatement) {
         self.imports.push(import_stmt);
     }
 
-    fn push_module_decl(&mut self, mod_name: Ident) {
-        self.module_decls.push(mod_name);
+    fn push_module_decl(&mut self, mod_decl: ModuleDeclaration) {
+        self.module_decls.push(mod_decl);
     }
 
     fn push_submodule(&mut self, submodule: SubModule) {
@@ -271,6 +285,7 @@ impl Precedence {
             Token::ShiftLeft => Precedence::Shift,
             Token::ShiftRight => Precedence::Shift,
             Token::Slash => Precedence::Product,
+            Token::Percent => Precedence::Product,
             Token::Star => Precedence::Product,
             _ => return None,
         };
@@ -299,10 +314,11 @@ impl std::fmt::Display for TopLevelStatement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             TopLevelStatement::Function(fun) => fun.fmt(f),
-            TopLevelStatement::Module(m) => write!(f, "mod {}", m),
+            TopLevelStatement::Module(m) => m.fmt(f),
             TopLevelStatement::Import(i) => i.fmt(f),
             TopLevelStatement::Struct(s) => s.fmt(f),
             TopLevelStatement::Impl(i) => i.fmt(f),
+            TopLevelStatement::TypeAlias(t) => t.fmt(f),
             TopLevelStatement::SubModule(s) => s.fmt(f),
             TopLevelStatement::GlobalConst(c) => c.fmt(f),
             TopLevelStatement::Error => write!(f, "error"),
@@ -310,10 +326,19 @@ impl std::fmt::Display for TopLevelStatement {
     }
 }
 
+impl std::fmt::Display for ModuleDeclaration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(path) = &self.path {
+            writeln!(f, "{}", Attribute::Path(path.clone()))?;
+        }
+        write!(f, "mod {}", self.name)
+    }
+}
+
 impl std::fmt::Display for ParsedModule {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for decl in &self.module_decls {
-            writeln!(f, "mod {};", decl)?;
+            writeln!(f, "{};", decl)?;
         }
 
         for import in &self.imports {
@@ -328,6 +353,10 @@ impl std::fmt::Display for ParsedModule {
             write!(f, "{}", type_)?;
         }
 
+        for type_alias in &self.type_aliases {
+            writeln!(f, "{};", type_alias)?;
+        }
+
         for function in &self.functions {
             write!(f, "{}", function)?;
         }
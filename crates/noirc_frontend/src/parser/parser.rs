@@ -2,8 +2,8 @@ use std::iter::repeat;
 
 use super::{
     foldl_with_span, parameter_name_recovery, parameter_recovery, parenthesized, then_commit,
-    then_commit_ignore, top_level_statement_recovery, ExprParser, NoirParser, ParsedModule,
-    ParserError, Precedence, SubModule, TopLevelStatement,
+    then_commit_ignore, top_level_statement_recovery, ExprParser, ModuleDeclaration, NoirParser,
+    ParsedModule, ParserError, Precedence, SubModule, TopLevelStatement,
 };
 use crate::ast::{
     Expression, ExpressionKind, LetStatement, Statement, UnresolvedArraySize, UnresolvedType,
@@ -15,7 +15,8 @@ use crate::util::vecmap;
 use crate::{
     AssignStatement, BinaryOp, BinaryOpKind, BlockExpression, ConstrainStatement, ForExpression,
     FunctionDefinition, Ident, IfExpression, ImportStatement, InfixExpression, IsConst, LValue,
-    NoirFunction, NoirImpl, NoirStruct, Path, PathKind, Pattern, Recoverable, UnaryOp,
+    NoirFunction, NoirImpl, NoirStruct, NoirTypeAlias, Path, PathKind, Pattern, Recoverable,
+    UnaryOp,
 };
 
 use chumsky::prelude::*;
@@ -49,6 +50,7 @@ fn module() -> impl NoirParser<ParsedModule> {
                     TopLevelStatement::Import(i) => program.push_import(i),
                     TopLevelStatement::Struct(s) => program.push_type(s),
                     TopLevelStatement::Impl(i) => program.push_impl(i),
+                    TopLevelStatement::TypeAlias(t) => program.push_type_alias(t),
                     TopLevelStatement::SubModule(s) => program.push_submodule(s),
                     TopLevelStatement::GlobalConst(c) => program.push_global_const(c),
                     TopLevelStatement::Error => (),
@@ -65,6 +67,7 @@ fn top_level_statement(
         function_definition(false).map(TopLevelStatement::Function),
         struct_definition(),
         implementation(),
+        type_alias_definition().then_ignore(force(just(Token::Semicolon))),
         submodule(module_parser),
         module_declaration().then_ignore(force(just(Token::Semicolon))),
         use_statement().then_ignore(force(just(Token::Semicolon))),
@@ -94,8 +97,19 @@ fn submodule(module_parser: impl NoirParser<ParsedModule>) -> impl NoirParser<To
 }
 
 fn function_definition(allow_self: bool) -> impl NoirParser<NoirFunction> {
-    attribute()
+    let function_attribute = attribute().validate(|attribute, span, emit| {
+        if let Attribute::Path(_) = attribute {
+            emit(ParserError::with_reason(
+                "path attributes are only allowed on module declarations".to_owned(),
+                span,
+            ));
+        }
+        attribute
+    });
+
+    function_attribute
         .or_not()
+        .then(keyword(Keyword::Unconstrained).or_not().map(|token| token.is_some()))
         .then_ignore(keyword(Keyword::Fn))
         .then(ident())
         .then(generics())
@@ -104,13 +118,17 @@ fn function_definition(allow_self: bool) -> impl NoirParser<NoirFunction> {
         .then(block(expression()))
         .map(
             |(
-                ((((attribute, name), generics), parameters), (return_visibility, return_type)),
+                (
+                    ((((attribute, is_unconstrained), name), generics), parameters),
+                    (return_visibility, return_type),
+                ),
                 body,
             )| {
                 FunctionDefinition {
                     span: name.0.span(),
                     name,
                     attribute, // XXX: Currently we only have one attribute defined. If more attributes are needed per function, we can make this a vector and make attribute definition more expressive
+                    is_unconstrained,
                     generics,
                     parameters,
                     body,
@@ -152,6 +170,19 @@ fn struct_definition() -> impl NoirParser<TopLevelStatement> {
     )
 }
 
+fn type_alias_definition() -> impl NoirParser<TopLevelStatement> {
+    use self::Keyword::Type;
+
+    let p = ignore_then_commit(keyword(Type), ident());
+    let p = then_commit(p, generics());
+    let p = then_commit_ignore(p, just(Token::Assign));
+    let p = then_commit(p, parse_type());
+
+    p.map_with_span(|((name, generics), typ), span| {
+        TopLevelStatement::TypeAlias(NoirTypeAlias { name, generics, typ, span })
+    })
+}
+
 fn function_return_type() -> impl NoirParser<(AbiFEType, UnresolvedType)> {
     just(Token::Arrow)
         .ignore_then(optional_visibility())
@@ -200,24 +231,32 @@ fn nothing<T>() -> impl NoirParser<T> {
 }
 
 fn self_parameter() -> impl NoirParser<(Pattern, UnresolvedType, AbiFEType)> {
-    filter_map(move |span, found: Token| match found {
-        Token::Ident(ref word) if word == "self" => {
-            let ident = Ident::new(found, span);
-            let path = Path::from_single("Self".to_owned(), span);
-            let self_type = UnresolvedType::Named(path, vec![]);
-            Ok((Pattern::Identifier(ident), self_type, AbiFEType::Private))
-        }
+    let self_ident = filter_map(move |span, found: Token| match found {
+        Token::Ident(ref word) if word == "self" => Ok(Ident::new(found, span)),
         _ => Err(ParserError::expected_label("parameter".to_owned(), found, span)),
+    });
+
+    keyword(Keyword::Mut).or_not().then(self_ident).map_with_span(|(mutable, ident), span| {
+        let self_type = UnresolvedType::Named(Path::from_single("Self".to_owned(), span), vec![]);
+        let pattern = Pattern::Identifier(ident);
+        let pattern = match mutable {
+            Some(_) => Pattern::Mutable(Box::new(pattern), span),
+            None => pattern,
+        };
+        (pattern, self_type, AbiFEType::Private)
     })
 }
 
 fn implementation() -> impl NoirParser<TopLevelStatement> {
     keyword(Keyword::Impl)
-        .ignore_then(path())
+        .ignore_then(generics())
+        .then(parse_type().map_with_span(|typ, span| (typ, span)))
         .then_ignore(just(Token::LeftBrace))
         .then(function_definition(true).repeated())
         .then_ignore(just(Token::RightBrace))
-        .map(|(type_path, methods)| TopLevelStatement::Impl(NoirImpl { type_path, methods }))
+        .map(|((generics, (object_type, type_span)), methods)| {
+            TopLevelStatement::Impl(NoirImpl { object_type, type_span, generics, methods })
+        })
 }
 
 fn block_expr<'a, P>(expr_parser: P) -> impl NoirParser<Expression> + 'a
@@ -277,7 +316,20 @@ fn optional_type_annotation() -> impl NoirParser<UnresolvedType> {
 }
 
 fn module_declaration() -> impl NoirParser<TopLevelStatement> {
-    keyword(Keyword::Mod).ignore_then(ident()).map(TopLevelStatement::Module)
+    let path_attribute = attribute().validate(|attribute, span, emit| match attribute {
+        Attribute::Path(path) => Some(path),
+        other => {
+            emit(ParserError::with_reason(
+                format!("{} is not a valid attribute for a module declaration", other),
+                span,
+            ));
+            None
+        }
+    });
+
+    path_attribute.or_not().then_ignore(keyword(Keyword::Mod)).then(ident()).map(|(path, name)| {
+        TopLevelStatement::Module(ModuleDeclaration { name, path: path.flatten() })
+    })
 }
 
 fn use_statement() -> impl NoirParser<TopLevelStatement> {
@@ -338,7 +390,17 @@ where
     P: ExprParser + 'a,
 {
     ignore_then_commit(keyword(Keyword::Constrain).labelled("statement"), expr_parser)
-        .map(|expr| Statement::Constrain(ConstrainStatement(expr)))
+        .then(just(Token::Comma).ignore_then(string_literal()).or_not())
+        .map(|(expr, message)| Statement::Constrain(ConstrainStatement(expr, message)))
+}
+
+fn string_literal() -> impl NoirParser<String> {
+    filter_map(|span, token: Token| match token {
+        Token::Str(contents) => Ok(contents),
+        unexpected => {
+            Err(ParserError::expected_label("string literal".to_string(), unexpected, span))
+        }
+    })
 }
 
 fn declaration<'a, P>(expr_parser: P) -> impl NoirParser<Statement> + 'a
@@ -938,7 +1000,7 @@ mod test {
 
     #[test]
     fn parse_infix() {
-        let valid = vec!["x + 6", "x - k", "x + (x + a)", " x * (x + a) + (x - 4)"];
+        let valid = vec!["x + 6", "x - k", "x + (x + a)", " x * (x + a) + (x - 4)", "x % 3 * y"];
         parse_all(expression(), valid);
         parse_all_failing(expression(), vec!["y ! x"]);
     }
@@ -1032,6 +1094,16 @@ mod test {
     fn parse_constrain() {
         parse_with(constrain(expression()), "constrain x == y").unwrap();
 
+        // The message is reported when the constraint is not satisfied
+        let src = r#"constrain x == y, "balance too low""#;
+        match parse_with(constrain(expression()), src).unwrap() {
+            Statement::Constrain(ConstrainStatement(_, message)) => {
+                assert_eq!(message, Some("balance too low".to_string()))
+            }
+            _ => unreachable!(),
+        }
+        parse_with(constrain(expression()), "constrain x == y, z").unwrap_err();
+
         // Currently we disallow constrain statements where the outer infix operator
         // produces a value. This would require an implicit `==` which
         // may not be intuitive to the user.
@@ -1042,6 +1114,7 @@ mod test {
             BinaryOpKind::And,
             BinaryOpKind::Subtract,
             BinaryOpKind::Divide,
+            BinaryOpKind::Modulo,
             BinaryOpKind::Multiply,
             BinaryOpKind::Or,
         ];
@@ -1112,12 +1185,18 @@ mod test {
                 "fn f(f: pub Field, y : Field, z : const Field) -> u8 { x + a }",
                 "fn func_name(f: Field, y : pub Field, z : pub [u8;5],) {}",
                 "fn func_name(x: [Field], y : [Field;2],y : pub [Field;2], z : pub [u8;5])  {}",
+                "unconstrained fn sqrt(x: u32) -> u32 { x }",
             ],
         );
 
         parse_all_failing(
             function_definition(false),
-            vec!["fn x2( f: []Field,,) {}", "fn ( f: []Field) {}", "fn ( f: []Field) {}"],
+            vec![
+                "fn x2( f: []Field,,) {}",
+                "fn ( f: []Field) {}",
+                "fn ( f: []Field) {}",
+                "fn unconstrained f() {}",
+            ],
         );
     }
 
@@ -1187,6 +1266,16 @@ mod test {
     fn parse_module_declaration() {
         parse_with(module_declaration(), "mod foo").unwrap();
         parse_with(module_declaration(), "mod 1").unwrap_err();
+
+        match parse_with(module_declaration(), "#[path = \"foo/bar baz.nr\"] mod foo").unwrap() {
+            TopLevelStatement::Module(decl) => {
+                assert_eq!(decl.name.0.contents, "foo");
+                assert_eq!(decl.path.as_deref(), Some("foo/bar baz.nr"));
+            }
+            other => panic!("expected a module declaration, found {}", other),
+        }
+        parse_with(module_declaration(), "#[builtin(foo)] mod foo").unwrap_err();
+        parse_with(function_definition(false), "#[path = \"foo.nr\"] fn foo() {}").unwrap_err();
     }
 
     #[test]
@@ -1260,6 +1349,34 @@ mod test {
         parse_all_failing(struct_definition(), failing);
     }
 
+    #[test]
+    fn parse_type_aliases() {
+        let cases = vec![
+            "type Hash = [u8; 32]",
+            "type Pair<T> = (T, T)",
+            "type Point = foo::Point",
+            "type Wrapper<T, U> = Foo<T, [U; 2]>",
+        ];
+        parse_all(type_alias_definition(), cases);
+
+        let failing = vec!["type = Field", "type Foo Field", "type Foo<> = Field"];
+        parse_all_failing(type_alias_definition(), failing);
+    }
+
+    #[test]
+    fn parse_impls() {
+        let cases = vec![
+            "impl Foo { fn bar(self) -> Field { self.x } }",
+            "impl Field { fn double(self) -> Field { self * 2 } }",
+            "impl<T> [T] { fn first(mut self) -> T { self[0] } }",
+            "impl u32 { fn is_zero(self, _other: u32) -> bool { self == 0 } }",
+        ];
+        parse_all(implementation(), cases);
+
+        let failing = vec!["impl { }", "impl<> Foo { }", "impl Foo { fn bar(self mut) {} }"];
+        parse_all_failing(implementation(), failing);
+    }
+
     #[test]
     fn parse_member_access() {
         let cases = vec!["a.b", "a + b.c", "foo.bar as i32"];
@@ -14,10 +14,14 @@ pub fn compile(src: String) -> JsValue {
 }
 // Deserialises bytes into ACIR structure
 #[wasm_bindgen]
-pub fn acir_from_bytes(bytes: Vec<u8>) -> JsValue {
+pub fn acir_from_bytes(bytes: Vec<u8>) -> Result<JsValue, JsValue> {
     console_error_panic_hook::set_once();
-    let circuit = Circuit::from_bytes(&bytes);
-    JsValue::from_serde(&circuit).unwrap()
+    let circuit = Circuit::from_bytes(&bytes).map_err(|err| JsValue::from_str(&err.to_string()))?;
+    circuit.validate().map_err(|errors| {
+        let errors: Vec<_> = errors.iter().map(ToString::to_string).collect();
+        JsValue::from_str(&errors.join("\n"))
+    })?;
+    Ok(JsValue::from_serde(&circuit).unwrap())
 }
 
 #[wasm_bindgen]